@@ -61,6 +61,10 @@ pub struct DebugExportTopDownEventsArgs {
     #[arg(long)]
     pub end_block_height: u64,
 
+    /// The number of blocks to query concurrently.
+    #[arg(long, default_value = "8")]
+    pub concurrency: usize,
+
     /// Location of the JSON file to write events to.
     #[arg(long)]
     pub events_file: PathBuf,
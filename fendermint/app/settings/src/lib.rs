@@ -25,6 +25,7 @@ use self::fvm::FvmSettings;
 use self::resolver::ResolverSettings;
 use ipc_observability::config::TracingSettings;
 use ipc_provider::config::deserialize::deserialize_eth_address_from_str;
+use ipc_provider::notify::NotifyConfig;
 
 pub mod eth;
 pub mod fvm;
@@ -172,6 +173,10 @@ pub struct TopDownSettings {
     pub max_proposal_range: BlockHeight,
     /// The max number of blocks to hold in memory for parent syncer
     pub max_cache_blocks: Option<BlockHeight>,
+    /// The max number of parent blocks fetched concurrently while catching up with the parent
+    pub max_parallel_fetches: Option<usize>,
+    /// Webhooks and commands notified of parent reorgs, stalls and finalized deposits
+    pub notify: Option<NotifyConfig>,
     /// Parent syncing cron period, in seconds
     #[serde_as(as = "DurationSeconds<u64>")]
     pub polling_interval: Duration,
@@ -49,6 +49,12 @@ async fn export_topdown_events(args: &DebugExportTopDownEventsArgs) -> anyhow::R
                 auth_token: args.parent_auth_token.clone(),
                 registry_addr: args.parent_registry,
                 gateway_addr: args.parent_gateway,
+                fee_policy: None,
+                keystore_path: None,
+                proxy: None,
+                tls: None,
+                auth: None,
+                pool: None,
             }),
         },
     )?;
@@ -60,6 +66,7 @@ async fn export_topdown_events(args: &DebugExportTopDownEventsArgs) -> anyhow::R
         &parent_proxy,
         args.start_block_height,
         args.end_block_height,
+        args.concurrency,
     )
     .await
     .context("failed to fetch topdown events")?;
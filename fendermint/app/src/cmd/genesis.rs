@@ -335,6 +335,12 @@ async fn new_genesis_from_parent(
                 auth_token: args.parent_auth_token.clone(),
                 registry_addr: args.parent_registry,
                 gateway_addr: args.parent_gateway,
+                fee_policy: None,
+                keystore_path: None,
+                proxy: None,
+                tls: None,
+                auth: None,
+                pool: None,
             }),
         },
     )?;
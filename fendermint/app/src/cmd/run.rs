@@ -257,6 +257,19 @@ async fn run(settings: Settings) -> anyhow::Result<()> {
             config = config.with_max_cache_blocks(v);
         }
 
+        if let Some(v) = topdown_config.max_parallel_fetches {
+            info!(value = v, "setting max parallel fetches");
+            config = config.with_max_parallel_fetches(v);
+        }
+
+        if let Some(notify) = topdown_config.notify.clone() {
+            info!(
+                webhooks = notify.webhooks.len(),
+                "setting top-down notifications"
+            );
+            config = config.with_notify(notify);
+        }
+
         let ipc_provider = {
             let p = make_ipc_provider_proxy(&settings)?;
             Arc::new(IPCProviderProxyWithLatency::new(p))
@@ -439,6 +452,12 @@ fn make_ipc_provider_proxy(settings: &Settings) -> anyhow::Result<IPCProviderPro
             auth_token: topdown_config.parent_http_auth_token.as_ref().cloned(),
             registry_addr: topdown_config.parent_registry,
             gateway_addr: topdown_config.parent_gateway,
+            fee_policy: None,
+            keystore_path: None,
+            proxy: None,
+            tls: None,
+            auth: None,
+            pool: None,
         }),
     };
     info!("init ipc provider with subnet: {}", subnet.id);
@@ -23,6 +23,7 @@ use fendermint_vm_genesis::{
 use fs_err as fs;
 use fvm_shared::{bigint::Zero, chainid::ChainID, econ::TokenAmount, version::NetworkVersion};
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::config::migrate::CONFIG_VERSION;
 use ipc_provider::config::subnet::{
     EVMSubnet, Subnet as IpcCliSubnet, SubnetConfig as IpcCliSubnetConfig,
 };
@@ -315,7 +316,10 @@ impl DockerMaterializer {
 
         let mut config = if !file_name.exists() {
             IpcCliConfig {
+                version: CONFIG_VERSION,
                 keystore_path: Some("~/.ipc".to_string()),
+                proxy: None,
+                approvals: None,
                 subnets: Default::default(),
             }
         } else {
@@ -519,6 +523,12 @@ impl DockerMaterializer {
                     auth_token: None,
                     registry_addr: submit_config.deployment.registry.into(),
                     gateway_addr: submit_config.deployment.gateway.into(),
+                    fee_policy: None,
+                    keystore_path: None,
+                    proxy: None,
+                    tls: None,
+                    auth: None,
+                    pool: None,
                 }),
             })
         })
@@ -1056,6 +1066,7 @@ mod tests {
     use fendermint_vm_actor_interface::ipc;
     use fvm_shared::address::Address;
     use ipc_api::subnet_id::SubnetID;
+    use ipc_provider::config::migrate::CONFIG_VERSION;
     use ipc_provider::config::subnet::{
         EVMSubnet, Subnet as IpcCliSubnet, SubnetConfig as IpcCliSubnetConfig,
     };
@@ -1068,7 +1079,10 @@ mod tests {
     #[test]
     fn test_ipc_cli_config_toml_roundtrip() {
         let mut config0 = IpcCliConfig {
+            version: CONFIG_VERSION,
             keystore_path: Some("~/.ipc".to_string()),
+            proxy: None,
+            approvals: None,
             subnets: Default::default(),
         };
 
@@ -1080,6 +1094,12 @@ mod tests {
                 auth_token: None,
                 registry_addr: ipc::SUBNETREGISTRY_ACTOR_ADDR,
                 gateway_addr: ipc::GATEWAY_ACTOR_ADDR,
+                fee_policy: None,
+                keystore_path: None,
+                proxy: None,
+                tls: None,
+                auth: None,
+                pool: None,
             }),
         });
 
@@ -15,4 +15,6 @@ pub enum Error {
     ParentChainReorgDetected,
     #[error("Cannot query parent at height {1}: {0}")]
     CannotQueryParent(String, BlockHeight),
+    #[error("Top down message at height {2} has nonce {1}, expected {0}")]
+    NonSequentialTopDownNonce(u64, u64, BlockHeight),
 }
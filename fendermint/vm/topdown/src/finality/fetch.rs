@@ -347,6 +347,8 @@ mod tests {
             max_proposal_range: Some(1),
             max_cache_blocks: None,
             proposal_delay: None,
+            max_parallel_fetches: None,
+            notify: None,
         };
         let genesis_epoch = blocks.lower_bound().unwrap();
         let proxy = Arc::new(TestParentProxy { blocks });
@@ -110,6 +110,8 @@ mod tests {
             max_proposal_range: None,
             max_cache_blocks: None,
             proposal_delay: None,
+            max_parallel_fetches: None,
+            notify: None,
         };
 
         CachedFinalityProvider::new(config, 10, Some(genesis_finality()), mocked_agent_proxy())
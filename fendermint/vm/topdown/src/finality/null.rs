@@ -385,6 +385,8 @@ mod tests {
             max_proposal_range: Some(6),
             max_cache_blocks: None,
             proposal_delay: Some(2),
+            max_parallel_fetches: None,
+            notify: None,
         };
         let committed_finality = IPCParentFinality {
             height: blocks[0].0,
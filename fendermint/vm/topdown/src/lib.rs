@@ -19,6 +19,7 @@ use ethers::utils::hex;
 use fvm_shared::clock::ChainEpoch;
 use ipc_api::cross::IpcEnvelope;
 use ipc_api::staking::StakingChangeRequest;
+use ipc_provider::notify::NotifyConfig;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::time::Duration;
@@ -38,6 +39,7 @@ pub(crate) const NULL_ROUND_ERR_MSG: &str = "requested epoch was a null round";
 pub(crate) const DEFAULT_MAX_PROPOSAL_RANGE: BlockHeight = 100;
 pub(crate) const DEFAULT_MAX_CACHE_BLOCK: BlockHeight = 500;
 pub(crate) const DEFAULT_PROPOSAL_DELAY: BlockHeight = 2;
+pub(crate) const DEFAULT_MAX_PARALLEL_FETCHES: usize = 1;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -57,6 +59,10 @@ pub struct Config {
     /// Max number of blocks that should be stored in cache
     pub max_cache_blocks: Option<BlockHeight>,
     pub proposal_delay: Option<BlockHeight>,
+    /// Max number of parent heights fetched concurrently while catching up
+    pub max_parallel_fetches: Option<usize>,
+    /// Where reorgs, stalls and finalized deposits seen by the syncer are reported
+    pub notify: Option<NotifyConfig>,
 }
 
 impl Config {
@@ -74,6 +80,8 @@ impl Config {
             max_proposal_range: None,
             max_cache_blocks: None,
             proposal_delay: None,
+            max_parallel_fetches: None,
+            notify: None,
         }
     }
 
@@ -92,6 +100,16 @@ impl Config {
         self
     }
 
+    pub fn with_max_parallel_fetches(mut self, max_parallel_fetches: usize) -> Self {
+        self.max_parallel_fetches = Some(max_parallel_fetches);
+        self
+    }
+
+    pub fn with_notify(mut self, notify: NotifyConfig) -> Self {
+        self.notify = Some(notify);
+        self
+    }
+
     pub fn max_proposal_range(&self) -> BlockHeight {
         self.max_proposal_range
             .unwrap_or(DEFAULT_MAX_PROPOSAL_RANGE)
@@ -104,6 +122,12 @@ impl Config {
     pub fn max_cache_blocks(&self) -> BlockHeight {
         self.max_cache_blocks.unwrap_or(DEFAULT_MAX_CACHE_BLOCK)
     }
+
+    pub fn max_parallel_fetches(&self) -> usize {
+        self.max_parallel_fetches
+            .unwrap_or(DEFAULT_MAX_PARALLEL_FETCHES)
+            .max(1)
+    }
 }
 
 /// The finality view for IPC parent at certain height.
@@ -12,13 +12,21 @@ use crate::{
 use anyhow::anyhow;
 use async_stm::{atomically, atomically_or_err, StmError};
 use ethers::utils::hex;
-use libp2p::futures::TryFutureExt;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::cross::IpcEnvelope;
+use ipc_provider::notify::{Notification, Notifier};
+use libp2p::futures::{stream, StreamExt, TryFutureExt};
+use std::cmp::min;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::instrument;
 
 use crate::observe::ParentFinalityAcquired;
 use ipc_observability::{emit, serde::HexEncodableBlockHash};
 
+/// How long the parent chain head may stay put before the syncer reports a stall.
+const SYNC_STALL_TIMEOUT: Duration = Duration::from_secs(600);
+
 /// Parent syncer that constantly poll parent. This struct handles lotus null blocks and deferred
 /// execution. For ETH based parent, it should work out of the box as well.
 pub(crate) struct LotusParentSyncer<T, P> {
@@ -35,6 +43,12 @@ pub(crate) struct LotusParentSyncer<T, P> {
     /// the polling frequence to where it's impractical after
     /// we have caught up.
     sync_many: bool,
+
+    notifier: Option<Notifier>,
+    /// The highest final parent height seen, and when it was first seen.
+    progress: (BlockHeight, Instant),
+    /// Whether the current stall was already reported.
+    stall_notified: bool,
 }
 
 impl<T, P> LotusParentSyncer<T, P>
@@ -49,6 +63,11 @@ where
         vote_tally: VoteTally,
         query: Arc<T>,
     ) -> anyhow::Result<Self> {
+        let notifier = config
+            .notify
+            .clone()
+            .filter(|n| !n.is_empty())
+            .map(Notifier::new);
         Ok(Self {
             config,
             parent_proxy,
@@ -56,6 +75,9 @@ where
             vote_tally,
             query,
             sync_many: true,
+            notifier,
+            progress: (0, Instant::now()),
+            stall_notified: false,
         })
     }
 
@@ -66,6 +88,7 @@ where
         } else {
             return Ok(());
         };
+        self.check_stalled(chain_head);
 
         let (mut latest_height_fetched, mut first_non_null_parent_hash) =
             self.latest_cached_data().await;
@@ -77,6 +100,9 @@ where
                 latest_height_fetched,
                 "chain head went backwards, potential reorg detected from height"
             );
+            self.notify(Notification::ReorgDetected {
+                parent_height: chain_head as ChainEpoch,
+            });
             return self.reset().await;
         }
 
@@ -89,31 +115,58 @@ where
             return Ok(());
         }
 
-        loop {
-            if self.exceed_cache_size_limit().await {
-                tracing::debug!("exceeded cache size limit");
-                break;
-            }
+        // Heights are fetched concurrently, up to `max_parallel_fetches` at a time, but are
+        // added to the cache in order, so reorgs are still detected by comparing parent hashes.
+        let window = if self.sync_many {
+            self.config.max_parallel_fetches()
+        } else {
+            1
+        };
 
-            first_non_null_parent_hash = match self
-                .poll_next(latest_height_fetched + 1, first_non_null_parent_hash)
-                .await
-            {
-                Ok(h) => h,
-                Err(Error::ParentChainReorgDetected) => {
-                    tracing::warn!("potential reorg detected, clear cache and retry");
-                    self.reset().await?;
-                    break;
+        'sync: loop {
+            let start = latest_height_fetched + 1;
+            let end = min(chain_head, latest_height_fetched + window as BlockHeight);
+            let parent_proxy = self.parent_proxy.as_ref();
+            let mut blocks = stream::iter(start..=end)
+                .map(|height| fetch_block(parent_proxy, height))
+                .buffered(window);
+
+            while let Some(block) = blocks.next().await {
+                if self.exceed_cache_size_limit().await {
+                    tracing::debug!("exceeded cache size limit");
+                    break 'sync;
                 }
-                Err(e) => return Err(anyhow!(e)),
-            };
 
-            latest_height_fetched += 1;
+                let height = latest_height_fetched + 1;
+                let applied = match block {
+                    Ok(block) => {
+                        self.apply_block(height, first_non_null_parent_hash, block)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                first_non_null_parent_hash = match applied {
+                    Ok(h) => h,
+                    Err(Error::ParentChainReorgDetected) => {
+                        tracing::warn!("potential reorg detected, clear cache and retry");
+                        self.notify(Notification::ReorgDetected {
+                            parent_height: height as ChainEpoch,
+                        });
+                        self.reset().await?;
+                        break 'sync;
+                    }
+                    Err(e) => return Err(anyhow!(e)),
+                };
 
-            if latest_height_fetched == chain_head {
-                tracing::debug!("reached the tip of the chain");
-                break;
-            } else if !self.sync_many {
+                latest_height_fetched = height;
+
+                if latest_height_fetched == chain_head {
+                    tracing::debug!("reached the tip of the chain");
+                    break 'sync;
+                }
+            }
+
+            if !self.sync_many {
                 break;
             }
         }
@@ -127,6 +180,33 @@ where
     T: ParentFinalityStateQuery + Send + Sync + 'static,
     P: ParentQueryProxy + Send + Sync + 'static,
 {
+    fn notify(&self, notification: Notification) {
+        if let Some(notifier) = &self.notifier {
+            notifier.notify(notification);
+        }
+    }
+
+    /// Reports a stall once the final parent height has not moved for [`SYNC_STALL_TIMEOUT`].
+    fn check_stalled(&mut self, chain_head: BlockHeight) {
+        if chain_head > self.progress.0 {
+            self.progress = (chain_head, Instant::now());
+            self.stall_notified = false;
+            return;
+        }
+        let stalled = self.progress.1.elapsed();
+        if stalled >= SYNC_STALL_TIMEOUT && !self.stall_notified {
+            tracing::warn!(
+                chain_head,
+                "parent chain head did not advance in {stalled:?}"
+            );
+            self.stall_notified = true;
+            self.notify(Notification::SyncStalled {
+                parent_height: chain_head as ChainEpoch,
+                stalled_secs: stalled.as_secs(),
+            });
+        }
+    }
+
     async fn exceed_cache_size_limit(&self) -> bool {
         let max_cache_blocks = self.config.max_cache_blocks();
         atomically(|| self.provider.cached_blocks()).await > max_cache_blocks
@@ -174,70 +254,60 @@ where
         .await
     }
 
-    /// Poll the next block height. Returns finalized and executed block data.
-    async fn poll_next(
-        &mut self,
+    /// Adds the block fetched at `height` to the cache. Returns the hash of the last non-null
+    /// block, to check the parent hash of the next block against.
+    async fn apply_block(
+        &self,
         height: BlockHeight,
         parent_block_hash: BlockHash,
+        block: Option<ParentBlock>,
     ) -> Result<BlockHash, Error> {
         tracing::debug!(
             height,
             parent_block_hash = hex::encode(&parent_block_hash),
-            "polling height with parent hash"
+            "applying height with parent hash"
         );
 
-        let block_hash_res = match self.parent_proxy.get_block_hash(height).await {
-            Ok(res) => res,
-            Err(e) => {
-                let err = e.to_string();
-                if is_null_round_str(&err) {
-                    tracing::debug!(
-                        height,
-                        "detected null round at height, inserted None to cache"
-                    );
+        let Some((block_parent_hash, data)) = block else {
+            tracing::debug!(
+                height,
+                "detected null round at height, inserted None to cache"
+            );
 
-                    atomically_or_err::<_, Error, _>(|| {
-                        self.provider.new_parent_view(height, None)?;
-                        self.vote_tally
-                            .add_block(height, None)
-                            .map_err(map_voting_err)?;
-                        Ok(())
-                    })
-                    .await?;
-
-                    emit(ParentFinalityAcquired {
-                        source: "Parent syncer",
-                        is_null: true,
-                        block_height: height,
-                        block_hash: None,
-                        commitment_hash: None,
-                        num_msgs: 0,
-                        num_validator_changes: 0,
-                    });
-
-                    // Null block received, no block hash for the current height being polled.
-                    // Return the previous parent hash as the non-null block hash.
-                    return Ok(parent_block_hash);
-                }
-                return Err(Error::CannotQueryParent(
-                    format!("get_block_hash: {e}"),
-                    height,
-                ));
-            }
+            atomically_or_err::<_, Error, _>(|| {
+                self.provider.new_parent_view(height, None)?;
+                self.vote_tally
+                    .add_block(height, None)
+                    .map_err(map_voting_err)?;
+                Ok(())
+            })
+            .await?;
+
+            emit(ParentFinalityAcquired {
+                source: "Parent syncer",
+                is_null: true,
+                block_height: height,
+                block_hash: None,
+                commitment_hash: None,
+                num_msgs: 0,
+                num_validator_changes: 0,
+            });
+
+            // Null block received, no block hash for the current height being polled.
+            // Return the previous parent hash as the non-null block hash.
+            return Ok(parent_block_hash);
         };
 
-        if block_hash_res.parent_block_hash != parent_block_hash {
+        if block_parent_hash != parent_block_hash {
             tracing::warn!(
                 height,
-                parent_hash = hex::encode(&block_hash_res.parent_block_hash),
+                parent_hash = hex::encode(&block_parent_hash),
                 previous_hash = hex::encode(&parent_block_hash),
                 "parent block hash diff than previous hash",
             );
             return Err(Error::ParentChainReorgDetected);
         }
 
-        let data = self.fetch_data(height, block_hash_res.block_hash).await?;
-
         tracing::debug!(
             height,
             staking_requests = data.1.len(),
@@ -269,15 +339,14 @@ where
             num_validator_changes: data.1.len(),
         });
 
-        Ok(data.0)
-    }
+        if !data.2.is_empty() {
+            self.notify(Notification::DepositFinalized {
+                parent_height: height as ChainEpoch,
+                messages: data.2.len(),
+            });
+        }
 
-    async fn fetch_data(
-        &self,
-        height: BlockHeight,
-        block_hash: BlockHash,
-    ) -> Result<ParentViewPayload, Error> {
-        fetch_data(self.parent_proxy.as_ref(), height, block_hash).await
+        Ok(data.0)
     }
 
     async fn finalized_chain_head(&self) -> anyhow::Result<Option<BlockHeight>> {
@@ -315,6 +384,44 @@ fn map_voting_err(e: StmError<voting::Error>) -> StmError<Error> {
     }
 }
 
+/// The parent hash and the data of a non-null parent block, `None` for a null round.
+type ParentBlock = (BlockHash, ParentViewPayload);
+
+/// Fetches the block at `height` and its top-down data, without touching the cache.
+async fn fetch_block<P>(parent_proxy: &P, height: BlockHeight) -> Result<Option<ParentBlock>, Error>
+where
+    P: ParentQueryProxy + Send + Sync + 'static,
+{
+    match parent_proxy.get_block_hash(height).await {
+        Ok(res) => {
+            let data = fetch_data(parent_proxy, height, res.block_hash).await?;
+            Ok(Some((res.parent_block_hash, data)))
+        }
+        Err(e) if is_null_round_str(&e.to_string()) => Ok(None),
+        Err(e) => Err(Error::CannotQueryParent(
+            format!("get_block_hash: {e}"),
+            height,
+        )),
+    }
+}
+
+/// Checks that the top-down messages follow each other without gaps, starting after `prev`,
+/// and returns the nonce of the last one.
+fn check_nonces(
+    prev: Option<u64>,
+    height: BlockHeight,
+    msgs: &[IpcEnvelope],
+) -> Result<Option<u64>, Error> {
+    msgs.iter().try_fold(prev, |prev, msg| match prev {
+        Some(prev) if msg.local_nonce != prev + 1 => Err(Error::NonSequentialTopDownNonce(
+            prev + 1,
+            msg.local_nonce,
+            height,
+        )),
+        _ => Ok(Some(msg.local_nonce)),
+    })
+}
+
 #[instrument(skip(parent_proxy))]
 async fn fetch_data<P>(
     parent_proxy: &P,
@@ -355,38 +462,36 @@ where
         return Err(Error::ParentChainReorgDetected);
     }
 
+    check_nonces(None, height, &topdown_msgs_res.value)?;
+
     Ok((block_hash, changes_res.value, topdown_msgs_res.value))
 }
 
+/// Fetches the top-down events in the range, up to `concurrency` heights at a time, and checks
+/// that the nonces of the messages are continuous across heights.
 pub async fn fetch_topdown_events<P>(
     parent_proxy: &P,
     start_height: BlockHeight,
     end_height: BlockHeight,
+    concurrency: usize,
 ) -> Result<Vec<(BlockHeight, ParentViewPayload)>, Error>
 where
     P: ParentQueryProxy + Send + Sync + 'static,
 {
+    let mut blocks = stream::iter(start_height..=end_height)
+        .map(|height| async move { (height, fetch_block(parent_proxy, height).await) })
+        .buffered(concurrency.max(1));
+
     let mut events = Vec::new();
-    for height in start_height..=end_height {
-        match parent_proxy.get_block_hash(height).await {
-            Ok(res) => {
-                let (block_hash, changes, msgs) =
-                    fetch_data(parent_proxy, height, res.block_hash).await?;
-
-                if !(changes.is_empty() && msgs.is_empty()) {
-                    events.push((height, (block_hash, changes, msgs)));
-                }
-            }
-            Err(e) => {
-                if is_null_round_str(&e.to_string()) {
-                    continue;
-                } else {
-                    return Err(Error::CannotQueryParent(
-                        format!("get_block_hash: {e}"),
-                        height,
-                    ));
-                }
-            }
+    let mut last_nonce = None;
+    while let Some((height, block)) = blocks.next().await {
+        let Some((_, (block_hash, changes, msgs))) = block? else {
+            continue;
+        };
+        last_nonce = check_nonces(last_nonce, height, &msgs)?;
+
+        if !(changes.is_empty() && msgs.is_empty()) {
+            events.push((height, (block_hash, changes, msgs)));
         }
     }
     Ok(events)
@@ -395,19 +500,22 @@ where
 #[cfg(test)]
 mod tests {
     use crate::proxy::ParentQueryProxy;
-    use crate::sync::syncer::LotusParentSyncer;
+    use crate::sync::syncer::{check_nonces, LotusParentSyncer};
     use crate::sync::ParentFinalityStateQuery;
     use crate::voting::VoteTally;
     use crate::{
-        BlockHash, BlockHeight, CachedFinalityProvider, Config, IPCParentFinality,
+        BlockHash, BlockHeight, CachedFinalityProvider, Config, Error, IPCParentFinality,
         SequentialKeyCache, Toggle, NULL_ROUND_ERR_MSG,
     };
     use anyhow::anyhow;
     use async_stm::atomically;
     use async_trait::async_trait;
     use fendermint_vm_genesis::{Power, Validator};
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
     use ipc_api::cross::IpcEnvelope;
     use ipc_api::staking::StakingChangeRequest;
+    use ipc_api::subnet_id::SubnetID;
     use ipc_provider::manager::{GetBlockHashResult, TopDownQueryPayload};
     use std::sync::Arc;
 
@@ -493,6 +601,8 @@ mod tests {
             max_proposal_range: Some(1),
             max_cache_blocks: None,
             proposal_delay: None,
+            max_parallel_fetches: None,
+            notify: None,
         };
         let genesis_epoch = blocks.lower_bound().unwrap();
         let proxy = Arc::new(TestParentProxy { blocks });
@@ -593,4 +703,58 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn parallel_catch_up() {
+        let parent_blocks = new_parent_blocks!(
+            100 => Some(vec![0; 32]),   // genesis block
+            101 => Some(vec![1; 32]),
+            102 => None,
+            103 => Some(vec![3; 32]),
+            104 => Some(vec![4; 32]),
+            105 => None,
+            106 => Some(vec![6; 32]),
+            107 => Some(vec![7; 32]),
+            108 => Some(vec![8; 32]),
+            109 => Some(vec![9; 32])    // chain head
+        );
+
+        let mut syncer = new_syncer(parent_blocks, true).await;
+        syncer.config.max_parallel_fetches = Some(3);
+
+        // everything up to the finalized chain head is fetched in a single round
+        syncer.sync().await.unwrap();
+        assert_eq!(
+            atomically(|| syncer.provider.latest_height()).await,
+            Some(109 - FINALITY_DELAY)
+        );
+    }
+
+    #[test]
+    fn top_down_nonce_gaps() {
+        let subnet_id = SubnetID::new(10, vec![Address::new_id(1000)]);
+        let msgs = |nonces: &[u64]| {
+            nonces
+                .iter()
+                .map(|n| {
+                    let mut msg = IpcEnvelope::new_fund_msg(
+                        &subnet_id,
+                        &Address::new_id(1),
+                        &Address::new_id(2),
+                        TokenAmount::from_atto(100),
+                    )
+                    .unwrap();
+                    msg.local_nonce = *n;
+                    msg
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(check_nonces(None, 1, &msgs(&[4, 5, 6])), Ok(Some(6)));
+        assert_eq!(check_nonces(Some(6), 2, &msgs(&[])), Ok(Some(6)));
+        assert_eq!(
+            check_nonces(Some(6), 3, &msgs(&[8])),
+            Err(Error::NonSequentialTopDownNonce(7, 8, 3))
+        );
+    }
 }
@@ -6,8 +6,11 @@ use crate::address::IPCAddress;
 use crate::subnet_id::SubnetID;
 use crate::HumanReadable;
 use anyhow::anyhow;
+use ethers::abi::Tokenize;
+use ethers::utils::keccak256;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
+use ipc_actors_abis::lib_gateway;
 use serde::{Deserialize, Serialize};
 use serde_tuple::{Deserialize_tuple, Serialize_tuple};
 use serde_with::serde_as;
@@ -110,6 +113,21 @@ impl IpcEnvelope {
         }
         Ok(IPCMsgType::TopDown)
     }
+
+    /// The id the gateway traces the message by in its events, computed as `toTracingId` in
+    /// Solidity from the fields that stay the same along the route of the message.
+    pub fn tracing_id(&self) -> anyhow::Result<[u8; 32]> {
+        let msg = lib_gateway::IpcEnvelope::try_from(self.clone())?;
+        let fields = (
+            msg.kind,
+            msg.to,
+            msg.from,
+            msg.value,
+            msg.message,
+            msg.original_nonce,
+        );
+        Ok(keccak256(ethers::abi::encode(&fields.into_tokens())))
+    }
 }
 
 /// Type of cross-net messages currently supported
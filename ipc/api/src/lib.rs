@@ -20,6 +20,8 @@ pub mod validator;
 
 pub mod evm;
 pub mod merkle;
+pub mod pubkey;
+pub mod quorum;
 pub mod staking;
 
 /// Converts an ethers::U256 TokenAmount into a FIL amount.
@@ -0,0 +1,102 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Conversions from a secp256k1 public key to the addresses it controls across the stack.
+
+use anyhow::anyhow;
+use ethers::prelude::k256;
+use ethers::prelude::k256::elliptic_curve::sec1::ToEncodedPoint;
+use ethers::utils::keccak256;
+use fvm_shared::address::Address;
+use ipc_types::EthAddress;
+
+/// The addresses derived from a single secp256k1 public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKeyAddresses {
+    /// The uncompressed SEC1 encoding of the key, `0x04 || x || y`.
+    pub public_key: [u8; 65],
+    /// The EVM address, the last 20 bytes of the keccak256 hash of `x || y`.
+    pub eth: ethers::types::Address,
+    /// The f410 address delegated to the EAM for the EVM address.
+    pub delegated: Address,
+    /// The f1 address of the key.
+    pub secp256k1: Address,
+}
+
+/// Parses a secp256k1 public key given as 65 uncompressed bytes, 33 compressed bytes or
+/// 32 x-only bytes, and returns its uncompressed encoding. An x-only key is lifted to the
+/// point with the even y coordinate, as BIP340 does.
+pub fn uncompressed_public_key(bytes: &[u8]) -> anyhow::Result<[u8; 65]> {
+    let sec1 = match bytes.len() {
+        32 => [&[0x02u8][..], bytes].concat(),
+        33 | 65 => bytes.to_vec(),
+        n => return Err(anyhow!("invalid secp256k1 public key length: {n}")),
+    };
+    let key = k256::PublicKey::from_sec1_bytes(&sec1)
+        .map_err(|e| anyhow!("invalid secp256k1 public key: {e}"))?;
+
+    let mut out = [0u8; 65];
+    out.copy_from_slice(key.to_encoded_point(false).as_bytes());
+    Ok(out)
+}
+
+/// Returns the EVM address controlled by a secp256k1 public key in any of the encodings
+/// accepted by [`uncompressed_public_key`].
+pub fn public_key_to_eth_address(bytes: &[u8]) -> anyhow::Result<ethers::types::Address> {
+    let pk = uncompressed_public_key(bytes)?;
+    let hash = keccak256(&pk[1..]);
+    Ok(ethers::types::Address::from_slice(&hash[12..]))
+}
+
+impl PublicKeyAddresses {
+    /// Derives all the addresses of a public key in any of the encodings accepted by
+    /// [`uncompressed_public_key`].
+    pub fn from_public_key(bytes: &[u8]) -> anyhow::Result<Self> {
+        let public_key = uncompressed_public_key(bytes)?;
+        let eth = public_key_to_eth_address(&public_key)?;
+        Ok(Self {
+            public_key,
+            eth,
+            delegated: Address::from(EthAddress(eth.0)),
+            secp256k1: Address::new_secp256k1(&public_key)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pubkey::PublicKeyAddresses;
+    use ethers::utils::hex;
+    use fvm_shared::address::Protocol;
+    use std::str::FromStr;
+
+    // The generator point, i.e. the public key of the secret key `1`.
+    const COMPRESSED: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn test_public_key_addresses() {
+        let compressed = hex::decode(COMPRESSED).unwrap();
+        let uncompressed = hex::decode(UNCOMPRESSED).unwrap();
+
+        let addresses = PublicKeyAddresses::from_public_key(&uncompressed).unwrap();
+        assert_eq!(addresses.public_key.to_vec(), uncompressed);
+        assert_eq!(
+            addresses.eth,
+            ethers::types::Address::from_str("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf").unwrap()
+        );
+        assert_eq!(addresses.delegated.protocol(), Protocol::Delegated);
+        assert_eq!(addresses.secp256k1.protocol(), Protocol::Secp256k1);
+
+        assert_eq!(
+            PublicKeyAddresses::from_public_key(&compressed).unwrap(),
+            addresses
+        );
+        assert_eq!(
+            PublicKeyAddresses::from_public_key(&compressed[1..]).unwrap(),
+            addresses
+        );
+
+        assert!(PublicKeyAddresses::from_public_key(&compressed[2..]).is_err());
+        assert!(PublicKeyAddresses::from_public_key(&[0u8; 33]).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Quorum certificates of bottom-up checkpoints, verifiable without trusting an RPC endpoint.
+
+use crate::checkpoint::{BottomUpCheckpoint, BottomUpCheckpointBundle, QuorumReachedEvent};
+use crate::evm::payload_to_evm_address;
+use crate::validator::Validator;
+use crate::HumanReadable;
+use anyhow::anyhow;
+use ethers::abi::Tokenize;
+use ethers::types::H256;
+use ethers::utils::keccak256;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use ipc_actors_abis::checkpointing_facet;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::collections::HashSet;
+
+/// A bottom-up checkpoint together with the signatures that made it reach quorum in the child
+/// subnet. Anyone holding the power table of the subnet can check that the signatories carried
+/// at least two thirds of the voting power.
+#[serde_as]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct QuorumCertificate {
+    pub checkpoint: BottomUpCheckpoint,
+    /// The hash the validators signed, as reported by the quorum reached event.
+    #[serde_as(as = "HumanReadable")]
+    pub hash: Vec<u8>,
+    /// The weight the gateway counted when the quorum was reached.
+    pub quorum_weight: TokenAmount,
+    #[serde_as(as = "Vec<HumanReadable>")]
+    pub signatures: Vec<Vec<u8>>,
+    pub signatories: Vec<Address>,
+}
+
+impl QuorumCertificate {
+    /// Builds the certificate of a checkpoint from its signature bundle and the quorum reached
+    /// event emitted for it.
+    pub fn new(
+        event: QuorumReachedEvent,
+        bundle: BottomUpCheckpointBundle,
+    ) -> anyhow::Result<Self> {
+        if event.height != bundle.checkpoint.block_height {
+            return Err(anyhow!(
+                "quorum reached at height {} but the checkpoint is at height {}",
+                event.height,
+                bundle.checkpoint.block_height
+            ));
+        }
+        Ok(Self {
+            checkpoint: bundle.checkpoint,
+            hash: event.obj_hash,
+            quorum_weight: event.quorum_weight,
+            signatures: bundle.signatures,
+            signatories: bundle.signatories,
+        })
+    }
+
+    pub fn height(&self) -> ChainEpoch {
+        self.checkpoint.block_height
+    }
+
+    /// Verifies that the certificate is for its checkpoint and that every signature was made by
+    /// its signatory over the checkpoint hash, and that the signatories hold at least two thirds
+    /// of the weight in `power_table` and no less than the weight the gateway counted. Returns
+    /// the weight of the signatories.
+    pub fn verify(&self, power_table: &[Validator]) -> anyhow::Result<TokenAmount> {
+        let hash = checkpoint_hash(&self.checkpoint)?;
+        if hash.as_slice() != self.hash.as_slice() {
+            return Err(anyhow!("certificate hash does not match the checkpoint"));
+        }
+        if self.signatures.len() != self.signatories.len() {
+            return Err(anyhow!(
+                "{} signatures for {} signatories",
+                self.signatures.len(),
+                self.signatories.len()
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut weight = TokenAmount::default();
+        for (signature, signatory) in self.signatures.iter().zip(&self.signatories) {
+            if !seen.insert(signatory) {
+                return Err(anyhow!("{signatory} signed more than once"));
+            }
+            let validator = power_table
+                .iter()
+                .find(|v| &v.addr == signatory)
+                .ok_or_else(|| anyhow!("{signatory} is not in the power table"))?;
+
+            let signature = ethers::types::Signature::try_from(signature.as_slice())
+                .map_err(|e| anyhow!("invalid signature of {signatory}: {e}"))?;
+            let signer = signature
+                .recover(H256(hash))
+                .map_err(|e| anyhow!("cannot recover the signer of {signatory}: {e}"))?;
+            if signer != payload_to_evm_address(signatory.payload())? {
+                return Err(anyhow!("signature of {signatory} was made by {signer:?}"));
+            }
+
+            weight += &validator.weight;
+        }
+
+        let total = power_table
+            .iter()
+            .fold(TokenAmount::default(), |acc, v| acc + &v.weight);
+        if total.is_zero() {
+            return Err(anyhow!("the power table has no weight"));
+        }
+        if weight.atto() * 3 < total.atto() * 2 {
+            return Err(anyhow!(
+                "signatories hold {weight} of {total}, less than two thirds"
+            ));
+        }
+        if weight < self.quorum_weight {
+            return Err(anyhow!(
+                "signatories hold {weight}, less than the quorum weight {}",
+                self.quorum_weight
+            ));
+        }
+
+        Ok(weight)
+    }
+}
+
+/// The hash validators sign for a checkpoint: keccak256 of its ABI encoding, as in Solidity.
+pub fn checkpoint_hash(checkpoint: &BottomUpCheckpoint) -> anyhow::Result<[u8; 32]> {
+    let checkpoint = checkpointing_facet::BottomUpCheckpoint::try_from(checkpoint.clone())?;
+    Ok(keccak256(ethers::abi::encode(&(checkpoint,).into_tokens())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkpoint::{consensus, BottomUpCheckpoint, CompressedActivityRollup};
+    use crate::ethers_address_to_fil_address;
+    use crate::quorum::{checkpoint_hash, QuorumCertificate};
+    use crate::subnet_id::SubnetID;
+    use crate::validator::Validator;
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::H256;
+    use fvm_shared::econ::TokenAmount;
+
+    fn checkpoint() -> BottomUpCheckpoint {
+        BottomUpCheckpoint {
+            subnet_id: SubnetID::new(123, vec![]),
+            block_height: 10,
+            block_hash: vec![1; 32],
+            next_configuration_number: 0,
+            msgs: vec![],
+            activity_rollup: CompressedActivityRollup {
+                consensus: consensus::CompressedSummary {
+                    stats: consensus::AggregatedStats {
+                        total_active_validators: 0,
+                        total_num_blocks_committed: 0,
+                    },
+                    data_root_commitment: vec![0; 32],
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_verify_quorum_certificate() {
+        let checkpoint = checkpoint();
+        let hash = checkpoint_hash(&checkpoint).unwrap();
+
+        let wallets = (1u8..=3)
+            .map(|i| LocalWallet::from_bytes(&[i; 32]).unwrap())
+            .collect::<Vec<_>>();
+        let power_table = wallets
+            .iter()
+            .map(|w| Validator {
+                addr: ethers_address_to_fil_address(&w.address()).unwrap(),
+                metadata: vec![],
+                weight: TokenAmount::from_whole(1),
+            })
+            .collect::<Vec<_>>();
+        let sign = |signers: &[usize]| QuorumCertificate {
+            checkpoint: checkpoint.clone(),
+            hash: hash.to_vec(),
+            quorum_weight: TokenAmount::from_whole(signers.len() as i64),
+            signatures: signers
+                .iter()
+                .map(|i| wallets[*i].sign_hash(H256(hash)).unwrap().to_vec())
+                .collect(),
+            signatories: signers.iter().map(|i| power_table[*i].addr).collect(),
+        };
+
+        assert_eq!(
+            sign(&[0, 1]).verify(&power_table).unwrap(),
+            TokenAmount::from_whole(2)
+        );
+        // one third of the power is not enough
+        assert!(sign(&[2]).verify(&power_table).is_err());
+        // the same signatory cannot be counted twice
+        assert!(sign(&[1, 1]).verify(&power_table).is_err());
+
+        // signatures are checked against their signatory
+        let mut cert = sign(&[0, 1]);
+        cert.signatures.swap(0, 1);
+        assert!(cert.verify(&power_table).is_err());
+
+        // and the hash against the checkpoint
+        let mut cert = sign(&[0, 1]);
+        cert.checkpoint.block_height = 20;
+        assert!(cert.verify(&power_table).is_err());
+
+        // the signatories must hold the weight the gateway counted
+        let mut cert = sign(&[0, 1]);
+        cert.quorum_weight = TokenAmount::from_whole(3);
+        assert!(cert.verify(&power_table).is_err());
+
+        // no signatures never reach quorum, whatever the power table
+        assert!(sign(&[]).verify(&[]).is_err());
+        let zero_power = power_table
+            .iter()
+            .map(|v| Validator {
+                weight: TokenAmount::default(),
+                ..v.clone()
+            })
+            .collect::<Vec<_>>();
+        assert!(sign(&[]).verify(&zero_power).is_err());
+    }
+}
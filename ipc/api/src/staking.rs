@@ -74,6 +74,16 @@ impl Display for ValidatorStakingInfo {
     }
 }
 
+impl ValidatorStakingInfo {
+    pub fn confirmed_collateral(&self) -> &TokenAmount {
+        &self.confirmed_collateral
+    }
+
+    pub fn total_collateral(&self) -> &TokenAmount {
+        &self.total_collateral
+    }
+}
+
 impl TryFrom<subnet_actor_getter_facet::ValidatorInfo> for ValidatorStakingInfo {
     type Error = anyhow::Error;
 
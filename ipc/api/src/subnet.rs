@@ -7,6 +7,7 @@
 /// However, we should either deprecate the native actors, or make
 /// them use the types from this sdk directly.
 use crate::subnet_id::SubnetID;
+use anyhow::anyhow;
 use fvm_ipld_encoding::repr::*;
 use fvm_shared::{address::Address, clock::ChainEpoch, econ::TokenAmount};
 use serde::{Deserialize, Serialize};
@@ -46,6 +47,19 @@ pub struct Asset {
     pub token_address: Option<Address>,
 }
 
+impl Asset {
+    /// Checks that the token address is set exactly when the asset is an ERC20.
+    fn validate(&self, what: &str) -> anyhow::Result<()> {
+        match (self.kind, &self.token_address) {
+            (AssetKind::ERC20, None) => Err(anyhow!("{what} is erc20 but has no token address")),
+            (AssetKind::Native, Some(addr)) => {
+                Err(anyhow!("{what} is native but has token address {addr}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 impl Default for Asset {
     fn default() -> Self {
         Self {
@@ -91,9 +105,102 @@ pub struct ConstructParams {
     pub validator_rewarder: Address,
 }
 
+impl ConstructParams {
+    /// Checks the parameters that the subnet actor would reject on-chain, or accept while leaving
+    /// the subnet unable to bootstrap, so they fail before a transaction is sent.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.min_validators == 0 {
+            return Err(anyhow!("min validators must be at least 1"));
+        }
+        if u64::from(self.active_validators_limit) < self.min_validators {
+            return Err(anyhow!(
+                "active validators limit {} is below min validators {}",
+                self.active_validators_limit,
+                self.min_validators
+            ));
+        }
+        if self.bottomup_check_period <= 0 {
+            return Err(anyhow!(
+                "bottom-up checkpoint period must be positive, got {}",
+                self.bottomup_check_period
+            ));
+        }
+        if self.permission_mode != PermissionMode::Federated
+            && !self.min_validator_stake.is_positive()
+        {
+            return Err(anyhow!(
+                "min validator stake must be positive in {:?} mode",
+                self.permission_mode
+            ));
+        }
+        if self.min_cross_msg_fee.is_negative() {
+            return Err(anyhow!("min cross-net message fee cannot be negative"));
+        }
+        self.supply_source.validate("supply source")?;
+        self.collateral_source.validate("collateral source")?;
+        Ok(())
+    }
+}
+
 /// Consensus types supported by hierarchical consensus
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize_repr, Serialize_repr)]
 #[repr(u64)]
 pub enum ConsensusType {
     Fendermint,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Asset, AssetKind, ConsensusType, ConstructParams, PermissionMode};
+    use crate::subnet_id::SubnetID;
+    use fvm_shared::{address::Address, econ::TokenAmount};
+    use std::str::FromStr;
+
+    fn params() -> ConstructParams {
+        ConstructParams {
+            parent: SubnetID::from_str("/r314159").unwrap(),
+            ipc_gateway_addr: Address::new_id(64),
+            consensus: ConsensusType::Fendermint,
+            min_validator_stake: TokenAmount::from_whole(1),
+            min_validators: 4,
+            bottomup_check_period: 10,
+            active_validators_limit: 100,
+            min_cross_msg_fee: TokenAmount::from_nano(1_000),
+            permission_mode: PermissionMode::Collateral,
+            supply_source: Asset::default(),
+            collateral_source: Asset::default(),
+            validator_gater: Address::new_id(0),
+            validator_rewarder: Address::new_id(0),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_params() {
+        assert!(params().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_params() {
+        let cases: Vec<fn(&mut ConstructParams)> = vec![
+            |p| p.min_validators = 0,
+            |p| p.active_validators_limit = 3,
+            |p| p.bottomup_check_period = 0,
+            |p| p.min_validator_stake = TokenAmount::default(),
+            |p| p.supply_source.kind = AssetKind::ERC20,
+            |p| p.collateral_source.token_address = Some(Address::new_id(100)),
+        ];
+        for modify in cases {
+            let mut p = params();
+            modify(&mut p);
+            assert!(p.validate().is_err(), "accepted {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_federated_allows_zero_stake() {
+        let mut p = params();
+        p.permission_mode = PermissionMode::Federated;
+        p.min_validator_stake = TokenAmount::default();
+        assert!(p.validate().is_ok());
+    }
+}
@@ -248,8 +248,45 @@ impl FromStr for SubnetID {
 mod tests {
     use crate::subnet_id::SubnetID;
     use fvm_shared::address::Address;
+    use quickcheck::{Arbitrary, Gen};
+    use quickcheck_macros::quickcheck;
     use std::str::FromStr;
 
+    #[derive(Debug, Clone)]
+    struct ArbSubnetID(SubnetID);
+
+    impl Arbitrary for ArbSubnetID {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let children = (0..usize::arbitrary(g) % 4)
+                .map(|_| {
+                    if bool::arbitrary(g) {
+                        Address::new_id(u64::arbitrary(g))
+                    } else {
+                        let subaddr: [u8; 20] = std::array::from_fn(|_| u8::arbitrary(g));
+                        Address::new_delegated(10, &subaddr).unwrap()
+                    }
+                })
+                .collect();
+            Self(SubnetID::new(u64::arbitrary(g), children))
+        }
+    }
+
+    #[quickcheck]
+    fn prop_subnet_id_display_roundtrip(id: ArbSubnetID) {
+        let parsed = SubnetID::from_str(&id.0.to_string()).unwrap();
+        assert_eq!(parsed, id.0);
+    }
+
+    /// Subnet IDs come from RPC responses and CLI input: parsing any string must not panic,
+    /// and whatever parses must display as an ID that parses back to itself.
+    #[quickcheck]
+    fn prop_subnet_id_parse_any_str(s: String, prefixed: bool) {
+        let s = if prefixed { format!("/r{s}") } else { s };
+        if let Ok(id) = SubnetID::from_str(&s) {
+            assert_eq!(SubnetID::from_str(&id.to_string()).unwrap(), id);
+        }
+    }
+
     #[test]
     fn test_parse_root_net() {
         let subnet_id = SubnetID::from_str("/r123").unwrap();
@@ -0,0 +1,148 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! How token amounts are rendered in the output of ipc-cli.
+//!
+//! Amounts are always printed with their unit, a `.` as the decimal separator and no grouping of
+//! thousands, whatever the locale, so the output can be parsed back.
+
+use clap::ValueEnum;
+use fvm_shared::bigint::{BigInt, Sign};
+use fvm_shared::econ::TokenAmount;
+use num_traits::{Signed, Zero};
+
+/// The unit amounts are shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Denomination {
+    #[default]
+    Fil,
+    #[value(name = "nanofil")]
+    NanoFil,
+    #[value(name = "attofil")]
+    AttoFil,
+}
+
+impl Denomination {
+    /// The number of decimal digits of one unit in attoFIL.
+    fn digits(&self) -> u32 {
+        match self {
+            Denomination::Fil => 18,
+            Denomination::NanoFil => 9,
+            Denomination::AttoFil => 0,
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            Denomination::Fil => "FIL",
+            Denomination::NanoFil => "nanoFIL",
+            Denomination::AttoFil => "attoFIL",
+        }
+    }
+}
+
+/// The denomination and precision to render amounts with.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AmountFormat {
+    pub denomination: Denomination,
+    /// The number of decimals to round to. All significant decimals are shown if not set.
+    pub precision: Option<u32>,
+}
+
+impl AmountFormat {
+    /// Renders `amount` with its unit, such as `1.5 FIL`.
+    pub fn format(&self, amount: &TokenAmount) -> String {
+        let digits = self.denomination.digits();
+        let atto = amount.atto();
+
+        // round half away from zero to the requested precision, and keep that many decimals
+        let (magnitude, decimals) = match self.precision {
+            Some(precision) if precision < digits => {
+                let step = BigInt::from(10u64).pow(digits - precision);
+                let rounded = (atto.abs() + &step / 2) / &step;
+                (rounded, precision)
+            }
+            _ => (atto.abs(), digits),
+        };
+
+        let scale = BigInt::from(10u64).pow(decimals);
+        let whole = &magnitude / &scale;
+        let mut fraction = match decimals {
+            0 => String::new(),
+            _ => format!(
+                "{:0>width$}",
+                &magnitude % &scale,
+                width = decimals as usize
+            ),
+        };
+        if self.precision.is_none() {
+            fraction.truncate(fraction.trim_end_matches('0').len());
+        }
+
+        let sign = if atto.sign() == Sign::Minus && !magnitude.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        let unit = self.denomination.unit();
+        if fraction.is_empty() {
+            format!("{sign}{whole} {unit}")
+        } else {
+            format!("{sign}{whole}.{fraction} {unit}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AmountFormat, Denomination};
+    use fvm_shared::econ::TokenAmount;
+
+    #[test]
+    fn test_format_amount() {
+        let fil = AmountFormat::default();
+        assert_eq!(fil.format(&TokenAmount::from_whole(2)), "2 FIL");
+        assert_eq!(
+            fil.format(&TokenAmount::from_nano(1_500_000_000)),
+            "1.5 FIL"
+        );
+        assert_eq!(
+            fil.format(&TokenAmount::from_atto(1)),
+            "0.000000000000000001 FIL"
+        );
+        assert_eq!(
+            fil.format(&TokenAmount::from_atto(-5)),
+            "-0.000000000000000005 FIL"
+        );
+
+        let rounded = AmountFormat {
+            denomination: Denomination::Fil,
+            precision: Some(2),
+        };
+        assert_eq!(
+            rounded.format(&TokenAmount::from_nano(1_234_000_000)),
+            "1.23 FIL"
+        );
+        assert_eq!(
+            rounded.format(&TokenAmount::from_nano(1_235_000_000)),
+            "1.24 FIL"
+        );
+        assert_eq!(rounded.format(&TokenAmount::from_whole(3)), "3.00 FIL");
+        assert_eq!(rounded.format(&TokenAmount::from_atto(-1)), "0.00 FIL");
+
+        let nano = AmountFormat {
+            denomination: Denomination::NanoFil,
+            precision: None,
+        };
+        assert_eq!(
+            nano.format(&TokenAmount::from_nano(1_500_000_000)),
+            "1500000000 nanoFIL"
+        );
+        assert_eq!(nano.format(&TokenAmount::from_atto(1_500)), "1.5 nanoFIL");
+
+        let atto = AmountFormat {
+            denomination: Denomination::AttoFil,
+            precision: Some(3),
+        };
+        assert_eq!(atto.format(&TokenAmount::from_atto(42)), "42 attoFIL");
+    }
+}
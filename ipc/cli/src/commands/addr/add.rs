@@ -0,0 +1,41 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Add a labelled address to the address book
+
+use async_trait::async_trait;
+use clap::Args;
+use ipc_provider::addressbook::AddressBook;
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+pub(crate) struct AddAddress;
+
+#[async_trait]
+impl CommandLineHandler for AddAddress {
+    type Arguments = AddAddressArgs;
+
+    async fn handle(_global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("add address with args: {:?}", arguments);
+
+        let path = AddressBook::default_path();
+        let mut book = AddressBook::load(&path)?;
+        book.add(&arguments.label, &arguments.address)?;
+        book.save(&path)?;
+
+        println!(
+            "added @{} to {}",
+            arguments.label.trim_start_matches('@'),
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Add or replace a labelled address")]
+pub(crate) struct AddAddressArgs {
+    #[arg(long, help = "The label, used as @label in other commands")]
+    pub label: String,
+    #[arg(long, help = "The f/t address or 0x ethereum address")]
+    pub address: String,
+}
@@ -0,0 +1,35 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! List the labelled addresses of the address book
+
+use async_trait::async_trait;
+use clap::Args;
+use ipc_provider::addressbook::{eth_representation, parse_address, AddressBook};
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+pub(crate) struct ListAddresses;
+
+#[async_trait]
+impl CommandLineHandler for ListAddresses {
+    type Arguments = ListAddressesArgs;
+
+    async fn handle(_global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("list addresses with args: {:?}", arguments);
+
+        let book = AddressBook::load(AddressBook::default_path())?;
+        for (label, address) in book.entries() {
+            // show both representations of delegated addresses, for use on either side
+            let addr = parse_address(address)?;
+            match eth_representation(&addr) {
+                Some(eth) => println!("@{label}: {addr} ({eth})"),
+                None => println!("@{label}: {addr}"),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(about = "List the labelled addresses")]
+pub(crate) struct ListAddressesArgs {}
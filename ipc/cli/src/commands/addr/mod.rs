@@ -0,0 +1,42 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Address book cli commands
+use crate::{CommandLineHandler, GlobalArguments};
+
+use clap::{Args, Subcommand};
+
+use self::add::{AddAddress, AddAddressArgs};
+use self::list::{ListAddresses, ListAddressesArgs};
+use self::remove::{RemoveAddress, RemoveAddressArgs};
+
+mod add;
+mod list;
+mod remove;
+
+#[derive(Debug, Args)]
+#[command(
+    name = "addr",
+    about = "manage labelled addresses, usable as @label wherever an address is expected"
+)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct AddrCommandsArgs {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+impl AddrCommandsArgs {
+    pub async fn handle(&self, global: &GlobalArguments) -> anyhow::Result<()> {
+        match &self.command {
+            Commands::Add(args) => AddAddress::handle(global, args).await,
+            Commands::List(args) => ListAddresses::handle(global, args).await,
+            Commands::Rm(args) => RemoveAddress::handle(global, args).await,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum Commands {
+    Add(AddAddressArgs),
+    List(ListAddressesArgs),
+    Rm(RemoveAddressArgs),
+}
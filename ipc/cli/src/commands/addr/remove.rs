@@ -0,0 +1,36 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Remove a labelled address from the address book
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_provider::addressbook::AddressBook;
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+pub(crate) struct RemoveAddress;
+
+#[async_trait]
+impl CommandLineHandler for RemoveAddress {
+    type Arguments = RemoveAddressArgs;
+
+    async fn handle(_global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("remove address with args: {:?}", arguments);
+
+        let path = AddressBook::default_path();
+        let mut book = AddressBook::load(&path)?;
+        let label = &arguments.label;
+        book.remove(label)
+            .ok_or_else(|| anyhow!("no address labelled {label} in the address book"))?;
+        book.save(&path)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Remove a labelled address")]
+pub(crate) struct RemoveAddressArgs {
+    #[arg(long, help = "The label to remove")]
+    pub label: String,
+}
@@ -0,0 +1,90 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Options shared by the operations that need m-of-n approval.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::anyhow;
+use clap::Args;
+use ipc_provider::approval::{Intent, IntentFile, APPROVALS_DIR};
+use ipc_provider::config::ApprovalConfig;
+use ipc_provider::store::FileStore;
+
+use crate::GlobalArguments;
+
+#[derive(Debug, Args)]
+pub(crate) struct ApprovalArgs {
+    #[arg(
+        long,
+        help = "Write the operation to this intent file for approval instead of running it"
+    )]
+    pub intent_out: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "intent_out",
+        help = "The intent file carrying the approvals needed to run the operation"
+    )]
+    pub approved: Option<String>,
+}
+
+/// Checks the approvals of `operation` against `config`, if the operation is covered by one.
+/// Returns false when the operation was written to an intent file and must not run yet.
+pub(crate) fn check_approval(
+    global: &GlobalArguments,
+    config: Option<&ApprovalConfig>,
+    arguments: &ApprovalArgs,
+    operation: &str,
+    params: BTreeMap<String, String>,
+) -> anyhow::Result<bool> {
+    let Some(config) = config else {
+        return Ok(true);
+    };
+
+    if let Some(path) = &arguments.intent_out {
+        IntentFile::new(Intent::new(operation, params)).save(path)?;
+        println!(
+            "{operation} needs {} approvals, intent written to {path}",
+            config.threshold
+        );
+        return Ok(false);
+    }
+
+    let path = arguments.approved.as_ref().ok_or_else(|| {
+        anyhow!(
+            "{operation} needs {} approvals, create an intent with --intent-out",
+            config.threshold
+        )
+    })?;
+    accept_intent(
+        &global.config_path(),
+        config,
+        &IntentFile::load(path)?,
+        operation,
+        &params,
+    )?;
+    Ok(true)
+}
+
+/// Checks that `file` approves `operation` with `params` and records it as executed, so the same
+/// approvals cannot run the operation a second time.
+pub(crate) fn accept_intent(
+    config_path: &str,
+    config: &ApprovalConfig,
+    file: &IntentFile,
+    operation: &str,
+    params: &BTreeMap<String, String>,
+) -> anyhow::Result<()> {
+    file.verify(config, operation, params)?;
+
+    let dir = Path::new(config_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(APPROVALS_DIR);
+    file.consume(&FileStore::open(dir)?)
+}
+
+/// An optional parameter as it is recorded in an intent, empty when not set.
+pub(crate) fn param<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
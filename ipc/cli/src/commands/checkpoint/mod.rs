@@ -7,18 +7,22 @@ use crate::commands::checkpoint::bottomup_height::{
 use crate::commands::checkpoint::list_validator_changes::{
     ListValidatorChanges, ListValidatorChangesArgs,
 };
+use crate::commands::checkpoint::quorum_cert::{GetQuorumCertificate, GetQuorumCertificateArgs};
 use crate::commands::checkpoint::quorum_reached::{
     GetQuorumReacehdEvents, GetQuorumReachedEventsArgs,
 };
 use crate::commands::checkpoint::relayer::{BottomUpRelayer, BottomUpRelayerArgs};
+use crate::commands::checkpoint::top_up::{TopUpRelayer, TopUpRelayerArgs};
 use crate::{CommandLineHandler, GlobalArguments};
 use clap::{Args, Subcommand};
 
 mod bottomup_bundles;
 mod bottomup_height;
 mod list_validator_changes;
+mod quorum_cert;
 mod quorum_reached;
 mod relayer;
+mod top_up;
 
 #[derive(Debug, Args)]
 #[command(name = "checkpoint", about = "checkpoint related commands")]
@@ -32,6 +36,7 @@ impl CheckpointCommandsArgs {
     pub async fn handle(&self, global: &GlobalArguments) -> anyhow::Result<()> {
         match &self.command {
             Commands::Relayer(args) => BottomUpRelayer::handle(global, args).await,
+            Commands::RelayerTopUp(args) => TopUpRelayer::handle(global, args).await,
             Commands::ListValidatorChanges(args) => {
                 ListValidatorChanges::handle(global, args).await
             }
@@ -39,6 +44,7 @@ impl CheckpointCommandsArgs {
             Commands::QuorumReachedEvents(args) => {
                 GetQuorumReacehdEvents::handle(global, args).await
             }
+            Commands::QuorumCert(args) => GetQuorumCertificate::handle(global, args).await,
             Commands::LastBottomupCheckpointHeight(args) => {
                 LastBottomUpCheckpointHeight::handle(global, args).await
             }
@@ -49,8 +55,10 @@ impl CheckpointCommandsArgs {
 #[derive(Debug, Subcommand)]
 pub(crate) enum Commands {
     Relayer(BottomUpRelayerArgs),
+    RelayerTopUp(TopUpRelayerArgs),
     ListValidatorChanges(ListValidatorChangesArgs),
     ListBottomupBundle(GetBottomUpBundlesArgs),
     QuorumReachedEvents(GetQuorumReachedEventsArgs),
+    QuorumCert(GetQuorumCertificateArgs),
     LastBottomupCheckpointHeight(LastBottomUpCheckpointHeightArgs),
 }
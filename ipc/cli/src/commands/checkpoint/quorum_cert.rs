@@ -0,0 +1,96 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Export and verify the quorum certificate of a bottom up checkpoint
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use fs_err as fs;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::quorum::QuorumCertificate;
+use ipc_api::subnet_id::SubnetID;
+
+use crate::commands::get_ipc_provider;
+use crate::errors::CliError;
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// The command to export the quorum certificate of a checkpoint.
+pub(crate) struct GetQuorumCertificate;
+
+#[async_trait]
+impl CommandLineHandler for GetQuorumCertificate {
+    type Arguments = GetQuorumCertificateArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("get quorum certificate with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+
+        let certificate = match (&arguments.input, arguments.height) {
+            (Some(path), _) => {
+                serde_json::from_str::<QuorumCertificate>(&fs::read_to_string(path)?)?
+            }
+            (None, Some(height)) => provider
+                .quorum_certificate(&subnet, height)
+                .await?
+                .ok_or_else(|| {
+                    CliError::QuorumNotReached(format!("no checkpoint at height {height}"))
+                })?,
+            (None, None) => return Err(anyhow!("either --height or --input is required")),
+        };
+
+        if arguments.verify {
+            let power_table = provider
+                .get_power_table_at(&subnet, certificate.height())
+                .await?;
+            let weight = certificate.verify(&power_table)?;
+            println!(
+                "checkpoint at height {} verified, signed by {} validators with weight {weight}",
+                certificate.height(),
+                certificate.signatories.len()
+            );
+            return Ok(());
+        }
+
+        let json = serde_json::to_string_pretty(&certificate)?;
+        match &arguments.output {
+            Some(path) => fs::write(path, json)?,
+            None => println!("{json}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "quorum-cert",
+    about = "Export the quorum certificate of a bottom up checkpoint, or verify it against the power table of the subnet"
+)]
+pub(crate) struct GetQuorumCertificateArgs {
+    #[arg(long, help = "The child subnet that signed the checkpoint")]
+    pub subnet: String,
+    #[arg(
+        long,
+        required_unless_present = "input",
+        help = "The height of the checkpoint"
+    )]
+    pub height: Option<ChainEpoch>,
+    #[arg(long, help = "Write the certificate to this file instead of stdout")]
+    pub output: Option<String>,
+    #[arg(
+        long,
+        requires = "verify",
+        help = "Verify a certificate previously exported to this file instead of fetching it"
+    )]
+    pub input: Option<String>,
+    #[arg(
+        long,
+        help = "Verify that the signatories hold two thirds of the weight of the membership at the checkpoint height"
+    )]
+    pub verify: bool,
+}
@@ -1,26 +1,31 @@
 // Copyright 2022-2024 Protocol Labs
 // SPDX-License-Identifier: MIT
 
-use crate::commands::get_subnet_config;
-use crate::{require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
+use crate::commands::{get_subnet_config, open_history, shutdown_signal};
+use crate::{f64_to_token_amount, require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
 use anyhow::anyhow;
 use anyhow::Context;
 use async_trait::async_trait;
 use clap::Args;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
 use ipc_api::subnet_id::SubnetID;
-use ipc_provider::checkpoint::BottomUpCheckpointManager;
+use ipc_provider::checkpoint::{BalanceThreshold, BottomUpCheckpointManager, FeeWindow};
 use ipc_provider::config::Config;
-use ipc_provider::new_evm_keystore_from_config;
+use ipc_provider::notify::{Notifier, NotifyConfig};
 use ipc_provider::observe::register_metrics as register_checkpoint_metrics;
+use ipc_provider::store::FileStore;
+use ipc_provider::{new_evm_keystore_from_config, new_evm_keystore_from_path};
 use ipc_wallet::EvmKeyStore;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 const DEFAULT_POLLING_INTERVAL: u64 = 15;
+const DEFAULT_STATE_DIR: &str = "state";
 
 /// The command to run the bottom up relayer in the background.
 pub(crate) struct BottomUpRelayer;
@@ -56,7 +61,20 @@ impl CommandLineHandler for BottomUpRelayer {
 
         let config_path = global.config_path();
         let config = Arc::new(Config::from_file(&config_path)?);
-        let mut keystore = new_evm_keystore_from_config(config)?;
+
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let parent = subnet
+            .parent()
+            .ok_or_else(|| anyhow!("root does not have parent"))?;
+
+        let child = get_subnet_config(&config_path, &subnet)?;
+        let parent = get_subnet_config(&config_path, &parent)?;
+
+        // checkpoints are submitted to the parent, so sign with the parent's keystore
+        let mut keystore = match parent.keystore_path() {
+            Some(path) => new_evm_keystore_from_path(path)?,
+            None => new_evm_keystore_from_config(config)?,
+        };
         let submitter = match (arguments.submitter.as_ref(), keystore.get_default()?) {
             (Some(submitter), _) => require_fil_addr_from_str(submitter)?,
             (None, Some(addr)) => {
@@ -68,14 +86,6 @@ impl CommandLineHandler for BottomUpRelayer {
             }
         };
 
-        let subnet = SubnetID::from_str(&arguments.subnet)?;
-        let parent = subnet
-            .parent()
-            .ok_or_else(|| anyhow!("root does not have parent"))?;
-
-        let child = get_subnet_config(&config_path, &subnet)?;
-        let parent = get_subnet_config(&config_path, &parent)?;
-
         let mut manager = BottomUpCheckpointManager::new_evm_manager(
             parent.clone(),
             child.clone(),
@@ -84,16 +94,53 @@ impl CommandLineHandler for BottomUpRelayer {
         )
         .await?;
 
+        // in flight submissions are kept next to the config unless told otherwise
+        let state_dir = match &arguments.state_dir {
+            Some(dir) => dir.clone(),
+            None => Path::new(&config_path)
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(DEFAULT_STATE_DIR),
+        };
+        log::info!("relayer state stored in: {}", state_dir.display());
+        manager = manager
+            .with_state_store(Arc::new(FileStore::open(state_dir)?))
+            .with_history(open_history(&config_path));
+
         if let Some(v) = arguments.finalization_blocks {
             manager = manager.with_finalization_blocks(v as ChainEpoch);
         }
 
+        let notify = NotifyConfig {
+            webhooks: arguments.webhook.clone(),
+            command: arguments.notify_command.clone(),
+        };
+        if !notify.is_empty() {
+            manager = manager.with_notifier(Notifier::new(notify));
+        }
+
+        if let Some(minimum) = arguments.min_balance {
+            manager = manager.with_balance_threshold(BalanceThreshold {
+                minimum: f64_to_token_amount(minimum)?,
+                refuse: arguments.refuse_below_min_balance,
+            });
+        }
+
+        if let Some(max_base_fee) = arguments.max_base_fee {
+            manager = manager.with_fee_window(FeeWindow {
+                max_base_fee: TokenAmount::from_atto(max_base_fee),
+                max_delay: Duration::from_secs(arguments.max_fee_delay_sec),
+            });
+        }
+
         let interval = Duration::from_secs(
             arguments
                 .checkpoint_interval_sec
                 .unwrap_or(DEFAULT_POLLING_INTERVAL),
         );
-        manager.run(submitter, interval).await;
+        manager
+            .run_until(submitter, interval, shutdown_signal()?)
+            .await;
 
         Ok(())
     }
@@ -120,6 +167,44 @@ pub(crate) struct BottomUpRelayerArgs {
         help = "The max parallelism for submitting checkpoints"
     )]
     pub max_parallelism: usize,
+    #[arg(
+        long,
+        help = "The directory where the relayer keeps its state across restarts, defaults to the state directory next to the config"
+    )]
+    pub state_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "A URL to post JSON notifications of checkpoint submissions to, can be repeated"
+    )]
+    pub webhook: Vec<String>,
+    #[arg(
+        long,
+        help = "A shell command run for every notification, with the JSON on its standard input"
+    )]
+    pub notify_command: Option<String>,
+    #[arg(
+        long,
+        help = "Warn, and notify, when the submitter's balance in the parent drops below this amount, in whole FIL"
+    )]
+    pub min_balance: Option<f64>,
+    #[arg(
+        long,
+        requires = "min_balance",
+        help = "Stop submitting checkpoints while the submitter's balance is below --min-balance"
+    )]
+    pub refuse_below_min_balance: bool,
+    #[arg(
+        long,
+        help = "Hold back checkpoint submissions while the parent's base fee per gas is above this amount, in attoFIL"
+    )]
+    pub max_base_fee: Option<u64>,
+    #[arg(
+        long,
+        default_value = "3600",
+        requires = "max_base_fee",
+        help = "The number of seconds checkpoints are held back by --max-base-fee at most"
+    )]
+    pub max_fee_delay_sec: u64,
 
     #[arg(
         long,
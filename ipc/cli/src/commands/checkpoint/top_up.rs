@@ -0,0 +1,91 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Top up the relayer's submitter in the parent
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+
+use crate::commands::get_ipc_provider;
+use crate::{f64_to_token_amount, require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
+
+/// The command to move funds to the submitter of a relayer from another key.
+pub(crate) struct TopUpRelayer;
+
+#[async_trait]
+impl CommandLineHandler for TopUpRelayer {
+    type Arguments = TopUpRelayerArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("top up relayer with args: {:?}", arguments);
+
+        let mut provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let parent = subnet
+            .parent()
+            .ok_or_else(|| anyhow!("root does not have parent"))?;
+        let from = match &arguments.from {
+            Some(address) => Some(require_fil_addr_from_str(address)?),
+            None => None,
+        };
+        let submitter = require_fil_addr_from_str(&arguments.submitter)?;
+
+        let amounts = global.amount_format();
+        let balance = provider.wallet_balance(&parent, &submitter).await?;
+        let amount = match arguments.target {
+            Some(target) => {
+                let target = f64_to_token_amount(target)?;
+                if balance >= target {
+                    println!(
+                        "submitter {submitter} already holds {}, nothing to do",
+                        amounts.format(&balance)
+                    );
+                    return Ok(());
+                }
+                target - &balance
+            }
+            None => f64_to_token_amount(arguments.amount.unwrap_or_default())?,
+        };
+
+        provider
+            .send_value(&parent, from, submitter, amount.clone())
+            .await?;
+        println!(
+            "sent {} to submitter {submitter} in {parent}, balance was {} and is now {}",
+            amounts.format(&amount),
+            amounts.format(&balance),
+            amounts.format(&provider.wallet_balance(&parent, &submitter).await?)
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "relayer-top-up",
+    about = "Send funds in the parent to the address the relayer submits checkpoints from"
+)]
+pub(crate) struct TopUpRelayerArgs {
+    #[arg(long, help = "The child subnet the relayer submits checkpoints of")]
+    pub subnet: String,
+    #[arg(long, help = "The address to send the funds from")]
+    pub from: Option<String>,
+    #[arg(long, help = "The address of the relayer's submitter")]
+    pub submitter: String,
+    #[arg(
+        long,
+        conflicts_with = "amount",
+        help = "Send just enough to bring the submitter's balance to this amount, in whole FIL"
+    )]
+    pub target: Option<f64>,
+    #[arg(
+        required_unless_present = "target",
+        help = "The amount to send, in whole FIL"
+    )]
+    pub amount: Option<f64>,
+}
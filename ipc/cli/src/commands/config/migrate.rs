@@ -0,0 +1,38 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+use crate::{CommandLineHandler, GlobalArguments};
+use async_trait::async_trait;
+use ipc_provider::config::migrate::CONFIG_VERSION;
+use ipc_provider::config::Config;
+use std::path::Path;
+
+use clap::Args;
+
+/// The command to upgrade the config file to the current schema version
+pub(crate) struct MigrateConfig;
+
+#[async_trait]
+impl CommandLineHandler for MigrateConfig {
+    type Arguments = MigrateConfigArgs;
+
+    async fn handle(global: &GlobalArguments, _arguments: &Self::Arguments) -> anyhow::Result<()> {
+        let path = global.config_path();
+        log::debug!("migrating config file in {}", path);
+
+        match Config::migrate_file(&path)? {
+            Some(from) => println!(
+                "migrated {path} from v{from} to v{CONFIG_VERSION}, the original is in {}",
+                Path::new(&path).with_extension("toml.bak").display()
+            ),
+            None => println!("{path} is already at v{CONFIG_VERSION}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Upgrade the config file to the current schema version, keeping the original in <config>.bak. Comments are not preserved"
+)]
+pub(crate) struct MigrateConfigArgs {}
@@ -3,11 +3,13 @@
 //! This mod triggers a config reload in the IPC-Agent Json RPC server.
 
 mod init;
+mod migrate;
 
 use clap::{Args, Subcommand};
 use std::fmt::Debug;
 
 use crate::commands::config::init::{InitConfig, InitConfigArgs};
+use crate::commands::config::migrate::{MigrateConfig, MigrateConfigArgs};
 use crate::{CommandLineHandler, GlobalArguments};
 
 #[derive(Debug, Args)]
@@ -22,6 +24,7 @@ impl ConfigCommandsArgs {
     pub async fn handle(&self, global: &GlobalArguments) -> anyhow::Result<()> {
         match &self.command {
             Commands::Init(args) => InitConfig::handle(global, args).await,
+            Commands::Migrate(args) => MigrateConfig::handle(global, args).await,
         }
     }
 }
@@ -29,4 +32,5 @@ impl ConfigCommandsArgs {
 #[derive(Debug, Subcommand)]
 pub(crate) enum Commands {
     Init(InitConfigArgs),
+    Migrate(MigrateConfigArgs),
 }
@@ -3,16 +3,17 @@
 //! Fund cli command handler.
 
 use async_trait::async_trait;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use fvm_shared::bigint::BigInt;
 use fvm_shared::econ::TokenAmount;
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::route::FundProgress;
 use num_traits::Num;
 use std::{fmt::Debug, str::FromStr};
 
 use crate::{
-    f64_to_token_amount, get_ipc_provider, require_fil_addr_from_str, CommandLineHandler,
-    GlobalArguments,
+    f64_to_token_amount, get_ipc_provider, print_fee_estimate, require_fil_addr_from_str,
+    CommandLineHandler, GlobalArguments,
 };
 
 /// The command to send funds to a subnet from parent
@@ -40,6 +41,43 @@ impl CommandLineHandler for Fund {
             None => None,
         };
 
+        if arguments.route.is_some() {
+            let source = match &arguments.source {
+                Some(source) => SubnetID::from_str(source)?,
+                None => SubnetID::new_root(subnet.root_id()),
+            };
+            let epoch = provider
+                .fund_route(
+                    &source,
+                    &subnet,
+                    from,
+                    to,
+                    f64_to_token_amount(arguments.amount)?,
+                    |progress| match progress {
+                        FundProgress::Funded { subnet, epoch } => {
+                            println!("funded {subnet} in epoch {epoch}")
+                        }
+                        FundProgress::Waiting { subnet } => {
+                            println!("waiting for the deposit to be executed in {subnet}")
+                        }
+                        FundProgress::Arrived { subnet } => {
+                            println!("deposit executed in {subnet}")
+                        }
+                    },
+                )
+                .await?;
+            println!("fund performed in epoch: {epoch:?}");
+            return Ok(());
+        }
+
+        if arguments.estimate_only {
+            let estimate = provider
+                .estimate_fund(subnet, from, to, f64_to_token_amount(arguments.amount)?)
+                .await?;
+            print_fee_estimate(&estimate, &global.amount_format());
+            return Ok(());
+        }
+
         println!(
             "fund performed in epoch: {:?}",
             provider
@@ -73,6 +111,24 @@ pub(crate) struct FundArgs {
     pub subnet: String,
     #[arg(help = "The amount to fund in FIL, in whole FIL")]
     pub amount: f64,
+    #[arg(
+        long,
+        help = "Print the expected gas and fees of funding instead of sending the transaction"
+    )]
+    pub estimate_only: bool,
+    #[arg(
+        long,
+        value_parser = ["auto"],
+        conflicts_with_all = ["gateway_address", "estimate_only"],
+        help = "Fund a subnet more than one level below the source, hop by hop"
+    )]
+    pub route: Option<String>,
+    #[arg(
+        long,
+        requires = "route",
+        help = "The subnet to route the funds from, the root of the subnet by default"
+    )]
+    pub source: Option<String>,
 }
 
 pub struct PreFund;
@@ -138,9 +194,26 @@ impl CommandLineHandler for FundWithToken {
             None => None,
         };
 
-        let amount = BigInt::from_str_radix(arguments.amount.as_str(), 10)
-            .map_err(|e| anyhow::anyhow!("not a token amount: {e}"))
-            .map(TokenAmount::from_atto)?;
+        let (amount, rendered) = match arguments.amount_unit {
+            AmountUnit::Base => {
+                if arguments.amount.contains('.') {
+                    return Err(anyhow::anyhow!(
+                        "{} is not an integer amount in the token's smallest unit, pass --amount-unit token for whole tokens",
+                        arguments.amount
+                    ));
+                }
+                let amount = BigInt::from_str_radix(arguments.amount.as_str(), 10)
+                    .map_err(|e| anyhow::anyhow!("not a token amount: {e}"))
+                    .map(TokenAmount::from_atto)?;
+                (amount, format!("{} base units", arguments.amount))
+            }
+            AmountUnit::Token => {
+                let token = provider.supply_token_info(&subnet).await?;
+                let amount = token.parse_amount(&arguments.amount)?;
+                let rendered = token.format_amount(&amount);
+                (amount, rendered)
+            }
+        };
 
         if arguments.approve {
             println!(
@@ -152,7 +225,7 @@ impl CommandLineHandler for FundWithToken {
         }
 
         println!(
-            "fund with token performed in epoch: {:?}",
+            "fund with {rendered} performed in epoch: {:?}",
             provider.fund_with_token(subnet, from, to, amount).await?,
         );
 
@@ -172,8 +245,27 @@ pub(crate) struct FundWithTokenArgs {
     pub to: Option<String>,
     #[arg(long, help = "The subnet to fund")]
     pub subnet: String,
-    #[arg(help = "The amount to fund in erc20, in the token's precision unit")]
+    #[arg(
+        help = "The amount to fund, in the token's smallest unit unless --amount-unit token is set"
+    )]
     pub amount: String,
     #[arg(long, help = "Approve gateway before funding")]
     pub approve: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value = "base",
+        help = "The unit of the amount: base for the token's smallest unit, token for whole tokens such as 1.5"
+    )]
+    pub amount_unit: AmountUnit,
+}
+
+/// The unit the amount of ERC20 tokens to fund is given in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum AmountUnit {
+    /// An integer amount of the smallest unit of the token.
+    #[default]
+    Base,
+    /// A decimal amount of whole tokens, using the decimals of the token.
+    Token,
 }
@@ -1,9 +1,12 @@
 // Copyright 2022-2024 Protocol Labs
 // SPDX-License-Identifier: MIT
 use self::fund::{FundWithToken, FundWithTokenArgs, PreFund, PreFundArgs};
+use self::postbox::{ListPostbox, ListPostboxArgs};
 use self::release::{PreRelease, PreReleaseArgs};
+use self::status::{CrossMsgStatus, CrossMsgStatusArgs};
 use self::topdown_cross::{
-    LatestParentFinality, LatestParentFinalityArgs, ListTopdownMsgs, ListTopdownMsgsArgs,
+    FollowParent, FollowParentArgs, LatestParentFinality, LatestParentFinalityArgs,
+    ListTopdownMsgs, ListTopdownMsgsArgs,
 };
 use crate::commands::crossmsg::fund::Fund;
 use crate::commands::crossmsg::propagate::Propagate;
@@ -16,8 +19,10 @@ use release::ReleaseArgs;
 use clap::{Args, Subcommand};
 
 pub mod fund;
+mod postbox;
 pub mod propagate;
 pub mod release;
+mod status;
 mod topdown_cross;
 
 #[derive(Debug, Args)]
@@ -39,6 +44,9 @@ impl CrossMsgsCommandsArgs {
             Commands::Propagate(args) => Propagate::handle(global, args).await,
             Commands::ListTopdownMsgs(args) => ListTopdownMsgs::handle(global, args).await,
             Commands::ParentFinality(args) => LatestParentFinality::handle(global, args).await,
+            Commands::Status(args) => CrossMsgStatus::handle(global, args).await,
+            Commands::Postbox(args) => ListPostbox::handle(global, args).await,
+            Commands::FollowParent(args) => FollowParent::handle(global, args).await,
         }
     }
 }
@@ -53,4 +61,7 @@ pub(crate) enum Commands {
     Propagate(PropagateArgs),
     ListTopdownMsgs(ListTopdownMsgsArgs),
     ParentFinality(LatestParentFinalityArgs),
+    Status(CrossMsgStatusArgs),
+    Postbox(ListPostboxArgs),
+    FollowParent(FollowParentArgs),
 }
@@ -0,0 +1,80 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! List postbox cli command handler.
+
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::address::IPCAddress;
+use ipc_api::subnet_id::SubnetID;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The command to list the messages waiting in the postbox of a subnet.
+pub(crate) struct ListPostbox;
+
+#[async_trait]
+impl CommandLineHandler for ListPostbox {
+    type Arguments = ListPostboxArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("list postbox with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+
+        for (key, msg) in provider.list_postbox(&subnet).await? {
+            if !matches(&msg.from, arguments.from.as_deref())
+                || !matches(&msg.to, arguments.to.as_deref())
+            {
+                continue;
+            }
+            println!(
+                "key: 0x{}, kind: {}, from: {}, to: {}, value: {}, nonce: {}",
+                hex::encode(key),
+                msg.kind,
+                msg.from,
+                msg.to,
+                msg.value,
+                msg.local_nonce
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `address` is, or is in the subnet, given by `filter`.
+fn matches(address: &IPCAddress, filter: Option<&str>) -> bool {
+    let Some(filter) = filter else {
+        return true;
+    };
+    format!("{address}") == filter
+        || address
+            .subnet()
+            .map_or(false, |subnet| subnet.to_string() == filter)
+        || address
+            .raw_addr()
+            .map_or(false, |addr| addr.to_string() == filter)
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "postbox",
+    about = "List the cross-net messages waiting in the postbox of a subnet, with the keys to propagate them"
+)]
+pub(crate) struct ListPostboxArgs {
+    #[arg(long, help = "The subnet whose postbox to list")]
+    pub subnet: String,
+    #[arg(
+        long,
+        help = "Only list the messages sent from this subnet, address or subnet:address"
+    )]
+    pub from: Option<String>,
+    #[arg(
+        long,
+        help = "Only list the messages sent to this subnet, address or subnet:address"
+    )]
+    pub to: Option<String>,
+}
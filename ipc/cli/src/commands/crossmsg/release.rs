@@ -4,9 +4,13 @@
 
 use async_trait::async_trait;
 use clap::Args;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
 use ipc_api::subnet_id::SubnetID;
+use std::collections::BTreeMap;
 use std::{fmt::Debug, str::FromStr};
 
+use crate::commands::approval::{check_approval, param, ApprovalArgs};
 use crate::{
     f64_to_token_amount, get_ipc_provider, require_fil_addr_from_str, CommandLineHandler,
     GlobalArguments,
@@ -37,16 +41,24 @@ impl CommandLineHandler for Release {
             None => None,
         };
 
+        let amount = f64_to_token_amount(arguments.amount)?;
+        // the approvals are bound to the key that signs the release
+        let from = provider.resolve_sender(&subnet, from)?;
+
+        let config = provider.config();
+        let approvals = config
+            .approvals
+            .as_ref()
+            .filter(|a| a.covers_release(arguments.amount));
+        let params = release_params(&subnet, &gateway_addr, &from, &to, &amount);
+        if !check_approval(global, approvals, &arguments.approval, "release", params)? {
+            return Ok(());
+        }
+
         println!(
             "release performed in epoch: {:?}",
             provider
-                .release(
-                    subnet,
-                    gateway_addr,
-                    from,
-                    to,
-                    f64_to_token_amount(arguments.amount)?,
-                )
+                .release(subnet, gateway_addr, Some(from), to, amount)
                 .await?,
         );
 
@@ -54,6 +66,23 @@ impl CommandLineHandler for Release {
     }
 }
 
+/// The parameters of a release as they are recorded in its intent.
+pub(crate) fn release_params(
+    subnet: &SubnetID,
+    gateway_addr: &Option<Address>,
+    from: &Address,
+    to: &Option<Address>,
+    amount: &TokenAmount,
+) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("subnet".to_string(), subnet.to_string()),
+        ("gateway".to_string(), param(gateway_addr)),
+        ("from".to_string(), from.to_string()),
+        ("to".to_string(), param(to)),
+        ("amount".to_string(), amount.atto().to_string()),
+    ])
+}
+
 #[derive(Debug, Args)]
 #[command(about = "Release operation in the gateway actor")]
 pub(crate) struct ReleaseArgs {
@@ -70,6 +99,8 @@ pub(crate) struct ReleaseArgs {
     pub subnet: String,
     #[arg(help = "The amount to release in FIL, in whole FIL")]
     pub amount: f64,
+    #[command(flatten)]
+    pub approval: ApprovalArgs,
 }
 
 pub struct PreRelease;
@@ -0,0 +1,87 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Cross message delivery status cli command handler.
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::CrossMsgDelivery;
+
+use crate::commands::get_ipc_provider;
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// The command to trace the cross messages sent by a transaction
+pub(crate) struct CrossMsgStatus;
+
+#[async_trait]
+impl CommandLineHandler for CrossMsgStatus {
+    type Arguments = CrossMsgStatusArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("cross message status with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let tx_hash: [u8; 32] = hex::decode(arguments.tx.trim_start_matches("0x"))?
+            .try_into()
+            .map_err(|_| anyhow!("transaction hash must be 32 bytes"))?;
+
+        let Some(status) = provider.cross_msg_status(&subnet, &tx_hash).await? else {
+            println!("transaction not found or still pending in {subnet}");
+            return Ok(());
+        };
+
+        println!("transaction included at height: {}", status.height);
+        if status.deliveries.is_empty() {
+            println!("no cross messages committed by this transaction");
+        }
+        for delivery in status.deliveries {
+            match delivery {
+                CrossMsgDelivery::TopDown {
+                    subnet,
+                    nonce,
+                    executed,
+                } => {
+                    let state = match executed {
+                        Some(true) => "executed",
+                        Some(false) => "waiting for parent finality",
+                        None => "unknown, subnet not configured",
+                    };
+                    println!("top-down message to {subnet} with nonce {nonce}: {state}");
+                }
+                CrossMsgDelivery::BottomUp {
+                    messages,
+                    checkpoint_height,
+                    submitted,
+                } => {
+                    let state = if submitted {
+                        "submitted to parent"
+                    } else {
+                        "waiting for checkpoint"
+                    };
+                    println!(
+                        "{messages} bottom-up message(s) in checkpoint at height {checkpoint_height}: {state}"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Trace the cross messages committed by a transaction to their destination")]
+pub(crate) struct CrossMsgStatusArgs {
+    #[arg(long, help = "The subnet the transaction was sent to")]
+    pub subnet: String,
+    #[arg(
+        long,
+        help = "The hash of the transaction that sent the cross messages"
+    )]
+    pub tx: String,
+}
@@ -7,6 +7,7 @@ use std::str::FromStr;
 
 use async_trait::async_trait;
 use clap::Args;
+use futures_util::StreamExt;
 use fvm_shared::clock::ChainEpoch;
 use ipc_api::subnet_id::SubnetID;
 
@@ -83,3 +84,65 @@ pub(crate) struct LatestParentFinalityArgs {
     #[arg(long, help = "The subnet id to check parent finality")]
     pub subnet: String,
 }
+
+/// The command to follow the parent of a subnet block by block.
+pub(crate) struct FollowParent;
+
+#[async_trait]
+impl CommandLineHandler for FollowParent {
+    type Arguments = FollowParentArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("follow parent with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+
+        let updates =
+            provider.follow_parent(&subnet, arguments.from, arguments.finalization_blocks);
+        futures_util::pin_mut!(updates);
+        while let Some(update) = updates.next().await {
+            let update = match update {
+                Ok(update) => update,
+                Err(e) => {
+                    log::warn!("cannot follow the parent, retrying: {e}");
+                    continue;
+                }
+            };
+            println!(
+                "block height: {}, block hash: {}, top down messages: {}, validator changes: {}",
+                update.height,
+                hex::encode(&update.block_hash),
+                update.top_down_msgs.len(),
+                update.validator_changes.len()
+            );
+            if arguments.to.is_some_and(|to| update.height >= to) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Follow the parent of a subnet block by block, with the top down messages and validator changes of each block"
+)]
+pub(crate) struct FollowParentArgs {
+    #[arg(long, help = "The subnet whose parent to follow")]
+    pub subnet: String,
+    #[arg(long, help = "The parent height to start from")]
+    pub from: ChainEpoch,
+    #[arg(
+        long,
+        help = "Stop after this parent height, follow the head if not set"
+    )]
+    pub to: Option<ChainEpoch>,
+    #[arg(
+        long,
+        default_value = "0",
+        help = "The number of blocks away from the parent's head that is considered final"
+    )]
+    pub finalization_blocks: ChainEpoch,
+}
@@ -0,0 +1,56 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! A read-only dashboard over the provider, served locally on top of the `serve` read api.
+
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+use axum::response::Html;
+use axum::routing::get;
+use clap::Args;
+use std::str::FromStr;
+
+use crate::commands::serve::read_router;
+use crate::commands::shutdown_signal;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+const INDEX: &str = include_str!("index.html");
+
+/// The command to serve the dashboard.
+pub(crate) struct Explorer;
+
+#[async_trait]
+impl CommandLineHandler for Explorer {
+    type Arguments = ExplorerArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("start explorer with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let addr = SocketAddr::from_str(&arguments.listen)?;
+
+        let router = read_router(provider).route("/", get(|| async { Html(INDEX) }));
+
+        let server = axum::Server::try_bind(&addr)?
+            .serve(router.into_make_service())
+            .with_graceful_shutdown(shutdown_signal()?);
+        println!("serving the explorer on http://{addr}");
+        server.await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "explorer",
+    about = "Serve a local read-only dashboard of the configured subnets"
+)]
+pub(crate) struct ExplorerArgs {
+    #[arg(
+        long,
+        default_value = "127.0.0.1:3030",
+        help = "The address to serve the dashboard on"
+    )]
+    pub listen: String,
+}
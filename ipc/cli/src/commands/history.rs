@@ -0,0 +1,165 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! History cli command handler.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::history::{HistoryFilter, HistoryStatus};
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::commands::open_history;
+use crate::{CommandLineHandler, GlobalArguments};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The command to list the operations broadcast by this client.
+pub(crate) struct ShowHistory;
+
+#[async_trait]
+impl CommandLineHandler for ShowHistory {
+    type Arguments = HistoryArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("list history with args: {:?}", arguments);
+
+        let history = open_history(&global.config_path());
+        let filter = HistoryFilter {
+            subnet: arguments
+                .subnet
+                .as_deref()
+                .map(SubnetID::from_str)
+                .transpose()?,
+            operation: arguments.operation.clone(),
+            since: arguments.since.as_deref().map(parse_date).transpose()?,
+            // a date includes the whole day
+            until: arguments
+                .until
+                .as_deref()
+                .map(|d| {
+                    parse_date(d).map(|t| match is_day(d) {
+                        true => t + SECONDS_PER_DAY - 1,
+                        false => t,
+                    })
+                })
+                .transpose()?,
+        };
+
+        for entry in history.list(&filter)? {
+            let status = match &entry.status {
+                HistoryStatus::Succeeded { result } => format!("ok: {result}"),
+                HistoryStatus::Failed { error } => format!("failed: {error}"),
+            };
+            let params = entry
+                .params
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "{} {} {} [{params}] {status}",
+                format_timestamp(entry.timestamp),
+                entry.operation,
+                entry.subnet
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "history",
+    about = "List the operations broadcast from this machine, such as create, join, fund, release and checkpoint"
+)]
+pub(crate) struct HistoryArgs {
+    #[arg(long, help = "Only list the operations of this subnet")]
+    pub subnet: Option<String>,
+    #[arg(long, help = "Only list this kind of operation, e.g. fund")]
+    pub operation: Option<String>,
+    #[arg(
+        long,
+        help = "Only list operations from this date (YYYY-MM-DD, UTC) or unix timestamp"
+    )]
+    pub since: Option<String>,
+    #[arg(
+        long,
+        help = "Only list operations until this date (YYYY-MM-DD, UTC) or unix timestamp"
+    )]
+    pub until: Option<String>,
+}
+
+fn is_day(s: &str) -> bool {
+    s.contains('-')
+}
+
+/// Parses a `YYYY-MM-DD` day, as the unix timestamp of its start, or a unix timestamp.
+fn parse_date(s: &str) -> anyhow::Result<u64> {
+    if !is_day(s) {
+        return Ok(s.parse()?);
+    }
+    let parts = s
+        .split('-')
+        .map(|p| p.parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| anyhow!("invalid date {s}, expected YYYY-MM-DD"))?;
+    let [year, month, day] = parts[..] else {
+        return Err(anyhow!("invalid date {s}, expected YYYY-MM-DD"));
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || year < 1970 {
+        return Err(anyhow!("invalid date {s}, expected YYYY-MM-DD"));
+    }
+    Ok(days_from_civil(year, month, day) as u64 * SECONDS_PER_DAY)
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    let secs = timestamp % SECONDS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Days since the unix epoch of a proleptic gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The proleptic gregorian date of a number of days since the unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_timestamp, parse_date};
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(parse_date("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_date("2024-02-29").unwrap(), 1709164800);
+        assert_eq!(parse_date("1709164800").unwrap(), 1709164800);
+        assert!(parse_date("2024-13-01").is_err());
+        assert_eq!(format_timestamp(1709164800 + 3723), "2024-02-29 01:02:03");
+    }
+}
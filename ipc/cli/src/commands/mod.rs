@@ -2,18 +2,29 @@
 // SPDX-License-Identifier: MIT
 //! This mod contains the different command line implementations.
 
+mod addr;
+mod approval;
 mod checkpoint;
 mod config;
 mod crossmsg;
+mod explorer;
+mod history;
+mod serve;
 // mod daemon;
 mod subnet;
 mod util;
 mod validator;
 mod wallet;
 
+use crate::amount::AmountFormat;
+use crate::commands::addr::AddrCommandsArgs;
 use crate::commands::checkpoint::CheckpointCommandsArgs;
 use crate::commands::crossmsg::CrossMsgsCommandsArgs;
+use crate::commands::explorer::{Explorer, ExplorerArgs};
+use crate::commands::history::{HistoryArgs, ShowHistory};
+use crate::commands::serve::{Serve, ServeArgs};
 use crate::commands::util::UtilCommandsArgs;
+use crate::errors::CliError;
 use crate::GlobalArguments;
 use anyhow::{anyhow, Context, Result};
 
@@ -24,11 +35,17 @@ use ipc_api::ethers_address_to_fil_address;
 
 use fvm_shared::address::set_current_network;
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::addressbook::{AddressBook, LABEL_PREFIX};
 use ipc_provider::config::{Config, Subnet};
+use ipc_provider::history::{History, HISTORY_DIR};
+use ipc_provider::manager::FeeEstimate;
 use std::fmt::Debug;
+use std::future::Future;
 use std::io;
 use std::path::Path;
 use std::str::FromStr;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
 
 use crate::commands::config::ConfigCommandsArgs;
 use crate::commands::validator::ValidatorCommandsArgs;
@@ -50,6 +67,10 @@ enum Commands {
     Checkpoint(CheckpointCommandsArgs),
     Util(UtilCommandsArgs),
     Validator(ValidatorCommandsArgs),
+    Addr(AddrCommandsArgs),
+    History(HistoryArgs),
+    Explorer(ExplorerArgs),
+    Serve(ServeArgs),
 }
 
 #[derive(Debug, Parser)]
@@ -117,6 +138,9 @@ struct GlobalOptions {
 pub async fn cli() -> anyhow::Result<()> {
     let global = GlobalOptions::parse();
     set_current_network(global.global_params.network());
+    if global.global_params.wait_for_lock() {
+        ipc_wallet::set_lock_wait(None);
+    }
 
     // parse the arguments
     let args = IPCAgentCliCommands::parse();
@@ -137,6 +161,10 @@ pub async fn cli() -> anyhow::Result<()> {
                 Commands::Checkpoint(args) => args.handle(global).await,
                 Commands::Util(args) => args.handle(global).await,
                 Commands::Validator(args) => args.handle(global).await,
+                Commands::Addr(args) => args.handle(global).await,
+                Commands::History(args) => ShowHistory::handle(global, args).await,
+                Commands::Explorer(args) => Explorer::handle(global, args).await,
+                Commands::Serve(args) => Serve::handle(global, args).await,
             };
 
             r.with_context(|| format!("error processing command {:?}", args.command))
@@ -151,7 +179,41 @@ fn print_completions<G: Generator>(gen: G, cmd: &mut Command) {
 }
 
 pub(crate) fn get_ipc_provider(global: &GlobalArguments) -> Result<ipc_provider::IpcProvider> {
-    ipc_provider::IpcProvider::new_from_config(global.config_path())
+    let mut provider = ipc_provider::IpcProvider::new_from_config(global.config_path())
+        .map_err(|e| CliError::Config(format!("{e:#}")))?;
+    if let Some(timeout) = global.timeout() {
+        provider.with_deadline(timeout);
+    }
+    if let Some(dir) = global.record_rpc() {
+        provider.with_rpc_recording(dir)?;
+    }
+    provider.with_history(open_history(&global.config_path()));
+    Ok(provider)
+}
+
+/// Opens the history of broadcast operations, kept next to the config file.
+pub(crate) fn open_history(config_path: &str) -> History {
+    let dir = Path::new(config_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(HISTORY_DIR);
+    History::open(dir)
+}
+
+/// Prints the expected cost of an operation run with `--estimate-only`.
+pub(crate) fn print_fee_estimate(estimate: &FeeEstimate, amounts: &AmountFormat) {
+    println!("gas limit: {}", estimate.gas_limit);
+    println!(
+        "max fee per gas: {} (priority fee: {})",
+        amounts.format(&estimate.max_fee_per_gas),
+        amounts.format(&estimate.max_priority_fee_per_gas)
+    );
+    println!("max gas cost: {}", amounts.format(&estimate.max_gas_cost()));
+    println!("value: {}", amounts.format(&estimate.value));
+    println!(
+        "max total cost: {}",
+        amounts.format(&estimate.max_total_cost())
+    );
 }
 
 pub(crate) fn f64_to_token_amount(f: f64) -> anyhow::Result<TokenAmount> {
@@ -161,8 +223,11 @@ pub(crate) fn f64_to_token_amount(f: f64) -> anyhow::Result<TokenAmount> {
 }
 
 /// Receives a f/eth-address as an input and returns the corresponding
-/// filecoin or delegated address, respectively
+/// filecoin or delegated address, respectively. An `@label` is looked up in the address book.
 pub(crate) fn require_fil_addr_from_str(s: &str) -> anyhow::Result<fvm_shared::address::Address> {
+    if s.starts_with(LABEL_PREFIX) {
+        return AddressBook::load(AddressBook::default_path())?.get(s);
+    }
     let addr = match fvm_shared::address::Address::from_str(s) {
         Err(_) => {
             // see if it is an eth address
@@ -174,17 +239,45 @@ pub(crate) fn require_fil_addr_from_str(s: &str) -> anyhow::Result<fvm_shared::a
     Ok(addr)
 }
 
+/// Resolves on the first SIGINT or SIGTERM, so long-running commands can finish their current
+/// work before exiting. The handlers are installed right away, so a signal received before the
+/// future is awaited is not lost.
+#[cfg(unix)]
+pub(crate) fn shutdown_signal() -> Result<impl Future<Output = ()>> {
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    Ok(async move {
+        tokio::select! {
+            _ = interrupt.recv() => log::info!("received SIGINT, shutting down"),
+            _ = terminate.recv() => log::info!("received SIGTERM, shutting down"),
+        }
+    })
+}
+
+/// Resolves on the first ctrl-c on platforms without unix signals.
+#[cfg(not(unix))]
+pub(crate) fn shutdown_signal() -> Result<impl Future<Output = ()>> {
+    Ok(async {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => log::info!("received ctrl-c, shutting down"),
+            Err(e) => {
+                // keep running rather than shutting down right away
+                log::error!("cannot listen for ctrl-c: {e}");
+                std::future::pending().await
+            }
+        }
+    })
+}
+
 /// Get the subnet configuration from the config path
 pub(crate) fn get_subnet_config(
     config_path: impl AsRef<Path>,
     subnet: &SubnetID,
 ) -> Result<Subnet> {
     let config = Config::from_file(&config_path)?;
-    Ok(config
-        .subnets
-        .get(subnet)
-        .ok_or_else(|| anyhow!("{subnet} is not configured"))?
-        .clone())
+    config
+        .subnet(subnet)
+        .ok_or_else(|| anyhow!("{subnet} is not configured"))
 }
 
 #[cfg(test)]
@@ -0,0 +1,199 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! API keys for an api shared by several applications, each with its own rate limit and the
+//! endpoints it may call.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use fs_err as fs;
+use serde::Deserialize;
+
+use super::ApiError;
+
+/// The header requests carry their api key in. The authorization header is left to the write
+/// token.
+pub(super) const API_KEY_HEADER: &str = "x-api-key";
+
+/// The access config, read from a TOML file such as:
+///
+/// ```toml
+/// [[keys]]
+/// name = "explorer"
+/// key = "..."
+/// requests_per_minute = 120
+/// methods = ["subnets", "validators", "checkpoints"]
+/// ```
+///
+/// Methods are the endpoint paths without the `/api/` prefix. A key without `methods` may call
+/// every endpoint, and one without `requests_per_minute` is not rate limited.
+#[derive(Debug, Deserialize)]
+pub(super) struct AccessConfig {
+    keys: Vec<ApiKey>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ApiKey {
+    name: String,
+    key: String,
+    requests_per_minute: Option<u32>,
+    methods: Option<Vec<String>>,
+}
+
+impl AccessConfig {
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let config: Self = toml::from_str(&fs::read_to_string(path)?)?;
+        let mut seen = HashSet::new();
+        for key in &config.keys {
+            if !seen.insert(&key.key) {
+                return Err(anyhow!("api key of {} is used more than once", key.name));
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// A token bucket holding up to a minute's worth of requests.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Checks the api key, allowed methods and rate limit of every request.
+pub(super) struct AccessControl {
+    keys: HashMap<String, ApiKey>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl AccessControl {
+    pub fn new(config: AccessConfig) -> Self {
+        Self {
+            keys: config
+                .keys
+                .into_iter()
+                .map(|k| (k.key.clone(), k))
+                .collect(),
+            buckets: Default::default(),
+        }
+    }
+
+    /// Admits a call to `method` with `key` at `now`, or says why it is refused.
+    fn admit(&self, key: Option<&str>, method: &str, now: Instant) -> Result<(), ApiError> {
+        let key = key.and_then(|k| self.keys.get(k)).ok_or_else(|| {
+            ApiError(
+                StatusCode::UNAUTHORIZED,
+                anyhow!("missing or invalid api key"),
+            )
+        })?;
+
+        if let Some(methods) = &key.methods {
+            if !methods.iter().any(|m| m == method) {
+                return Err(ApiError(
+                    StatusCode::FORBIDDEN,
+                    anyhow!("api key {} may not call {method}", key.name),
+                ));
+            }
+        }
+
+        let Some(per_minute) = key.requests_per_minute else {
+            return Ok(());
+        };
+        let per_minute = per_minute as f64;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(key.key.clone()).or_insert(Bucket {
+            tokens: per_minute,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_minute / 60.0).min(per_minute);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return Err(ApiError(
+                StatusCode::TOO_MANY_REQUESTS,
+                anyhow!(
+                    "api key {} is over its limit of {per_minute} requests per minute",
+                    key.name
+                ),
+            ));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+/// The middleware refusing the requests [`AccessControl`] does not admit.
+pub(super) async fn check<B>(
+    State(access): State<Arc<AccessControl>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let method = request.uri().path().trim_start_matches("/api/").to_string();
+    let key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    match access.admit(key, &method, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessConfig, AccessControl};
+    use axum::http::StatusCode;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_access_control() {
+        let config: AccessConfig = toml::from_str(
+            r#"
+            [[keys]]
+            name = "explorer"
+            key = "k1"
+            requests_per_minute = 2
+            methods = ["subnets"]
+
+            [[keys]]
+            name = "ops"
+            key = "k2"
+            "#,
+        )
+        .unwrap();
+        let access = AccessControl::new(config);
+        let now = Instant::now();
+
+        let status = |key: Option<&str>, method: &str, at: Instant| {
+            access.admit(key, method, at).err().map(|e| e.0)
+        };
+
+        assert_eq!(status(None, "subnets", now), Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            status(Some("k3"), "subnets", now),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(status(Some("k1"), "fund", now), Some(StatusCode::FORBIDDEN));
+        assert_eq!(status(Some("k1"), "subnets", now), None);
+        assert_eq!(status(Some("k1"), "subnets", now), None);
+        assert_eq!(
+            status(Some("k1"), "subnets", now),
+            Some(StatusCode::TOO_MANY_REQUESTS)
+        );
+        // a request is earned back every 30 seconds
+        assert_eq!(
+            status(Some("k1"), "subnets", now + Duration::from_secs(30)),
+            None
+        );
+        // other keys are not affected
+        assert_eq!(status(Some("k2"), "fund", now), None);
+    }
+}
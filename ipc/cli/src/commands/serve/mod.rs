@@ -0,0 +1,164 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! A local JSON API over the provider, for integrations that do not link the Rust crates.
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::IpcProvider;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::commands::shutdown_signal;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+use access::{AccessConfig, AccessControl};
+
+mod access;
+mod read;
+mod write;
+
+/// The command to serve the API.
+pub(crate) struct Serve;
+
+#[async_trait]
+impl CommandLineHandler for Serve {
+    type Arguments = ServeArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("start api server on {}", arguments.listen);
+
+        let provider = get_ipc_provider(global)?;
+        let addr = SocketAddr::from_str(&arguments.listen)?;
+
+        let mut router = read_router(provider.clone());
+        if let Some(token) = arguments.write_token()? {
+            log::info!("write operations enabled");
+            router = router.merge(write::router(
+                provider,
+                Arc::new(token),
+                global.config_path(),
+            ));
+        }
+        if let Some(path) = &arguments.access_config {
+            let access = Arc::new(AccessControl::new(AccessConfig::from_file(path)?));
+            log::info!("api keys required, read from {path}");
+            router = router.layer(middleware::from_fn_with_state(access, access::check));
+        }
+
+        let server = axum::Server::try_bind(&addr)?
+            .serve(router.into_make_service())
+            .with_graceful_shutdown(shutdown_signal()?);
+        println!("serving the api on http://{addr}");
+        server.await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "serve",
+    about = "Serve the provider read operations, and optionally write operations, as a local JSON API"
+)]
+pub(crate) struct ServeArgs {
+    #[arg(
+        long,
+        default_value = "127.0.0.1:3031",
+        help = "The address to serve the api on"
+    )]
+    pub listen: String,
+    #[arg(
+        long,
+        env = "IPC_SERVE_WRITE_TOKEN",
+        help = "Enables fund, release and send-value, signed with the local keystore, for requests with this bearer token. Prefer the env var or --write-token-file, as arguments show in the process list"
+    )]
+    pub write_token: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "write_token",
+        help = "Reads the write token from this file instead of --write-token"
+    )]
+    pub write_token_file: Option<String>,
+    #[arg(
+        long,
+        help = "A TOML file of the api keys requests must carry in the x-api-key header, each with its rate limit and allowed endpoints"
+    )]
+    pub access_config: Option<String>,
+}
+
+impl ServeArgs {
+    /// The write token, if write operations are enabled.
+    fn write_token(&self) -> anyhow::Result<Option<String>> {
+        let Some(path) = &self.write_token_file else {
+            return Ok(self.write_token.clone());
+        };
+        let token = fs_err::read_to_string(path)?.trim().to_string();
+        if token.is_empty() {
+            return Err(anyhow!("the write token file {path} is empty"));
+        }
+        Ok(Some(token))
+    }
+}
+
+/// The read-only endpoints, shared with the explorer.
+pub(crate) fn read_router(provider: IpcProvider) -> Router {
+    Router::new()
+        .route("/api/subnets", get(read::subnets))
+        .route("/api/validators", get(read::validators))
+        .route("/api/checkpoints", get(read::checkpoints))
+        .route("/api/topdown", get(read::topdown))
+        .route("/api/chain-head", get(read::chain_head))
+        .route("/api/genesis-epoch", get(read::genesis_epoch))
+        .route("/api/bootstrap-status", get(read::bootstrap_status))
+        .route("/api/balance", get(read::balance))
+        .with_state(provider)
+}
+
+/// Failures are reported as JSON instead of taking down the server.
+pub(crate) struct ApiError(StatusCode, anyhow::Error);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": format!("{:#}", self.1) }));
+        (self.0, body).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, e)
+    }
+}
+
+impl ApiError {
+    fn bad_request(e: anyhow::Error) -> Self {
+        Self(StatusCode::BAD_REQUEST, e)
+    }
+}
+
+pub(crate) type ApiResult = Result<Json<Value>, ApiError>;
+
+#[derive(Deserialize)]
+pub(crate) struct SubnetQuery {
+    subnet: String,
+}
+
+impl SubnetQuery {
+    fn subnet(&self) -> Result<SubnetID, ApiError> {
+        parse_subnet(&self.subnet)
+    }
+}
+
+fn parse_subnet(s: &str) -> Result<SubnetID, ApiError> {
+    SubnetID::from_str(s).map_err(|e| ApiError::bad_request(anyhow!("invalid subnet {s}: {e}")))
+}
@@ -0,0 +1,157 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Read-only endpoints.
+
+use axum::extract::{Query, State};
+use axum::Json;
+use fvm_shared::econ::TokenAmount;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::parallel::{join_bounded, DEFAULT_CONCURRENCY};
+use ipc_provider::IpcProvider;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{parse_subnet, ApiError, ApiResult, SubnetQuery};
+use crate::amount::AmountFormat;
+use crate::require_fil_addr_from_str;
+
+/// The configured subnets, each with the children registered in its gateway.
+pub(super) async fn subnets(State(provider): State<IpcProvider>) -> ApiResult {
+    let mut ids = provider.list_connections().into_keys().collect::<Vec<_>>();
+    ids.sort_by_key(|id| id.to_string());
+
+    let subnets = join_bounded(
+        ids.into_iter().map(|id| subnet_children(&provider, id)),
+        DEFAULT_CONCURRENCY,
+    )
+    .await;
+    Ok(Json(json!(subnets)))
+}
+
+/// A configured subnet with the children registered in its gateway, or the error listing them.
+async fn subnet_children(provider: &IpcProvider, id: SubnetID) -> Value {
+    let children = match provider.list_child_subnets(None, &id).await {
+        Ok(children) => {
+            let mut children = children
+                .into_values()
+                .map(|c| {
+                    json!({
+                        "id": c.id.to_string(),
+                        "stake": format_amount(&c.stake),
+                        "circ_supply": format_amount(&c.circ_supply),
+                        "genesis_epoch": c.genesis_epoch,
+                    })
+                })
+                .collect::<Vec<_>>();
+            children.sort_by_key(|c| c["id"].to_string());
+            json!(children)
+        }
+        Err(e) => json!({ "error": format!("{e:#}") }),
+    };
+    json!({ "id": id.to_string(), "children": children })
+}
+
+pub(super) async fn validators(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let validators = provider.list_validators(&query.subnet()?).await?;
+    let validators = validators
+        .into_iter()
+        .map(|(address, info)| {
+            json!({
+                "address": address.to_string(),
+                "confirmed_collateral": format_amount(info.staking.confirmed_collateral()),
+                "total_collateral": format_amount(info.staking.total_collateral()),
+                "active": info.is_active,
+                "waiting": info.is_waiting,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(Json(json!(validators)))
+}
+
+pub(super) async fn checkpoints(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let subnet = query.subnet()?;
+    let last_checkpoint = provider.last_bottom_up_checkpoint_height(&subnet).await?;
+    // the subnet itself may not be reachable from here
+    let chain_head = provider.get_chain_head_height(&subnet).await.ok();
+    Ok(Json(json!({
+        "last_checkpoint_height": last_checkpoint,
+        "chain_head": chain_head,
+    })))
+}
+
+pub(super) async fn topdown(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let subnet = query.subnet()?;
+    let queue = provider.top_down_queue(&subnet).await?;
+    let finality = provider.latest_parent_finality(&subnet).await?;
+    Ok(Json(json!({
+        "committed": queue.committed,
+        "applied": queue.applied,
+        "pending": queue.pending(),
+        "parent_finality": finality,
+    })))
+}
+
+pub(super) async fn chain_head(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let height = provider.chain_head(&query.subnet()?).await?;
+    Ok(Json(json!({ "height": height })))
+}
+
+pub(super) async fn genesis_epoch(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let epoch = provider.genesis_epoch(&query.subnet()?).await?;
+    Ok(Json(json!({ "epoch": epoch })))
+}
+
+pub(super) async fn bootstrap_status(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<SubnetQuery>,
+) -> ApiResult {
+    let status = provider.bootstrap_status(&query.subnet()?).await?;
+    Ok(Json(json!({
+        "bootstrapped": status.bootstrapped,
+        "validators": status.validators,
+        "min_validators": status.min_validators,
+        "collateral": format_amount(&status.collateral),
+        "min_collateral": format_amount(&status.min_collateral),
+        "genesis_epoch": status.genesis_epoch,
+    })))
+}
+
+#[derive(Deserialize)]
+pub(super) struct BalanceQuery {
+    subnet: String,
+    address: String,
+}
+
+pub(super) async fn balance(
+    State(provider): State<IpcProvider>,
+    Query(query): Query<BalanceQuery>,
+) -> ApiResult {
+    let subnet = parse_subnet(&query.subnet)?;
+    let address = require_fil_addr_from_str(&query.address).map_err(ApiError::bad_request)?;
+    let balance = provider.wallet_balance(&subnet, &address).await?;
+    Ok(Json(json!({
+        "address": address.to_string(),
+        "balance": format_amount(&balance),
+    })))
+}
+
+/// Amounts are strings with their unit in FIL, such as `"1.5 FIL"`, whatever the denomination
+/// the cli prints with.
+fn format_amount(amount: &TokenAmount) -> String {
+    AmountFormat::default().format(amount)
+}
@@ -0,0 +1,176 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Write endpoints, signed with the local keystore. Only served with a write token.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use ethers::utils::keccak256;
+use fvm_shared::address::Address;
+use ipc_provider::approval::IntentFile;
+use ipc_provider::IpcProvider;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{parse_subnet, ApiError, ApiResult};
+use crate::commands::approval::accept_intent;
+use crate::commands::crossmsg::release::release_params;
+use crate::{f64_to_token_amount, require_fil_addr_from_str};
+
+#[derive(Clone)]
+pub(super) struct WriteState {
+    provider: IpcProvider,
+    token: Arc<String>,
+    /// Executed intents are recorded next to the config, as they are by the cli commands.
+    config_path: Arc<String>,
+}
+
+pub(super) fn router(provider: IpcProvider, token: Arc<String>, config_path: String) -> Router {
+    Router::new()
+        .route("/api/fund", post(fund))
+        .route("/api/release", post(release))
+        .route("/api/send-value", post(send_value))
+        .with_state(WriteState {
+            provider,
+            token,
+            config_path: Arc::new(config_path),
+        })
+}
+
+/// A transfer of `amount` whole FIL. Addresses default to the configured sender.
+#[derive(Deserialize)]
+pub(super) struct Transfer {
+    subnet: String,
+    from: Option<String>,
+    to: Option<String>,
+    amount: f64,
+    /// The approved intent of a release covered by the `approvals` config.
+    #[serde(default)]
+    approved: Option<IntentFile>,
+}
+
+impl Transfer {
+    fn address(s: &Option<String>) -> Result<Option<Address>, ApiError> {
+        s.as_deref()
+            .map(require_fil_addr_from_str)
+            .transpose()
+            .map_err(ApiError::bad_request)
+    }
+}
+
+impl WriteState {
+    /// Checks the bearer token and returns a provider to sign with.
+    fn authorize(&self, headers: &HeaderMap) -> Result<IpcProvider, ApiError> {
+        let expected = format!("Bearer {}", self.token);
+        match headers.get(header::AUTHORIZATION) {
+            Some(value) if token_matches(value.as_bytes(), expected.as_bytes()) => {
+                Ok(self.provider.clone())
+            }
+            _ => Err(ApiError(
+                StatusCode::UNAUTHORIZED,
+                anyhow!("missing or invalid write token"),
+            )),
+        }
+    }
+}
+
+/// Compares the hashes of the tokens in constant time, so the time a request takes does not
+/// tell how much of the token it got right.
+fn token_matches(given: &[u8], expected: &[u8]) -> bool {
+    keccak256(given)
+        .iter()
+        .zip(keccak256(expected).iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+async fn fund(
+    State(state): State<WriteState>,
+    headers: HeaderMap,
+    Json(transfer): Json<Transfer>,
+) -> ApiResult {
+    let mut provider = state.authorize(&headers)?;
+    let subnet = parse_subnet(&transfer.subnet)?;
+    let amount = f64_to_token_amount(transfer.amount).map_err(ApiError::bad_request)?;
+    let epoch = provider
+        .fund(
+            subnet,
+            None,
+            Transfer::address(&transfer.from)?,
+            Transfer::address(&transfer.to)?,
+            amount,
+        )
+        .await?;
+    Ok(Json(json!({ "epoch": epoch })))
+}
+
+async fn release(
+    State(state): State<WriteState>,
+    headers: HeaderMap,
+    Json(transfer): Json<Transfer>,
+) -> ApiResult {
+    let mut provider = state.authorize(&headers)?;
+    let subnet = parse_subnet(&transfer.subnet)?;
+    let amount = f64_to_token_amount(transfer.amount).map_err(ApiError::bad_request)?;
+    let from = provider.resolve_sender(&subnet, Transfer::address(&transfer.from)?)?;
+    let to = Transfer::address(&transfer.to)?;
+
+    // the write token does not stand in for the approvals the cli command requires
+    let config = provider.config();
+    if let Some(approvals) = config
+        .approvals
+        .as_ref()
+        .filter(|a| a.covers_release(transfer.amount))
+    {
+        let file = transfer.approved.as_ref().ok_or_else(|| {
+            ApiError(
+                StatusCode::FORBIDDEN,
+                anyhow!(
+                    "release needs {} approvals, pass the approved intent as `approved`",
+                    approvals.threshold
+                ),
+            )
+        })?;
+        let params = release_params(&subnet, &None, &from, &to, &amount);
+        accept_intent(&state.config_path, approvals, file, "release", &params)
+            .map_err(|e| ApiError(StatusCode::FORBIDDEN, e))?;
+    }
+
+    let epoch = provider
+        .release(subnet, None, Some(from), to, amount)
+        .await?;
+    Ok(Json(json!({ "epoch": epoch })))
+}
+
+async fn send_value(
+    State(state): State<WriteState>,
+    headers: HeaderMap,
+    Json(transfer): Json<Transfer>,
+) -> ApiResult {
+    let mut provider = state.authorize(&headers)?;
+    let subnet = parse_subnet(&transfer.subnet)?;
+    let amount = f64_to_token_amount(transfer.amount).map_err(ApiError::bad_request)?;
+    let to = Transfer::address(&transfer.to)?
+        .ok_or_else(|| ApiError::bad_request(anyhow!("send-value needs a to address")))?;
+    provider
+        .send_value(&subnet, Transfer::address(&transfer.from)?, to, amount)
+        .await?;
+    Ok(Json(json!({})))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::token_matches;
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches(b"Bearer secret", b"Bearer secret"));
+        assert!(!token_matches(b"Bearer secre", b"Bearer secret"));
+        assert!(!token_matches(b"Bearer secrets", b"Bearer secret"));
+        assert!(!token_matches(b"", b"Bearer secret"));
+    }
+}
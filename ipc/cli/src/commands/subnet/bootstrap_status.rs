@@ -0,0 +1,98 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Subnet bootstrap status cli command
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::manager::BootstrapStatus;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::amount::AmountFormat;
+use crate::commands::shutdown_signal;
+use crate::errors::CliError;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The command to report the progress of a subnet towards bootstrapping.
+pub(crate) struct SubnetBootstrapStatus;
+
+#[async_trait]
+impl CommandLineHandler for SubnetBootstrapStatus {
+    type Arguments = SubnetBootstrapStatusArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("get subnet bootstrap status with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+
+        let started = Instant::now();
+        let timeout = arguments.timeout.map(Duration::from_secs);
+        let interval = Duration::from_secs(arguments.interval);
+        let shutdown = shutdown_signal()?;
+        tokio::pin!(shutdown);
+
+        loop {
+            let status = provider.bootstrap_status(&subnet).await?;
+            println!("{}", format_status(&status, &global.amount_format()));
+
+            if status.bootstrapped || !arguments.watch {
+                return Ok(());
+            }
+            if timeout.map_or(false, |t| started.elapsed() >= t) {
+                return Err(CliError::NotBootstrapped(format!(
+                    "{subnet} after {}s",
+                    started.elapsed().as_secs()
+                ))
+                .into());
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => {
+                    return Err(anyhow!("interrupted before subnet {subnet} bootstrapped"));
+                }
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+}
+
+fn format_status(status: &BootstrapStatus, amounts: &AmountFormat) -> String {
+    match status.genesis_epoch {
+        Some(epoch) => format!("bootstrapped, genesis epoch: {epoch}"),
+        None => format!(
+            "waiting: validators {}/{}, collateral {}/{}",
+            status.validators,
+            status.min_validators,
+            amounts.format(&status.collateral),
+            amounts.format(&status.min_collateral)
+        ),
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "bootstrap-status",
+    about = "Show the joined validators and collateral of a subnet against its activation thresholds"
+)]
+pub(crate) struct SubnetBootstrapStatusArgs {
+    #[arg(long, help = "The subnet id to query")]
+    pub subnet: String,
+    #[arg(long, help = "Keep polling until the subnet is bootstrapped")]
+    pub watch: bool,
+    #[arg(
+        long,
+        default_value = "10",
+        help = "The number of seconds between polls with --watch"
+    )]
+    pub interval: u64,
+    #[arg(
+        long,
+        requires = "watch",
+        help = "Exit with an error if the subnet is not bootstrapped after this many seconds"
+    )]
+    pub timeout: Option<u64>,
+}
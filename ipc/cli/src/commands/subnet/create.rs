@@ -9,12 +9,18 @@ use async_trait::async_trait;
 use clap::Args;
 use fvm_shared::clock::ChainEpoch;
 
-use ipc_api::subnet::{Asset, AssetKind, PermissionMode};
+use fvm_shared::address::Address;
+use ipc_api::subnet::{Asset, AssetKind, ConsensusType, ConstructParams, PermissionMode};
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::manager::{CreatedSubnet, FeeEstimate};
 
 use crate::commands::get_ipc_provider;
+use crate::commands::subnet::receipt::SubnetReceipt;
 use crate::commands::subnet::ZERO_ADDRESS;
-use crate::{f64_to_token_amount, require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
+use crate::{
+    f64_to_token_amount, get_subnet_config, print_fee_estimate, require_fil_addr_from_str,
+    CommandLineHandler, GlobalArguments,
+};
 
 const DEFAULT_ACTIVE_VALIDATORS: u16 = 100;
 
@@ -25,7 +31,7 @@ impl CreateSubnet {
     pub async fn create(
         global: &GlobalArguments,
         arguments: &CreateSubnetArgs,
-    ) -> anyhow::Result<String> {
+    ) -> anyhow::Result<CreatedSubnet> {
         let mut provider = get_ipc_provider(global)?;
         let parent = SubnetID::from_str(&arguments.parent)?;
 
@@ -36,19 +42,9 @@ impl CreateSubnet {
 
         let supply_source = parse_supply_source(arguments)?;
         let collateral_source = parse_collateral_source(arguments)?;
-
-        let raw_addr = arguments
-            .validator_gater
-            .clone()
-            .unwrap_or(ZERO_ADDRESS.to_string());
-        let validator_gater = require_fil_addr_from_str(&raw_addr)?;
-
-        let raw_addr = arguments
-            .validator_rewarder
-            .clone()
-            .unwrap_or(ZERO_ADDRESS.to_string());
-        let validator_rewarder = require_fil_addr_from_str(&raw_addr)?;
-        let addr = provider
+        let validator_gater = parse_optional_contract(&arguments.validator_gater)?;
+        let validator_rewarder = parse_optional_contract(&arguments.validator_rewarder)?;
+        provider
             .create_subnet(
                 from,
                 parent,
@@ -65,10 +61,114 @@ impl CreateSubnet {
                 validator_gater,
                 validator_rewarder,
             )
-            .await?;
+            .await
+    }
 
-        Ok(addr.to_string())
+    /// Previews the gas and fees of creating the subnet without sending the transaction.
+    pub async fn estimate(
+        global: &GlobalArguments,
+        arguments: &CreateSubnetArgs,
+    ) -> anyhow::Result<FeeEstimate> {
+        let mut provider = get_ipc_provider(global)?;
+        let from = parse_from(arguments)?;
+        let params = construct_params(global, arguments)?;
+
+        provider.estimate_create_subnet(from, params).await
     }
+
+    /// Sends the creation of the subnet and returns the transaction hash without waiting for it.
+    pub async fn submit(
+        global: &GlobalArguments,
+        arguments: &CreateSubnetArgs,
+    ) -> anyhow::Result<[u8; 32]> {
+        let mut provider = get_ipc_provider(global)?;
+        let from = parse_from(arguments)?;
+        let params = construct_params(global, arguments)?;
+
+        provider.submit_create_subnet(from, params).await
+    }
+}
+
+fn parse_from(arguments: &CreateSubnetArgs) -> anyhow::Result<Option<Address>> {
+    match &arguments.from {
+        Some(address) => Ok(Some(require_fil_addr_from_str(address)?)),
+        None => Ok(None),
+    }
+}
+
+/// The constructor parameters of the subnet actor described by `arguments`.
+fn construct_params(
+    global: &GlobalArguments,
+    arguments: &CreateSubnetArgs,
+) -> anyhow::Result<ConstructParams> {
+    let parent = SubnetID::from_str(&arguments.parent)?;
+
+    Ok(ConstructParams {
+        parent: parent.clone(),
+        ipc_gateway_addr: get_subnet_config(global.config_path(), &parent)?.gateway_addr(),
+        consensus: ConsensusType::Fendermint,
+        min_validator_stake: f64_to_token_amount(arguments.min_validator_stake)?,
+        min_validators: arguments.min_validators,
+        bottomup_check_period: arguments.bottomup_check_period,
+        active_validators_limit: arguments
+            .active_validators_limit
+            .unwrap_or(DEFAULT_ACTIVE_VALIDATORS),
+        min_cross_msg_fee: f64_to_token_amount(arguments.min_cross_msg_fee)?,
+        permission_mode: arguments.permission_mode,
+        supply_source: parse_supply_source(arguments)?,
+        collateral_source: parse_collateral_source(arguments)?,
+        validator_gater: parse_optional_contract(&arguments.validator_gater)?,
+        validator_rewarder: parse_optional_contract(&arguments.validator_rewarder)?,
+    })
+}
+
+/// Records the parameters of a newly created subnet, for `subnet receipt` and as defaults for
+/// later commands.
+fn write_receipt(
+    global: &GlobalArguments,
+    arguments: &CreateSubnetArgs,
+    created: &CreatedSubnet,
+) -> anyhow::Result<()> {
+    let mut provider = get_ipc_provider(global)?;
+    let parent = SubnetID::from_str(&arguments.parent)?;
+    let subnet = SubnetID::new_from_parent(&parent, created.address);
+    let creator = provider.resolve_sender(&parent, parse_from(arguments)?)?;
+
+    let receipt = SubnetReceipt {
+        subnet_id: subnet.to_string(),
+        parent: parent.to_string(),
+        creator: Some(creator.to_string()),
+        tx_hash: Some(format!("0x{}", hex::encode(created.tx_hash))),
+        parent_epoch: created.height,
+        created_at: SubnetReceipt::now(),
+        min_validator_stake: arguments.min_validator_stake,
+        min_validators: arguments.min_validators,
+        bottomup_check_period: arguments.bottomup_check_period,
+        active_validators_limit: arguments
+            .active_validators_limit
+            .unwrap_or(DEFAULT_ACTIVE_VALIDATORS),
+        min_cross_msg_fee: arguments.min_cross_msg_fee,
+        permission_mode: format!("{:?}", arguments.permission_mode).to_lowercase(),
+        supply_source_kind: format!("{:?}", arguments.supply_source_kind).to_lowercase(),
+        supply_source_address: arguments.supply_source_address.clone(),
+        collateral_source_kind: arguments
+            .collateral_source_kind
+            .map(|k| format!("{k:?}").to_lowercase()),
+        collateral_source_address: arguments.collateral_source_address.clone(),
+        validator_gater: arguments.validator_gater.clone(),
+        validator_rewarder: arguments.validator_rewarder.clone(),
+    };
+    let path = receipt.save(&global.config_path())?;
+    println!(
+        "subnet {subnet} created, receipt written to {}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Parses an optional contract address, defaulting to the zero address when it is not set.
+fn parse_optional_contract(addr: &Option<String>) -> anyhow::Result<Address> {
+    require_fil_addr_from_str(addr.as_deref().unwrap_or(ZERO_ADDRESS))
 }
 
 fn parse_supply_source(arguments: &CreateSubnetArgs) -> anyhow::Result<Asset> {
@@ -107,14 +207,37 @@ impl CommandLineHandler for CreateSubnet {
     async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
         log::debug!("create subnet with args: {:?}", arguments);
 
-        let address = CreateSubnet::create(global, arguments).await?;
+        if arguments.estimate_only {
+            print_fee_estimate(
+                &CreateSubnet::estimate(global, arguments).await?,
+                &global.amount_format(),
+            );
+            return Ok(());
+        }
+
+        if arguments.detach {
+            let tx_hash = hex::encode(CreateSubnet::submit(global, arguments).await?);
+            println!("subnet creation sent in transaction 0x{tx_hash}");
+            println!(
+                "follow it with: ipc-cli subnet creation-status --parent {} 0x{tx_hash} --wait",
+                arguments.parent
+            );
+            return Ok(());
+        }
+
+        let created = CreateSubnet::create(global, arguments).await?;
 
         log::info!(
             "created subnet actor with id: {}/{}",
             arguments.parent,
-            address
+            created.address
         );
 
+        // the subnet exists at this point, a receipt that cannot be written is only reported
+        if let Err(e) = write_receipt(global, arguments, &created) {
+            log::warn!("cannot write the receipt of the subnet: {e}");
+        }
+
         Ok(())
     }
 }
@@ -185,4 +308,15 @@ pub struct CreateSubnetArgs {
         help = "The address of collateral source of a subnet on its parent subnet. None if kind is native"
     )]
     pub collateral_source_address: Option<String>,
+    #[arg(
+        long,
+        help = "Print the expected gas and fees of creating the subnet instead of sending the transaction"
+    )]
+    pub estimate_only: bool,
+    #[arg(
+        long,
+        conflicts_with = "estimate_only",
+        help = "Return the transaction hash once sent instead of waiting for the subnet to be created; no receipt is written"
+    )]
+    pub detach: bool,
 }
@@ -0,0 +1,107 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Subnet creation status cli command
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::manager::SubnetCreation;
+use std::fmt::Debug;
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::commands::shutdown_signal;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// The command to follow a subnet creation sent with `subnet create --detach`.
+pub(crate) struct SubnetCreationStatus;
+
+#[async_trait]
+impl CommandLineHandler for SubnetCreationStatus {
+    type Arguments = SubnetCreationStatusArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("get subnet creation status with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let parent = SubnetID::from_str(&arguments.parent)?;
+        let tx_hash: [u8; 32] = hex::decode(arguments.tx.trim_start_matches("0x"))?
+            .try_into()
+            .map_err(|_| anyhow!("transaction hash must be 32 bytes"))?;
+
+        let started = Instant::now();
+        let interval = Duration::from_secs(arguments.interval);
+        // the spinner is only drawn for a person watching, not in logs or pipes
+        let spinner = arguments.wait && std::io::stderr().is_terminal();
+        let shutdown = shutdown_signal()?;
+        tokio::pin!(shutdown);
+
+        for frame in SPINNER.iter().cycle() {
+            let status = provider.create_subnet_status(&parent, &tx_hash).await?;
+            if spinner && status != SubnetCreation::Pending {
+                eprint!("\r\x1b[K");
+            }
+
+            match status {
+                SubnetCreation::Created(address) => {
+                    println!("created: {}", SubnetID::new_from_parent(&parent, address));
+                    return Ok(());
+                }
+                SubnetCreation::Failed(reason) => {
+                    return Err(anyhow!("subnet creation failed: {reason}"));
+                }
+                SubnetCreation::Pending if !arguments.wait => {
+                    println!("pending");
+                    return Ok(());
+                }
+                SubnetCreation::Pending => {
+                    if spinner {
+                        eprint!(
+                            "\r{frame} waiting for the subnet creation ({}s)",
+                            started.elapsed().as_secs()
+                        );
+                        std::io::stderr().flush()?;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => {
+                    if spinner {
+                        eprintln!();
+                    }
+                    return Err(anyhow!("interrupted while the subnet creation is pending"));
+                }
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+        unreachable!("the spinner cycles forever")
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "creation-status",
+    about = "Show whether a subnet creation sent with `subnet create --detach` is pending, created or failed"
+)]
+pub(crate) struct SubnetCreationStatusArgs {
+    #[arg(long, help = "The parent subnet the subnet is created in")]
+    pub parent: String,
+    #[arg(help = "The hash of the creation transaction, as printed by `subnet create --detach`")]
+    pub tx: String,
+    #[arg(
+        long,
+        help = "Keep polling until the subnet is created or the creation fails"
+    )]
+    pub wait: bool,
+    #[arg(
+        long,
+        default_value = "5",
+        help = "The number of seconds between polls with --wait"
+    )]
+    pub interval: u64,
+}
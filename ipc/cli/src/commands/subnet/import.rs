@@ -0,0 +1,122 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Import subnet cli command handler.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use fs_err as fs;
+use fvm_shared::address::Address;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::config::serialize::subnet_to_toml;
+use ipc_provider::config::subnet::{EVMSubnet, SubnetConfig};
+use ipc_provider::config::Subnet;
+use ipc_types::EthAddress;
+use std::fmt::Debug;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The address of the gateway deployed by fendermint in the genesis of a child subnet.
+const CHILD_GATEWAY_ADDR: &str = "0x77aa40b105843728088c0132e43fc44348881da8";
+/// The address of the registry deployed by fendermint in the genesis of a child subnet.
+const CHILD_REGISTRY_ADDR: &str = "0x74539671a1d2f1c8f200826baba665179f53a1b7";
+
+/// The command to add an existing subnet to the config.
+pub(crate) struct ImportSubnet;
+
+#[async_trait]
+impl CommandLineHandler for ImportSubnet {
+    type Arguments = ImportSubnetArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("import subnet with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        if provider.config().subnets.contains_key(&subnet) {
+            return Err(anyhow!("subnet {subnet} is already in the config"));
+        }
+
+        let parent = subnet
+            .parent()
+            .ok_or_else(|| anyhow!("{subnet} is a root, only child subnets can be imported"))?;
+        let children = provider.list_child_subnets(None, &parent).await?;
+        if !children.contains_key(&subnet) {
+            return Err(anyhow!("subnet {subnet} is not registered in {parent}"));
+        }
+
+        let provider_http = arguments
+            .provider_http
+            .replace("{chain_id}", &subnet.chain_id().to_string())
+            .parse()?;
+        let imported = Subnet {
+            id: subnet.clone(),
+            config: SubnetConfig::Fevm(EVMSubnet {
+                provider_http,
+                provider_timeout: None,
+                auth_token: None,
+                registry_addr: eth_address(&arguments.registry_address)?,
+                gateway_addr: eth_address(&arguments.gateway_address)?,
+                fee_policy: None,
+                keystore_path: None,
+                proxy: None,
+                tls: None,
+                auth: None,
+                pool: None,
+            }),
+        };
+        let stanza = subnet_to_toml(&imported)?;
+
+        if arguments.dry_run {
+            print!("{stanza}");
+            return Ok(());
+        }
+
+        // appended, so the comments and layout of the rest of the config are kept
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(global.config_path())?;
+        write!(file, "\n{stanza}")?;
+        println!("imported {subnet} into {}", global.config_path());
+
+        Ok(())
+    }
+}
+
+fn eth_address(s: &str) -> anyhow::Result<Address> {
+    Ok(EthAddress::from_str(s)?.into())
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "import",
+    about = "Add an existing child subnet to the config, after checking it is registered in its parent"
+)]
+pub(crate) struct ImportSubnetArgs {
+    #[arg(long, help = "The id of the subnet to import")]
+    pub subnet: String,
+    #[arg(
+        long,
+        help = "The RPC endpoint of the subnet, {chain_id} is replaced with the chain id of the subnet"
+    )]
+    pub provider_http: String,
+    #[arg(
+        long,
+        default_value = CHILD_GATEWAY_ADDR,
+        help = "The gateway address in the subnet, the one deployed in genesis by default"
+    )]
+    pub gateway_address: String,
+    #[arg(
+        long,
+        default_value = CHILD_REGISTRY_ADDR,
+        help = "The registry address in the subnet, the one deployed in genesis by default"
+    )]
+    pub registry_address: String,
+    #[arg(
+        long,
+        help = "Print the config stanza instead of adding it to the config"
+    )]
+    pub dry_run: bool,
+}
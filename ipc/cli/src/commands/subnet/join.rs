@@ -8,9 +8,10 @@ use ipc_api::subnet_id::SubnetID;
 use num_traits::Zero;
 use std::{fmt::Debug, str::FromStr};
 
+use crate::commands::subnet::receipt::SubnetReceipt;
 use crate::{
-    f64_to_token_amount, get_ipc_provider, require_fil_addr_from_str, CommandLineHandler,
-    GlobalArguments,
+    f64_to_token_amount, get_ipc_provider, print_fee_estimate, require_fil_addr_from_str,
+    CommandLineHandler, GlobalArguments,
 };
 
 /// The command to join a subnet
@@ -29,6 +30,27 @@ impl CommandLineHandler for JoinSubnet {
             Some(address) => Some(require_fil_addr_from_str(address)?),
             None => None,
         };
+        let collateral = match arguments.collateral {
+            Some(collateral) => collateral,
+            None => {
+                let receipt =
+                    SubnetReceipt::load(&global.config_path(), &subnet)?.ok_or_else(|| {
+                        anyhow::anyhow!("--collateral is required, no receipt found for {subnet}")
+                    })?;
+                log::info!(
+                    "staking the minimum collateral of the subnet: {}",
+                    receipt.min_validator_stake
+                );
+                receipt.min_validator_stake
+            }
+        };
+        if arguments.estimate_only {
+            let estimate = provider
+                .estimate_join_subnet(subnet, from, f64_to_token_amount(collateral)?)
+                .await?;
+            print_fee_estimate(&estimate, &global.amount_format());
+            return Ok(());
+        }
         if let Some(initial_balance) = arguments.initial_balance.filter(|x| !x.is_zero()) {
             log::info!("pre-funding address with {initial_balance}");
             provider
@@ -36,7 +58,7 @@ impl CommandLineHandler for JoinSubnet {
                 .await?;
         }
         let epoch = provider
-            .join_subnet(subnet, from, f64_to_token_amount(arguments.collateral)?)
+            .join_subnet(subnet, from, f64_to_token_amount(collateral)?)
             .await?;
         println!("joined at epoch: {epoch}");
 
@@ -53,14 +75,19 @@ pub struct JoinSubnetArgs {
     pub subnet: String,
     #[arg(
         long,
-        help = "The collateral to stake in the subnet (in whole FIL units)"
+        help = "The collateral to stake in the subnet (in whole FIL units), the minimum stake in the subnet's receipt by default"
     )]
-    pub collateral: f64,
+    pub collateral: Option<f64>,
     #[arg(
         long,
         help = "Optionally add an initial balance to the validator in genesis in the subnet"
     )]
     pub initial_balance: Option<f64>,
+    #[arg(
+        long,
+        help = "Print the expected gas and fees of joining instead of sending the transaction"
+    )]
+    pub estimate_only: bool,
 }
 
 /// The command to stake in a subnet from validator
@@ -79,9 +106,13 @@ impl CommandLineHandler for StakeSubnet {
             Some(address) => Some(require_fil_addr_from_str(address)?),
             None => None,
         };
-        provider
-            .stake(subnet, from, f64_to_token_amount(arguments.collateral)?)
-            .await
+        let collateral = f64_to_token_amount(arguments.collateral)?;
+        if arguments.estimate_only {
+            let estimate = provider.estimate_stake(subnet, from, collateral).await?;
+            print_fee_estimate(&estimate, &global.amount_format());
+            return Ok(());
+        }
+        provider.stake(subnet, from, collateral).await
     }
 }
 
@@ -97,6 +128,11 @@ pub struct StakeSubnetArgs {
         help = "The collateral to stake in the subnet (in whole FIL units)"
     )]
     pub collateral: f64,
+    #[arg(
+        long,
+        help = "Print the expected gas and fees of staking instead of sending the transaction"
+    )]
+    pub estimate_only: bool,
 }
 
 /// The command to unstake in a subnet from validator
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: MIT
 //! Kill a subnet cli command handler.
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::Args;
 use ipc_api::subnet_id::SubnetID;
+use std::collections::BTreeMap;
 use std::{fmt::Debug, str::FromStr};
 
+use crate::commands::approval::{check_approval, ApprovalArgs};
 use crate::{get_ipc_provider, require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
 
 /// The command to kill an existing subnet.
@@ -26,7 +29,25 @@ impl CommandLineHandler for KillSubnet {
             None => None,
         };
 
-        provider.kill_subnet(subnet, from).await
+        // the approvals are bound to the key that signs the kill, on the parent
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let from = provider.resolve_sender(&parent, from)?;
+
+        let params = BTreeMap::from([
+            ("subnet".to_string(), subnet.to_string()),
+            ("from".to_string(), from.to_string()),
+        ]);
+        if !check_approval(
+            global,
+            provider.config().approvals.as_ref(),
+            &arguments.approval,
+            "subnet-kill",
+            params,
+        )? {
+            return Ok(());
+        }
+
+        provider.kill_subnet(subnet, Some(from)).await
     }
 }
 
@@ -37,4 +58,6 @@ pub struct KillSubnetArgs {
     pub from: Option<String>,
     #[arg(long, help = "The subnet to kill")]
     pub subnet: String,
+    #[command(flatten)]
+    pub approval: ApprovalArgs,
 }
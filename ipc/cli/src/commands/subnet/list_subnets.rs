@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: MIT
 //! List subnets cli command
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::Args;
+use futures_util::future::{join_all, BoxFuture, FutureExt};
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::manager::SubnetInfo;
+use ipc_provider::parallel::DEFAULT_CONCURRENCY;
+use ipc_provider::IpcProvider;
 use std::fmt::Debug;
 use std::str::FromStr;
+use tokio::sync::Semaphore;
 
+use crate::amount::AmountFormat;
 use crate::{get_ipc_provider, require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
 
 /// The command to create a new subnet actor.
@@ -21,7 +28,16 @@ impl CommandLineHandler for ListSubnets {
         log::debug!("list subnets with args: {:?}", arguments);
 
         let provider = get_ipc_provider(global)?;
-        let subnet = SubnetID::from_str(&arguments.parent)?;
+
+        if arguments.tree {
+            return print_tree(&provider, arguments, global.amount_format()).await;
+        }
+
+        let parent = arguments
+            .parent
+            .as_deref()
+            .ok_or_else(|| anyhow!("--parent is required unless --tree is set"))?;
+        let subnet = SubnetID::from_str(parent)?;
 
         let gateway_addr = match &arguments.gateway_address {
             Some(address) => Some(require_fil_addr_from_str(address)?),
@@ -30,17 +46,140 @@ impl CommandLineHandler for ListSubnets {
 
         let ls = provider.list_child_subnets(gateway_addr, &subnet).await?;
 
+        let amounts = global.amount_format();
         for (_, s) in ls.iter() {
-            println!(
-                "{} - collateral: {} FIL, circ.supply: {} FIL, genesis: {}",
-                s.id, s.stake, s.circ_supply, s.genesis_epoch
-            );
+            println!("{} - {}", s.id, format_info(s, &amounts));
         }
 
         Ok(())
     }
 }
 
+/// A subnet and the children registered in its gateway.
+struct SubnetTree {
+    id: SubnetID,
+    /// The entry of the subnet in its parent's gateway, `None` for the roots of the walk.
+    info: Option<SubnetInfo>,
+    /// Why the children could not be listed, if they could not.
+    error: Option<String>,
+    children: Vec<SubnetTree>,
+}
+
+/// Prints the hierarchy below `--parent`, or below every configured subnet whose parent is not
+/// configured itself.
+async fn print_tree(
+    provider: &IpcProvider,
+    arguments: &ListSubnetsArgs,
+    amounts: AmountFormat,
+) -> anyhow::Result<()> {
+    let configured = provider.list_connections();
+
+    let mut roots = match &arguments.parent {
+        Some(parent) => vec![SubnetID::from_str(parent)?],
+        None => configured
+            .keys()
+            .filter(|id| id.parent().map_or(true, |p| !configured.contains_key(&p)))
+            .cloned()
+            .collect(),
+    };
+    roots.sort_by_key(|id| id.to_string());
+
+    // one limit for the whole walk, however deep the hierarchy is
+    let limit = Semaphore::new(DEFAULT_CONCURRENCY);
+    let trees = join_all(
+        roots
+            .into_iter()
+            .map(|root| walk(provider, &limit, root, None)),
+    )
+    .await;
+    for tree in trees {
+        print_node(&tree, "", "", &amounts);
+    }
+
+    Ok(())
+}
+
+/// Queries the children of `id` recursively, the siblings at once, with at most as many queries
+/// in flight as `limit` has permits. Subnets that are not in the config cannot be connected to,
+/// so the walk stops at them.
+fn walk<'a>(
+    provider: &'a IpcProvider,
+    limit: &'a Semaphore,
+    id: SubnetID,
+    info: Option<SubnetInfo>,
+) -> BoxFuture<'a, SubnetTree> {
+    async move {
+        let mut node = SubnetTree {
+            id,
+            info,
+            error: None,
+            children: vec![],
+        };
+
+        if provider.connection(&node.id).is_none() {
+            return node;
+        }
+
+        // the permit is only held for the query, not while the children are walked
+        let listed = {
+            let _permit = limit.acquire().await.expect("semaphore is never closed");
+            provider.list_child_subnets(None, &node.id).await
+        };
+        match listed {
+            Ok(children) => {
+                let mut children = children.into_values().collect::<Vec<_>>();
+                children.sort_by_key(|c| c.id.to_string());
+                node.children = join_all(
+                    children
+                        .into_iter()
+                        .map(|child| walk(provider, limit, child.id.clone(), Some(child))),
+                )
+                .await;
+            }
+            Err(e) => node.error = Some(e.to_string()),
+        }
+
+        node
+    }
+    .boxed()
+}
+
+fn format_info(s: &SubnetInfo, amounts: &AmountFormat) -> String {
+    format!(
+        "collateral: {}, circ.supply: {}, genesis: {}",
+        amounts.format(&s.stake),
+        amounts.format(&s.circ_supply),
+        s.genesis_epoch
+    )
+}
+
+fn print_node(node: &SubnetTree, prefix: &str, child_prefix: &str, amounts: &AmountFormat) {
+    let status = match (&node.info, &node.error) {
+        (_, Some(e)) => format!("error listing children: {e}"),
+        (Some(s), None) => format_info(s, amounts),
+        (None, None) => "root".to_string(),
+    };
+    println!("{prefix}{} - {status}", node.id);
+
+    for (i, child) in node.children.iter().enumerate() {
+        if i + 1 == node.children.len() {
+            print_node(
+                child,
+                &format!("{child_prefix}└── "),
+                &format!("{child_prefix}    "),
+                amounts,
+            );
+        } else {
+            print_node(
+                child,
+                &format!("{child_prefix}├── "),
+                &format!("{child_prefix}│   "),
+                amounts,
+            );
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 #[command(
     name = "list",
@@ -49,6 +188,16 @@ impl CommandLineHandler for ListSubnets {
 pub(crate) struct ListSubnetsArgs {
     #[arg(long, help = "The gateway address to query subnets")]
     pub gateway_address: Option<String>,
-    #[arg(long, help = "The network id to query child subnets")]
-    pub parent: String,
+    #[arg(
+        long,
+        required_unless_present = "tree",
+        help = "The network id to query child subnets"
+    )]
+    pub parent: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "gateway_address",
+        help = "Walk the child subnets recursively and print the hierarchy, starting from --parent or all configured root subnets"
+    )]
+    pub tree: bool,
 }
@@ -0,0 +1,124 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Validator liveness cli command
+
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_api::subnet_id::SubnetID;
+use ipc_observability::emit;
+use ipc_provider::liveness::ValidatorLiveness;
+use ipc_provider::observe::{register_metrics, ValidatorLivenessChecked};
+
+use crate::commands::get_ipc_provider;
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// The command to check which validators keep signing the checkpoints of a subnet.
+pub(crate) struct SubnetLiveness;
+
+#[async_trait]
+impl CommandLineHandler for SubnetLiveness {
+    type Arguments = SubnetLivenessArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("subnet liveness with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+
+        let Some(addr) = &arguments.metrics_address else {
+            let liveness = provider
+                .validator_liveness(&subnet, arguments.checkpoints)
+                .await?;
+            print_liveness(&liveness, arguments.max_missed);
+            return Ok(());
+        };
+
+        let addr = SocketAddr::from_str(addr)?;
+        let registry = prometheus::Registry::new();
+        register_metrics(&registry)?;
+        let mut builder = prometheus_exporter::Builder::new(addr);
+        builder.with_registry(registry);
+        let _ = builder.start().context("failed to start metrics server")?;
+        log::info!("serving metrics on: {addr}");
+
+        loop {
+            match provider
+                .validator_liveness(&subnet, arguments.checkpoints)
+                .await
+            {
+                Ok(liveness) => {
+                    for l in liveness.iter() {
+                        emit(ValidatorLivenessChecked {
+                            validator: l.validator.to_string(),
+                            missed_in_a_row: l.missed_in_a_row as i64,
+                        });
+                    }
+                    print_liveness(&liveness, arguments.max_missed);
+                }
+                Err(e) => log::error!("cannot check the liveness of {subnet}: {e}"),
+            }
+            tokio::time::sleep(Duration::from_secs(arguments.interval_secs)).await;
+        }
+    }
+}
+
+fn print_liveness(liveness: &[ValidatorLiveness], max_missed: usize) {
+    for l in liveness {
+        let last_signed = l
+            .last_signed
+            .map(|h| h.to_string())
+            .unwrap_or_else(|| "never".to_string());
+        println!(
+            "{} signed {}/{} last signed at {} missed in a row {}{}",
+            l.validator,
+            l.signed,
+            l.checkpoints,
+            last_signed,
+            l.missed_in_a_row,
+            if l.is_lagging(max_missed) {
+                " LAGGING"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "liveness",
+    about = "Show which validators signed the latest bottom-up checkpoints of a subnet"
+)]
+pub(crate) struct SubnetLivenessArgs {
+    #[arg(long, help = "The subnet to check the validators of")]
+    pub subnet: String,
+    #[arg(
+        long,
+        default_value = "10",
+        help = "The number of latest committed checkpoints to look at"
+    )]
+    pub checkpoints: usize,
+    #[arg(
+        long,
+        default_value = "3",
+        help = "Flag validators that missed more than this many checkpoints in a row"
+    )]
+    pub max_missed: usize,
+    #[arg(
+        long,
+        help = "Keep checking and serve the missed checkpoints of each validator as Prometheus metrics on this address"
+    )]
+    pub metrics_address: Option<String>,
+    #[arg(
+        long,
+        default_value = "60",
+        help = "Seconds between checks when serving metrics"
+    )]
+    pub interval_secs: u64,
+}
@@ -2,16 +2,22 @@
 // SPDX-License-Identifier: MIT
 
 use self::bootstrap::{AddBootstrap, AddBootstrapArgs, ListBootstraps, ListBootstrapsArgs};
+use self::bootstrap_status::{SubnetBootstrapStatus, SubnetBootstrapStatusArgs};
 use self::join::{StakeSubnet, StakeSubnetArgs, UnstakeSubnet, UnstakeSubnetArgs};
 use self::leave::{Claim, ClaimArgs};
 use self::rpc::{ChainIdSubnet, ChainIdSubnetArgs};
 pub use crate::commands::subnet::create::{CreateSubnet, CreateSubnetArgs};
+use crate::commands::subnet::creation_status::{SubnetCreationStatus, SubnetCreationStatusArgs};
 use crate::commands::subnet::genesis_epoch::{GenesisEpoch, GenesisEpochArgs};
+use crate::commands::subnet::import::{ImportSubnet, ImportSubnetArgs};
 pub use crate::commands::subnet::join::{JoinSubnet, JoinSubnetArgs};
 pub use crate::commands::subnet::kill::{KillSubnet, KillSubnetArgs};
 pub use crate::commands::subnet::leave::{LeaveSubnet, LeaveSubnetArgs};
 use crate::commands::subnet::list_subnets::{ListSubnets, ListSubnetsArgs};
 use crate::commands::subnet::list_validators::{ListValidators, ListValidatorsArgs};
+use crate::commands::subnet::liveness::{SubnetLiveness, SubnetLivenessArgs};
+use crate::commands::subnet::peg_report::{PegReport, PegReportArgs};
+use crate::commands::subnet::receipt::{ShowReceipt, ShowReceiptArgs};
 use crate::commands::subnet::rpc::{RPCSubnet, RPCSubnetArgs};
 use crate::commands::subnet::send_value::{SendValue, SendValueArgs};
 use crate::commands::subnet::set_federated_power::{SetFederatedPower, SetFederatedPowerArgs};
@@ -19,22 +25,30 @@ use crate::commands::subnet::show_gateway_contract_commit_sha::{
     ShowGatewayContractCommitSha, ShowGatewayContractCommitShaArgs,
 };
 use crate::commands::subnet::validator::{ValidatorInfo, ValidatorInfoArgs};
+use crate::commands::subnet::validators_diff::{ValidatorsDiff, ValidatorsDiffArgs};
 use crate::{CommandLineHandler, GlobalArguments};
 use clap::{Args, Subcommand};
 
 pub mod bootstrap;
+mod bootstrap_status;
 pub mod create;
+mod creation_status;
 mod genesis_epoch;
+mod import;
 pub mod join;
 pub mod kill;
 pub mod leave;
 pub mod list_subnets;
 pub mod list_validators;
+mod liveness;
+mod peg_report;
+mod receipt;
 pub mod rpc;
 pub mod send_value;
 mod set_federated_power;
 pub mod show_gateway_contract_commit_sha;
 mod validator;
+mod validators_diff;
 
 pub(crate) const ZERO_ADDRESS: &str = "0000000000000000000000000000000000000000";
 
@@ -66,12 +80,19 @@ impl SubnetCommandsArgs {
             Commands::Claim(args) => Claim::handle(global, args).await,
             Commands::AddBootstrap(args) => AddBootstrap::handle(global, args).await,
             Commands::ListBootstraps(args) => ListBootstraps::handle(global, args).await,
+            Commands::BootstrapStatus(args) => SubnetBootstrapStatus::handle(global, args).await,
             Commands::GenesisEpoch(args) => GenesisEpoch::handle(global, args).await,
             Commands::GetValidator(args) => ValidatorInfo::handle(global, args).await,
             Commands::ShowGatewayContractCommitSha(args) => {
                 ShowGatewayContractCommitSha::handle(global, args).await
             }
             Commands::SetFederatedPower(args) => SetFederatedPower::handle(global, args).await,
+            Commands::Import(args) => ImportSubnet::handle(global, args).await,
+            Commands::PegReport(args) => PegReport::handle(global, args).await,
+            Commands::Receipt(args) => ShowReceipt::handle(global, args).await,
+            Commands::Liveness(args) => SubnetLiveness::handle(global, args).await,
+            Commands::CreationStatus(args) => SubnetCreationStatus::handle(global, args).await,
+            Commands::ValidatorsDiff(args) => ValidatorsDiff::handle(global, args).await,
         }
     }
 }
@@ -92,8 +113,15 @@ pub(crate) enum Commands {
     Claim(ClaimArgs),
     AddBootstrap(AddBootstrapArgs),
     ListBootstraps(ListBootstrapsArgs),
+    BootstrapStatus(SubnetBootstrapStatusArgs),
     GenesisEpoch(GenesisEpochArgs),
     GetValidator(ValidatorInfoArgs),
     ShowGatewayContractCommitSha(ShowGatewayContractCommitShaArgs),
     SetFederatedPower(SetFederatedPowerArgs),
+    Import(ImportSubnetArgs),
+    PegReport(PegReportArgs),
+    Receipt(ShowReceiptArgs),
+    Liveness(SubnetLivenessArgs),
+    CreationStatus(SubnetCreationStatusArgs),
+    ValidatorsDiff(ValidatorsDiffArgs),
 }
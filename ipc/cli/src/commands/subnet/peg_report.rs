@@ -0,0 +1,200 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Report of the value moved in and out of a subnet
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use clap::Args;
+use fs_err as fs;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::cross::IpcEnvelope;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::manager::TxRef;
+use serde::Serialize;
+
+use crate::commands::get_ipc_provider;
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// One movement of value across the boundary of the subnet, with the transactions that moved
+/// it on either side.
+#[derive(Debug, Serialize)]
+struct PegEntry {
+    /// `deposit` for top-down messages, `withdrawal` for bottom-up ones.
+    direction: &'static str,
+    from: String,
+    to: String,
+    /// The value in atto.
+    amount: String,
+    nonce: u64,
+    /// The parent transaction that sent a deposit or committed the checkpoint of a withdrawal.
+    parent_tx: PegTx,
+    /// The subnet transaction that sent a withdrawal. Deposits are executed by the subnet when
+    /// it commits the parent finality, without a transaction of their own.
+    subnet_tx: Option<PegTx>,
+    /// The subnet height of the checkpoint of a withdrawal.
+    checkpoint_height: Option<ChainEpoch>,
+}
+
+#[derive(Debug, Serialize)]
+struct PegTx {
+    height: ChainEpoch,
+    hash: String,
+    /// The timestamp of the block, in seconds since the unix epoch.
+    timestamp: u64,
+}
+
+impl From<&TxRef> for PegTx {
+    fn from(tx: &TxRef) -> Self {
+        Self {
+            height: tx.height,
+            hash: format!("0x{}", hex::encode(tx.tx_hash)),
+            timestamp: tx.timestamp,
+        }
+    }
+}
+
+impl PegEntry {
+    fn new(direction: &'static str, msg: &IpcEnvelope, parent_tx: &TxRef) -> Self {
+        Self {
+            direction,
+            from: format!("{}", msg.from),
+            to: format!("{}", msg.to),
+            amount: msg.value.atto().to_string(),
+            nonce: msg.local_nonce,
+            parent_tx: parent_tx.into(),
+            subnet_tx: None,
+            checkpoint_height: None,
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let tx = |tx: Option<&PegTx>| match tx {
+            Some(tx) => format!("{},{},{}", tx.height, tx.hash, tx.timestamp),
+            None => String::from(",,"),
+        };
+        format!(
+            "{},{},{},{},{},{},{},{}\n",
+            self.direction,
+            self.from,
+            self.to,
+            self.amount,
+            self.nonce,
+            tx(Some(&self.parent_tx)),
+            tx(self.subnet_tx.as_ref()),
+            self.checkpoint_height
+                .map(|h| h.to_string())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+const CSV_HEADER: &str = "direction,from,to,amount,nonce,parent_height,parent_tx,parent_timestamp,subnet_height,subnet_tx,subnet_timestamp,checkpoint_height\n";
+
+/// The command to report the deposits into and withdrawals from a subnet.
+pub(crate) struct PegReport;
+
+#[async_trait]
+impl CommandLineHandler for PegReport {
+    type Arguments = PegReportArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("peg report with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let (from, to) = (arguments.from_height, arguments.to_height);
+        let mut entries = vec![];
+
+        for (msg, tx) in provider.top_down_msg_txs(&subnet, from, to).await? {
+            if !msg.value.is_zero() {
+                entries.push(PegEntry::new("deposit", &msg, &tx));
+            }
+        }
+
+        // withdrawals are those of the checkpoints committed to the parent in the range, so both
+        // directions are reported over the same parent heights
+        let commits = provider.checkpoint_commit_txs(&subnet, from, to).await?;
+        if let (Some(first), Some(last)) = (
+            commits.iter().map(|(h, _)| *h).min(),
+            commits.iter().map(|(h, _)| *h).max(),
+        ) {
+            let period = provider.checkpoint_period(&subnet).await?;
+            if period <= 0 {
+                return Err(anyhow::anyhow!("invalid checkpoint period {period}"));
+            }
+            // messages are queued in the period before the checkpoint that carries them
+            let subnet_txs = provider
+                .bottom_up_msg_txs(&subnet, (first - period).max(0), last)
+                .await?;
+
+            for (height, parent_tx) in commits.iter() {
+                let Some(bundle) = provider.get_bottom_up_bundle(&subnet, *height).await? else {
+                    return Err(anyhow::anyhow!(
+                        "the checkpoint at height {height} is committed but not found in {subnet}"
+                    ));
+                };
+                for msg in bundle.checkpoint.msgs.iter() {
+                    if msg.value.is_zero() {
+                        continue;
+                    }
+                    let mut entry = PegEntry::new("withdrawal", msg, parent_tx);
+                    entry.subnet_tx = subnet_txs.get(&msg.tracing_id()?).map(PegTx::from);
+                    entry.checkpoint_height = Some(*height);
+                    entries.push(entry);
+                }
+            }
+        }
+
+        let report = match arguments.format.as_str() {
+            "json" => serde_json::to_string_pretty(&entries)?,
+            _ => {
+                let mut csv = String::from(CSV_HEADER);
+                for e in entries.iter() {
+                    csv.push_str(&e.csv_row());
+                }
+                csv
+            }
+        };
+
+        match &arguments.output {
+            Some(path) => {
+                fs::write(path, report)?;
+                println!("wrote {} entries to {path}", entries.len());
+            }
+            None => print!("{report}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "peg-report",
+    about = "Report every deposit into and withdrawal from a subnet in a range of parent heights"
+)]
+pub(crate) struct PegReportArgs {
+    #[arg(long, help = "The subnet to report on")]
+    pub subnet: String,
+    #[arg(
+        long,
+        help = "Include deposits sent and withdrawals committed from this height of the parent"
+    )]
+    pub from_height: ChainEpoch,
+    #[arg(
+        long,
+        help = "Include deposits sent and withdrawals committed up to this height of the parent"
+    )]
+    pub to_height: ChainEpoch,
+    #[arg(
+        long,
+        default_value = "csv",
+        value_parser = ["csv", "json"],
+        help = "The format of the report, with amounts in atto"
+    )]
+    pub format: String,
+    #[arg(long, help = "Write the report to this file instead of stdout")]
+    pub output: Option<String>,
+}
@@ -0,0 +1,117 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Receipts of the subnets created from this machine, and the cli handler to show them.
+
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use fs_err as fs;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::subnet_id::SubnetID;
+use serde::{Deserialize, Serialize};
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// The directory next to the config where receipts are kept.
+const RECEIPTS_DIR: &str = "receipts";
+
+/// What was used to create a subnet, kept so later commands can default to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SubnetReceipt {
+    pub subnet_id: String,
+    pub parent: String,
+    /// The address that created the subnet.
+    pub creator: Option<String>,
+    /// The transaction that created the subnet, not kept by older receipts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// The epoch of the parent that included the creation.
+    pub parent_epoch: ChainEpoch,
+    /// Seconds since the unix epoch when the subnet was created.
+    pub created_at: u64,
+    pub min_validator_stake: f64,
+    pub min_validators: u64,
+    pub bottomup_check_period: ChainEpoch,
+    pub active_validators_limit: u16,
+    pub min_cross_msg_fee: f64,
+    pub permission_mode: String,
+    pub supply_source_kind: String,
+    pub supply_source_address: Option<String>,
+    pub collateral_source_kind: Option<String>,
+    pub collateral_source_address: Option<String>,
+    pub validator_gater: Option<String>,
+    pub validator_rewarder: Option<String>,
+}
+
+impl SubnetReceipt {
+    pub fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+
+    /// Writes the receipt next to the config, replacing any previous one for the subnet.
+    pub fn save(&self, config_path: &str) -> anyhow::Result<PathBuf> {
+        let subnet = SubnetID::from_str(&self.subnet_id)?;
+        let path = receipt_path(config_path, &subnet);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// The receipt of `subnet`, if it was created from this machine.
+    pub fn load(config_path: &str, subnet: &SubnetID) -> anyhow::Result<Option<Self>> {
+        let path = receipt_path(config_path, subnet);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+}
+
+fn receipt_path(config_path: &str, subnet: &SubnetID) -> PathBuf {
+    // the subnet id is made a single file name, e.g. r314159_t410f...
+    let name = subnet.to_string().trim_start_matches('/').replace('/', "_");
+    Path::new(config_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(RECEIPTS_DIR)
+        .join(format!("{name}.json"))
+}
+
+/// The command to show the receipt of a subnet created from this machine.
+pub(crate) struct ShowReceipt;
+
+#[async_trait]
+impl CommandLineHandler for ShowReceipt {
+    type Arguments = ShowReceiptArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("show subnet receipt with args: {:?}", arguments);
+
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let receipt = SubnetReceipt::load(&global.config_path(), &subnet)?
+            .ok_or_else(|| anyhow!("no receipt for {subnet}, it was not created from here"))?;
+        println!("{}", serde_json::to_string_pretty(&receipt)?);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "receipt",
+    about = "Show the parameters a subnet was created with from this machine"
+)]
+pub(crate) struct ShowReceiptArgs {
+    #[arg(help = "The subnet id")]
+    pub subnet: String,
+}
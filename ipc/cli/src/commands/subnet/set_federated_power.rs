@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: MIT
 //! Set federated power cli handler
 
+use crate::commands::approval::{check_approval, ApprovalArgs};
 use crate::commands::{get_ipc_provider, require_fil_addr_from_str};
 use crate::{CommandLineHandler, GlobalArguments};
 use async_trait::async_trait;
 use clap::Args;
 use fvm_shared::address::Address;
 use ipc_api::subnet_id::SubnetID;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 /// The command to set federated power.
@@ -37,6 +39,39 @@ impl CommandLineHandler for crate::commands::subnet::SetFederatedPower {
 
         let from_address = require_fil_addr_from_str(&arguments.from).unwrap();
 
+        let join = |items: Vec<String>| items.join(",");
+        let params = BTreeMap::from([
+            ("subnet".to_string(), subnet.to_string()),
+            ("from".to_string(), from_address.to_string()),
+            (
+                "validators".to_string(),
+                join(addresses.iter().map(|a| a.to_string()).collect()),
+            ),
+            (
+                "pubkeys".to_string(),
+                join(public_keys.iter().map(hex::encode).collect()),
+            ),
+            (
+                "power".to_string(),
+                join(
+                    arguments
+                        .validator_power
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect(),
+                ),
+            ),
+        ]);
+        if !check_approval(
+            global,
+            provider.config().approvals.as_ref(),
+            &arguments.approval,
+            "set-federated-power",
+            params,
+        )? {
+            return Ok(());
+        }
+
         let chain_epoch = provider
             .set_federated_power(
                 &from_address,
@@ -68,4 +103,6 @@ pub struct SetFederatedPowerArgs {
     pub validator_pubkeys: Vec<String>,
     #[arg(long, num_args = 1.., help = "Federated of validators, separated by space")]
     pub validator_power: Vec<u128>,
+    #[command(flatten)]
+    pub approval: ApprovalArgs,
 }
@@ -0,0 +1,92 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Validator set diff cli command
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::validator_diff::ValidatorStake;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::amount::AmountFormat;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The command to show how the validator set of a subnet changed between two parent heights.
+pub(crate) struct ValidatorsDiff;
+
+#[async_trait]
+impl CommandLineHandler for ValidatorsDiff {
+    type Arguments = ValidatorsDiffArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("diff validator set with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let to = match arguments.to {
+            Some(to) => to,
+            None => {
+                let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+                provider.chain_head(&parent).await?
+            }
+        };
+
+        let amounts = global.amount_format();
+        let diff = provider
+            .diff_validator_set(&subnet, arguments.from, to)
+            .await?;
+
+        println!(
+            "validator set of {subnet} from height {} to {}",
+            diff.from, diff.to
+        );
+        if diff.is_empty() {
+            println!("no changes");
+            return Ok(());
+        }
+        for stake in &diff.joined {
+            println!("joined:  {}", format_stake(stake, &amounts));
+        }
+        for stake in &diff.left {
+            println!("left:    {}", format_stake(stake, &amounts));
+        }
+        for change in &diff.changed {
+            println!(
+                "changed: {} -> {} (delta {})",
+                format_stake(&change.before, &amounts),
+                format_stake(&change.after, &amounts),
+                amounts.format(&change.delta())
+            );
+        }
+        Ok(())
+    }
+}
+
+fn format_stake(stake: &ValidatorStake, amounts: &AmountFormat) -> String {
+    let state = if stake.active { "active" } else { "waiting" };
+    format!(
+        "{} {} {state}",
+        stake.validator,
+        amounts.format(&stake.collateral)
+    )
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "validators-diff",
+    about = "Show the validators that joined, left or changed their stake between two heights of the parent"
+)]
+pub(crate) struct ValidatorsDiffArgs {
+    #[arg(long, help = "The subnet id to query")]
+    pub subnet: String,
+    #[arg(long, help = "The parent height to compare from")]
+    pub from: ChainEpoch,
+    #[arg(
+        long,
+        help = "The parent height to compare to, defaults to the parent's chain head"
+    )]
+    pub to: Option<ChainEpoch>,
+}
@@ -0,0 +1,120 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Address conversion util
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Args, Subcommand};
+use fvm_shared::address::{Address, Payload};
+use ipc_api::evm::payload_to_evm_address;
+use ipc_api::pubkey::PublicKeyAddresses;
+use ipc_types::EthAddress;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+#[derive(Debug, Args)]
+#[command(name = "addr", about = "Address utilities")]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct AddrUtilCommandsArgs {
+    #[command(subcommand)]
+    command: AddrUtilCommands,
+}
+
+impl AddrUtilCommandsArgs {
+    pub async fn handle(&self, global: &GlobalArguments) -> anyhow::Result<()> {
+        match &self.command {
+            AddrUtilCommands::Convert(args) => ConvertAddr::handle(global, args).await,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum AddrUtilCommands {
+    Convert(ConvertAddrArgs),
+}
+
+pub(crate) struct ConvertAddr;
+
+#[async_trait]
+impl CommandLineHandler for ConvertAddr {
+    type Arguments = ConvertAddrArgs;
+
+    async fn handle(_global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        for (form, value) in convert(&arguments.input)? {
+            println!("{form}: {value}");
+        }
+        Ok(())
+    }
+}
+
+/// Every form of the address or public key in `input` that can be derived from it.
+fn convert(input: &str) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let hex_input = input.strip_prefix("0x").unwrap_or(input);
+
+    // 20 bytes are an EVM address, 32, 33 and 65 bytes a public key
+    if let Ok(bytes) = hex::decode(hex_input) {
+        if bytes.len() == 20 {
+            let eth = EthAddress::from_str(input)?;
+            return Ok(vec![
+                ("eth", format!("0x{}", hex::encode(eth.0))),
+                ("f410", Address::from(eth).to_string()),
+            ]);
+        }
+        if matches!(bytes.len(), 32 | 33 | 65) {
+            let addresses = PublicKeyAddresses::from_public_key(&bytes)?;
+            return Ok(vec![
+                ("public key", hex::encode(addresses.public_key)),
+                ("eth", format!("{:?}", addresses.eth)),
+                ("f410", addresses.delegated.to_string()),
+                ("f1", addresses.secp256k1.to_string()),
+            ]);
+        }
+    }
+
+    let addr = Address::from_str(input)
+        .map_err(|e| anyhow!("{input} is not an address or a public key: {e}"))?;
+    let mut forms = vec![("address", addr.to_string())];
+    match addr.payload() {
+        Payload::Delegated(_) => forms.push((
+            "eth",
+            format!("{:?}", payload_to_evm_address(addr.payload())?),
+        )),
+        Payload::ID(id) => forms.push(("id", id.to_string())),
+        // f1, f2 and f3 addresses are hashes, nothing else can be derived from them
+        _ => {}
+    }
+    Ok(forms)
+}
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Convert between 0x, f410 and f1 addresses and secp256k1 public keys (uncompressed, compressed or x-only hex)"
+)]
+pub(crate) struct ConvertAddrArgs {
+    #[arg(help = "The address or hex encoded public key to convert")]
+    pub input: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert;
+
+    #[test]
+    fn test_convert() {
+        let forms =
+            convert("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        let eth = forms.iter().find(|(f, _)| *f == "eth").unwrap().1.clone();
+        assert_eq!(eth, "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+
+        let f410 = forms.iter().find(|(f, _)| *f == "f410").unwrap().1.clone();
+        let back = convert(&f410).unwrap();
+        assert!(back.contains(&("eth", eth.clone())));
+
+        let from_eth = convert(&eth).unwrap();
+        assert!(from_eth.contains(&("f410", f410)));
+
+        assert!(convert("not an address").is_err());
+    }
+}
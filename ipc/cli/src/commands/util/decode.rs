@@ -0,0 +1,123 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Decode util for the encoded ipc types
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use clap::Args;
+use ipc_api::checkpoint::{BottomUpCheckpoint, BottomUpCheckpointBundle};
+use ipc_api::cross::IpcEnvelope;
+use ipc_api::quorum::QuorumCertificate;
+use ipc_api::subnet_id::SubnetID;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+use crate::{CommandLineHandler, GlobalArguments};
+
+/// The kinds tried, in order, when no kind is given.
+const KINDS: [&str; 5] = [
+    "bundle",
+    "checkpoint",
+    "quorum-cert",
+    "envelope",
+    "subnet-id",
+];
+
+pub(crate) struct Decode;
+
+#[async_trait]
+impl CommandLineHandler for Decode {
+    type Arguments = DecodeArgs;
+
+    async fn handle(_global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        let bytes = decode_bytes(arguments.input.trim(), &arguments.encoding)?;
+
+        let (kind, decoded) = match &arguments.kind {
+            Some(kind) => (kind.as_str(), decode_as(kind, &bytes)?),
+            None => KINDS
+                .iter()
+                .find_map(|kind| decode_as(kind, &bytes).ok().map(|d| (*kind, d)))
+                .ok_or_else(|| anyhow!("input does not decode as any of {}", KINDS.join(", ")))?,
+        };
+        println!("{kind}:\n{decoded}");
+
+        Ok(())
+    }
+}
+
+/// Reads the blob in `input`, guessing hex or base64 when the encoding is `auto`.
+fn decode_bytes(input: &str, encoding: &str) -> anyhow::Result<Vec<u8>> {
+    let as_hex = |s: &str| hex::decode(s.strip_prefix("0x").unwrap_or(s));
+    match encoding {
+        "hex" => Ok(as_hex(input)?),
+        "base64" => Ok(BASE64_STANDARD.decode(input)?),
+        _ => as_hex(input)
+            .ok()
+            .or_else(|| BASE64_STANDARD.decode(input).ok())
+            .ok_or_else(|| anyhow!("input is neither hex nor base64")),
+    }
+}
+
+/// Decodes the CBOR in `bytes` as `kind` and renders it as pretty JSON.
+fn decode_as(kind: &str, bytes: &[u8]) -> anyhow::Result<String> {
+    match kind {
+        "bundle" => to_json::<BottomUpCheckpointBundle>(bytes),
+        "checkpoint" => to_json::<BottomUpCheckpoint>(bytes),
+        "quorum-cert" => to_json::<QuorumCertificate>(bytes),
+        "envelope" => to_json::<IpcEnvelope>(bytes),
+        // shown in its usual string form rather than as the serialized tuple
+        "subnet-id" => Ok(fvm_ipld_encoding::from_slice::<SubnetID>(bytes)?.to_string()),
+        _ => Err(anyhow!("unknown kind {kind}")),
+    }
+}
+
+fn to_json<T: DeserializeOwned + Serialize>(bytes: &[u8]) -> anyhow::Result<String> {
+    let value: T = fvm_ipld_encoding::from_slice(bytes)?;
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Decode and pretty print a CBOR encoded checkpoint, envelope, quorum certificate or subnet id"
+)]
+pub(crate) struct DecodeArgs {
+    #[arg(
+        long,
+        value_parser = KINDS,
+        help = "What the input holds, every kind is tried when not given"
+    )]
+    pub kind: Option<String>,
+    #[arg(
+        long,
+        default_value = "auto",
+        value_parser = ["auto", "hex", "base64"],
+        help = "How the input is encoded"
+    )]
+    pub encoding: String,
+    #[arg(help = "The encoded blob")]
+    pub input: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_as, decode_bytes};
+    use base64::{prelude::BASE64_STANDARD, Engine};
+    use fvm_shared::address::Address;
+    use ipc_api::subnet_id::SubnetID;
+
+    #[test]
+    fn test_decode_subnet_id() {
+        let subnet = SubnetID::new(314159, vec![Address::new_id(1001)]);
+        let bytes = fvm_ipld_encoding::to_vec(&subnet).unwrap();
+
+        let from_hex = decode_bytes(&format!("0x{}", hex::encode(&bytes)), "auto").unwrap();
+        assert_eq!(from_hex, bytes);
+        let from_base64 = decode_bytes(&BASE64_STANDARD.encode(&bytes), "base64").unwrap();
+        assert_eq!(from_base64, bytes);
+
+        assert_eq!(decode_as("subnet-id", &bytes).unwrap(), subnet.to_string());
+        assert!(decode_as("envelope", &bytes).is_err());
+    }
+}
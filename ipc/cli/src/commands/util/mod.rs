@@ -4,9 +4,13 @@ use crate::{CommandLineHandler, GlobalArguments};
 
 use clap::{Args, Subcommand};
 
+use self::convert::AddrUtilCommandsArgs;
+use self::decode::{Decode, DecodeArgs};
 use self::eth::{F4ToEthAddr, F4ToEthAddrArgs};
 use self::f4::{EthToF4Addr, EthToF4AddrArgs};
 
+mod convert;
+mod decode;
 mod eth;
 mod f4;
 
@@ -23,6 +27,8 @@ impl UtilCommandsArgs {
         match &self.command {
             Commands::EthToF4Addr(args) => EthToF4Addr::handle(global, args).await,
             Commands::F4ToEthAddr(args) => F4ToEthAddr::handle(global, args).await,
+            Commands::Addr(args) => args.handle(global).await,
+            Commands::Decode(args) => Decode::handle(global, args).await,
         }
     }
 }
@@ -31,4 +37,6 @@ impl UtilCommandsArgs {
 pub(crate) enum Commands {
     EthToF4Addr(EthToF4AddrArgs),
     F4ToEthAddr(F4ToEthAddrArgs),
+    Addr(AddrUtilCommandsArgs),
+    Decode(DecodeArgs),
 }
@@ -3,14 +3,21 @@
 
 mod batch_claim;
 mod list;
+mod onboarding_bundle;
+mod stake_history;
 
 use crate::commands::validator::batch_claim::{BatchClaim, BatchClaimArgs};
 use crate::commands::validator::list::{ListActivities, ListActivitiesArgs};
+use crate::commands::validator::onboarding_bundle::{OnboardingBundle, OnboardingBundleArgs};
+use crate::commands::validator::stake_history::{StakeHistory, StakeHistoryArgs};
 use crate::{CommandLineHandler, GlobalArguments};
 use clap::{Args, Subcommand};
 
 #[derive(Debug, Args)]
-#[command(name = "validator", about = "validator reward related commands")]
+#[command(
+    name = "validator",
+    about = "validator reward and staking related commands"
+)]
 #[command(args_conflicts_with_subcommands = true)]
 pub(crate) struct ValidatorCommandsArgs {
     #[command(subcommand)]
@@ -22,6 +29,8 @@ impl ValidatorCommandsArgs {
         match &self.command {
             Commands::BatchClaim(args) => BatchClaim::handle(global, args).await,
             Commands::ListValidatorActivities(args) => ListActivities::handle(global, args).await,
+            Commands::StakeHistory(args) => StakeHistory::handle(global, args).await,
+            Commands::OnboardingBundle(args) => OnboardingBundle::handle(global, args).await,
         }
     }
 }
@@ -30,4 +39,6 @@ impl ValidatorCommandsArgs {
 pub(crate) enum Commands {
     BatchClaim(BatchClaimArgs),
     ListValidatorActivities(ListActivitiesArgs),
+    StakeHistory(StakeHistoryArgs),
+    OnboardingBundle(OnboardingBundleArgs),
 }
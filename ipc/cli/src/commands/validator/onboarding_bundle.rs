@@ -0,0 +1,158 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Onboarding bundle cli command handler.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use fs_err as fs;
+use ipc_api::ethers_address_to_fil_address;
+use ipc_api::evm::payload_to_evm_address;
+use ipc_api::subnet_id::SubnetID;
+use ipc_provider::config::serialize::subnet_to_toml;
+use ipc_provider::config::subnet::SubnetConfig;
+use ipc_provider::config::Subnet;
+use ipc_provider::IpcProvider;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::commands::wallet::export::WalletPublicKey;
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The command to gather everything a new validator needs to join a subnet in one file.
+pub(crate) struct OnboardingBundle;
+
+#[async_trait]
+impl CommandLineHandler for OnboardingBundle {
+    type Arguments = OnboardingBundleArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("create onboarding bundle with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let parent = subnet
+            .parent()
+            .ok_or_else(|| anyhow!("{subnet} is a root, it has no validators to onboard"))?;
+
+        let genesis = provider.get_genesis_info(&subnet).await?;
+        let bootstrap_peers = provider.list_bootstrap_nodes(&subnet).await?;
+        let parent_subnet = provider
+            .config()
+            .subnet(&parent)
+            .ok_or_else(|| anyhow!("parent subnet {parent} not found in the config"))?;
+        let SubnetConfig::Fevm(parent_evm) = &parent_subnet.config;
+
+        let validators = genesis
+            .validators
+            .iter()
+            .map(|v| {
+                json!({
+                    "address": v.addr.to_string(),
+                    "weight": v.weight.atto().to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let genesis_balances = genesis
+            .genesis_balances
+            .iter()
+            .map(|(addr, amount)| (addr.to_string(), Value::from(amount.atto().to_string())))
+            .collect::<serde_json::Map<_, _>>();
+
+        let from = arguments.address.as_deref().unwrap_or("<EVM_ADDRESS>");
+
+        let bundle = json!({
+            "subnet": subnet.to_string(),
+            "parent": parent.to_string(),
+            "chain_id": subnet.chain_id(),
+            "genesis": {
+                "genesis_epoch": genesis.genesis_epoch,
+                "bottom_up_checkpoint_period": genesis.bottom_up_checkpoint_period,
+                "majority_percentage": genesis.majority_percentage,
+                "active_validators_limit": genesis.active_validators_limit,
+                "min_collateral": genesis.min_collateral.atto().to_string(),
+                "permission_mode": format!("{:?}", genesis.permission_mode).to_lowercase(),
+                "supply_source": genesis.supply_source,
+                "validators": validators,
+                "genesis_balances": genesis_balances,
+            },
+            "bootstrap_peers": bootstrap_peers,
+            "parent_config": parent_stanza(parent_subnet.clone())?,
+            "keys": keys(&provider, arguments.address.as_deref())?,
+            "commands": {
+                "genesis": format!(
+                    "fendermint genesis --genesis-file genesis.json ipc from-parent --subnet-id {subnet} -p {} --parent-gateway {:?} --parent-registry {:?}",
+                    parent_evm.provider_http,
+                    payload_to_evm_address(parent_evm.gateway_addr.payload())?,
+                    payload_to_evm_address(parent_evm.registry_addr.payload())?,
+                ),
+                "join": format!(
+                    "ipc-cli subnet join --subnet {subnet} --from {from} --collateral <FIL, at least {}>",
+                    genesis.min_collateral
+                ),
+            },
+        });
+
+        let bundle = serde_json::to_string_pretty(&bundle)?;
+        match &arguments.output {
+            Some(path) => {
+                fs::write(path, bundle)?;
+                println!("onboarding bundle for {subnet} written to {path}");
+            }
+            None => println!("{bundle}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// The config of the parent, without credentials or local paths, for the new validator to add to
+/// their own config.
+fn parent_stanza(mut subnet: Subnet) -> anyhow::Result<String> {
+    match &mut subnet.config {
+        SubnetConfig::Fevm(config) => {
+            config.auth_token = None;
+            config.auth = None;
+            config.tls = None;
+            config.keystore_path = None;
+            config.proxy = None;
+        }
+    }
+    subnet_to_toml(&subnet)
+}
+
+/// The formats of the validator key the subnet expects, filled in when the key is in the keystore.
+fn keys(provider: &IpcProvider, address: Option<&str>) -> anyhow::Result<Value> {
+    let Some(address) = address else {
+        return Ok(json!({
+            "evm_address": "ipc-cli wallet new --wallet-type evm",
+            "public_key": "ipc-cli wallet pub-key --wallet-type evm --address <EVM_ADDRESS>",
+            "fendermint_secret_key": "ipc-cli wallet export --wallet-type evm --address <EVM_ADDRESS> --fendermint",
+        }));
+    };
+    let eth = ethers::types::Address::from_str(address)?;
+    Ok(json!({
+        "evm_address": format!("{eth:?}"),
+        "fil_address": ethers_address_to_fil_address(&eth)?.to_string(),
+        "public_key": WalletPublicKey::pubkey_evm(provider, address)?,
+        "fendermint_secret_key": format!("ipc-cli wallet export --wallet-type evm --address {address} --fendermint"),
+    }))
+}
+
+#[derive(Debug, Args)]
+#[command(
+    name = "onboarding-bundle",
+    about = "Write the genesis parameters, bootstrap peers, parent config, key formats and join command of a subnet to one file"
+)]
+pub(crate) struct OnboardingBundleArgs {
+    #[arg(long, help = "The subnet to onboard validators to")]
+    pub subnet: String,
+    #[arg(
+        long,
+        help = "The evm address of the new validator in the local keystore, to fill in its public key"
+    )]
+    pub address: Option<String>,
+    #[arg(long, help = "The file to write the bundle to, stdout by default")]
+    pub output: Option<String>,
+}
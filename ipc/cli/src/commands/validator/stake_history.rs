@@ -0,0 +1,80 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+
+use crate::amount::AmountFormat;
+use crate::commands::get_ipc_provider;
+use crate::{require_fil_addr_from_str, CommandLineHandler, GlobalArguments};
+use async_trait::async_trait;
+use clap::Args;
+use ethers::types::U256;
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::eth_to_fil_amount;
+use ipc_api::staking::{StakingChange, StakingOperation};
+use ipc_api::subnet_id::SubnetID;
+use std::str::FromStr;
+
+#[derive(Debug, Args)]
+#[command(about = "List the staking changes of a validator in a subnet")]
+pub(crate) struct StakeHistoryArgs {
+    #[arg(long, help = "The subnet of the validator")]
+    pub subnet: String,
+    #[arg(long, help = "The address of the validator")]
+    pub validator: String,
+    #[arg(long, help = "The parent epoch to list changes from")]
+    pub from: ChainEpoch,
+    #[arg(long, help = "The parent epoch to list changes to")]
+    pub to: ChainEpoch,
+}
+
+pub(crate) struct StakeHistory;
+
+#[async_trait]
+impl CommandLineHandler for StakeHistory {
+    type Arguments = StakeHistoryArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("list validator stake history with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let subnet = SubnetID::from_str(&arguments.subnet)?;
+        let validator = require_fil_addr_from_str(&arguments.validator)?;
+
+        let history = provider
+            .stake_history(&subnet, &validator, arguments.from, arguments.to)
+            .await?;
+
+        println!("found total {} entries", history.len());
+        for entry in history {
+            println!(
+                "  height: {}, tx: 0x{}, configuration: {}, {}",
+                entry.height,
+                hex::encode(entry.tx_hash),
+                entry.change.configuration_number,
+                describe(&entry.change.change, &global.amount_format())?
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes a change, decoding the amount of deposits and withdrawals.
+fn describe(change: &StakingChange, amounts: &AmountFormat) -> anyhow::Result<String> {
+    let amount = || {
+        anyhow::ensure!(
+            change.payload.len() == 32,
+            "unexpected {:?} payload of {} bytes",
+            change.op,
+            change.payload.len()
+        );
+        let amount = eth_to_fil_amount(&U256::from_big_endian(&change.payload))?;
+        Ok(amounts.format(&amount))
+    };
+    Ok(match change.op {
+        StakingOperation::Deposit => format!("deposit: {}", amount()?),
+        StakingOperation::Withdraw => format!("withdraw: {}", amount()?),
+        StakingOperation::SetMetadata => "set metadata".to_string(),
+        StakingOperation::SetFederatedPower => "set federated power".to_string(),
+        _ => format!("{:?}", change.op),
+    })
+}
@@ -0,0 +1,61 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Wallet approve cli handler
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_provider::approval::IntentFile;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+pub(crate) struct WalletApprove;
+
+#[async_trait]
+impl CommandLineHandler for WalletApprove {
+    type Arguments = WalletApproveArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("approve intent with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let keystore = provider.evm_wallet()?;
+        let address = ethers::types::Address::from_str(&arguments.address)?;
+        let key_info = keystore
+            .read()
+            .unwrap()
+            .get(&address.into())?
+            .ok_or_else(|| anyhow!("key does not exists"))?;
+
+        let mut file = IntentFile::load(&arguments.intent)?;
+        println!("operation: {}", file.intent.operation);
+        for (name, value) in &file.intent.params {
+            println!("  {name}: {value}");
+        }
+
+        file.approve(key_info.private_key())?;
+        file.save(&arguments.intent)?;
+
+        let config = provider.config();
+        match config.approvals.as_ref() {
+            Some(approvals) => println!(
+                "approved, {} of {} required approvals",
+                file.approvers(approvals)?.len(),
+                approvals.threshold
+            ),
+            None => println!("approved, {} approvals", file.approvals.len()),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Sign an intent file of an operation that needs approval")]
+pub(crate) struct WalletApproveArgs {
+    #[arg(long, help = "The intent file to sign, updated in place")]
+    pub intent: String,
+    #[arg(long, help = "The ethereum address of the key to sign with")]
+    pub address: String,
+}
@@ -4,10 +4,11 @@
 
 use async_trait::async_trait;
 use clap::Args;
-use futures_util::future::join_all;
 use fvm_shared::{address::Address, econ::TokenAmount};
 use ipc_api::ethers_address_to_fil_address;
 use ipc_api::subnet_id::SubnetID;
+use ipc_provider::addressbook::{parse_address, AddressBook, LABEL_PREFIX};
+use ipc_provider::parallel::{join_bounded, DEFAULT_CONCURRENCY};
 use ipc_wallet::{EthKeyAddress, EvmKeyStore, WalletType};
 use std::{fmt::Debug, str::FromStr};
 
@@ -23,11 +24,22 @@ impl CommandLineHandler for WalletBalances {
         log::debug!("list wallets with args: {:?}", arguments);
 
         let provider = get_ipc_provider(global)?;
+        let amounts = global.amount_format();
 
         let wallet_type = WalletType::from_str(&arguments.wallet_type)?;
         let subnet = SubnetID::from_str(&arguments.subnet)?;
         let mut errors = Vec::new();
 
+        if arguments.token && matches!(wallet_type, WalletType::Fvm) {
+            return Err(anyhow::anyhow!(
+                "token balances are only supported for evm wallets"
+            ));
+        }
+        let token = match arguments.token {
+            true => Some(provider.supply_token_info(&subnet).await?),
+            false => None,
+        };
+
         match wallet_type {
             WalletType::Evm => {
                 let wallet = provider.evm_wallet()?;
@@ -37,19 +49,25 @@ impl CommandLineHandler for WalletBalances {
                     .map(|addr| {
                         let provider = provider.clone();
                         let subnet = subnet.clone();
+                        let token = token.clone();
                         async move {
-                            provider
-                                .wallet_balance(
-                                    &subnet,
-                                    &ethers_address_to_fil_address(&(addr.clone()).into())?,
-                                )
-                                .await
-                                .map(|balance| (balance, addr))
+                            let address = ethers_address_to_fil_address(&(addr.clone()).into())?;
+                            match &token {
+                                Some(token) => provider
+                                    .supply_token_balance(&subnet, &address)
+                                    .await
+                                    .map(|balance| (token.format_amount(&balance), addr)),
+                                None => provider
+                                    .wallet_balance(&subnet, &address)
+                                    .await
+                                    .map(|balance| (amounts.format(&balance), addr)),
+                            }
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let v: Vec<anyhow::Result<(TokenAmount, &EthKeyAddress)>> = join_all(r).await;
+                let v: Vec<anyhow::Result<(String, &EthKeyAddress)>> =
+                    join_bounded(r, DEFAULT_CONCURRENCY).await;
 
                 for r in v.into_iter() {
                     match r {
@@ -91,16 +109,54 @@ impl CommandLineHandler for WalletBalances {
                     })
                     .collect::<Vec<_>>();
 
-                let r = join_all(r)
+                let r = join_bounded(r, DEFAULT_CONCURRENCY)
                     .await
                     .into_iter()
                     .collect::<anyhow::Result<Vec<(TokenAmount, &Address)>>>()?;
                 for (balance, addr) in r {
-                    println!("{:?} - Balance: {}", addr, balance);
+                    println!("{:?} - Balance: {}", addr, amounts.format(&balance));
                 }
             }
         };
 
+        if arguments.watched {
+            let book = AddressBook::load(AddressBook::default_path())?;
+            let r = book
+                .entries()
+                .map(|(label, address)| {
+                    let provider = provider.clone();
+                    let subnet = subnet.clone();
+                    let token = token.clone();
+                    async move {
+                        let addr = parse_address(address)?;
+                        let balance = match &token {
+                            Some(token) => token.format_amount(
+                                &provider.supply_token_balance(&subnet, &addr).await?,
+                            ),
+                            None => amounts.format(&provider.wallet_balance(&subnet, &addr).await?),
+                        };
+                        anyhow::Ok((label, address, balance))
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            for r in join_bounded(r, DEFAULT_CONCURRENCY).await {
+                match r {
+                    Ok((label, address, balance)) => {
+                        println!("{LABEL_PREFIX}{label} ({address}) - Balance: {balance}")
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+            if !errors.is_empty() {
+                let error = errors.into_iter().fold(
+                    anyhow::anyhow!("Error fetching balances of watched addresses"),
+                    |acc, err| acc.context(err),
+                );
+                return Err(error);
+            }
+        }
+
         Ok(())
     }
 }
@@ -112,4 +168,14 @@ pub(crate) struct WalletBalancesArgs {
     pub subnet: String,
     #[arg(long, help = "The type of the wallet, i.e. fvm, evm")]
     pub wallet_type: String,
+    #[arg(
+        long,
+        help = "List the balances in the ERC20 supply source of the subnet, on its parent"
+    )]
+    pub token: bool,
+    #[arg(
+        long,
+        help = "Also list the balances of the addresses in the address book, which need no key"
+    )]
+    pub watched: bool,
 }
@@ -4,9 +4,10 @@
 use anyhow::anyhow;
 use async_trait::async_trait;
 use base64::{prelude::BASE64_STANDARD, Engine};
-use clap::Args;
+use clap::{Args, ValueEnum};
 use fs_err as fs;
 use fvm_shared::address::Address;
+use fvm_shared::crypto::signature::SignatureType;
 use ipc_provider::{lotus::message::wallet::WalletKeyType, IpcProvider, LotusJsonKeyType};
 use ipc_wallet::{EvmKeyStore, PersistentKeyInfo, WalletType};
 use std::fmt::Debug;
@@ -20,6 +21,17 @@ use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
 pub(crate) struct WalletExport;
 
 impl WalletExport {
+    /// Encodes a secp256k1 secret key in one of the formats understood by the node software,
+    /// or returns `None` for the wallet specific JSON format.
+    fn encode_secret(private_key: &[u8], format: ExportFormat) -> anyhow::Result<Option<String>> {
+        match format {
+            ExportFormat::Hex => Ok(Some(hex::encode(private_key))),
+            ExportFormat::Fendermint => Ok(Some(BASE64_STANDARD.encode(private_key))),
+            ExportFormat::Cometbft => Ok(Some(priv_validator_key(private_key)?)),
+            ExportFormat::Json => Ok(None),
+        }
+    }
+
     fn export_evm(provider: &IpcProvider, arguments: &WalletExportArgs) -> anyhow::Result<String> {
         let keystore = provider.evm_wallet()?;
         let address = ethers::types::Address::from_str(&arguments.address)?;
@@ -30,12 +42,8 @@ impl WalletExport {
             .get(&address.into())?
             .ok_or_else(|| anyhow!("key does not exists"))?;
 
-        if arguments.hex {
-            return Ok(hex::encode(key_info.private_key()));
-        }
-
-        if arguments.fendermint {
-            return Ok(BASE64_STANDARD.encode(key_info.private_key()));
+        if let Some(v) = Self::encode_secret(key_info.private_key(), arguments.format())? {
+            return Ok(v);
         }
 
         let info = PersistentKeyInfo::new(
@@ -51,12 +59,12 @@ impl WalletExport {
         let addr = Address::from_str(&arguments.address)?;
         let key_info = wallet.write().unwrap().export(&addr)?;
 
-        if arguments.hex {
-            return Ok(hex::encode(key_info.private_key()));
+        let format = arguments.format();
+        if format != ExportFormat::Json && *key_info.key_type() != SignatureType::Secp256k1 {
+            return Err(anyhow!("only secp256k1 keys can be exported as {format:?}"));
         }
-
-        if arguments.fendermint {
-            return Ok(BASE64_STANDARD.encode(key_info.private_key()));
+        if let Some(v) = Self::encode_secret(key_info.private_key(), format)? {
+            return Ok(v);
         }
 
         Ok(serde_json::to_string(&LotusJsonKeyType {
@@ -101,7 +109,7 @@ impl CommandLineHandler for WalletExport {
 }
 
 #[derive(Debug, Args)]
-#[command(about = "Export the key from a wallet address in JSON format")]
+#[command(about = "Export the key from a wallet address, in JSON format by default")]
 pub(crate) struct WalletExportArgs {
     #[arg(long, help = "Address of the key to export")]
     pub address: String,
@@ -114,22 +122,77 @@ pub(crate) struct WalletExportArgs {
     pub wallet_type: String,
     #[arg(
         long,
-        help = "Exports the secret key encoded in base64 as Fendermint expects"
+        conflicts_with = "format",
+        help = "Exports the secret key encoded in base64 as Fendermint expects, same as --format fendermint"
     )]
     pub fendermint: bool,
-    #[arg(long, help = "Export the hex encoded secret key")]
+    #[arg(
+        long,
+        conflicts_with_all = ["format", "fendermint"],
+        help = "Export the hex encoded secret key, same as --format hex"
+    )]
     pub hex: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "The format of the exported key: the wallet's JSON (default), hex, the base64 secret key Fendermint expects, or a CometBFT priv_validator_key.json"
+    )]
+    pub format: Option<ExportFormat>,
+}
+
+impl WalletExportArgs {
+    fn format(&self) -> ExportFormat {
+        if self.hex {
+            ExportFormat::Hex
+        } else if self.fendermint {
+            ExportFormat::Fendermint
+        } else {
+            self.format.unwrap_or_default()
+        }
+    }
+}
+
+/// The formats a key can be exported in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ExportFormat {
+    #[default]
+    Json,
+    Hex,
+    Fendermint,
+    Cometbft,
+}
+
+/// Renders a secp256k1 secret key as the `priv_validator_key.json` CometBFT loads for a validator.
+fn priv_validator_key(private_key: &[u8]) -> anyhow::Result<String> {
+    let sk = libsecp256k1::SecretKey::parse_slice(private_key)?;
+    let pk = libsecp256k1::PublicKey::from_secret_key(&sk);
+    let vk = tendermint::crypto::default::ecdsa_secp256k1::VerifyingKey::from_sec1_bytes(
+        &pk.serialize(),
+    )
+    .map_err(|e| anyhow!("failed to convert public key: {e}"))?;
+    let pub_key = tendermint::PublicKey::Secp256k1(vk);
+    let address = tendermint::account::Id::from(pub_key);
+
+    // tendermint-rs has no secp256k1 private validator key, so the JSON is built by hand the
+    // same way `fendermint key into-tendermint` does.
+    let key = serde_json::json!({
+        "address": address,
+        "pub_key": pub_key,
+        "priv_key": {
+            "type": "tendermint/PrivKeySecp256k1",
+            "value": BASE64_STANDARD.encode(sk.serialize()),
+        }
+    });
+    Ok(serde_json::to_string_pretty(&key)?)
 }
 
 pub(crate) struct WalletPublicKey;
 
 impl WalletPublicKey {
-    fn pubkey_evm(
-        provider: &IpcProvider,
-        arguments: &WalletPublicKeyArgs,
-    ) -> anyhow::Result<String> {
+    /// The hex encoded, uncompressed public key of an evm `address` in the keystore.
+    pub(crate) fn pubkey_evm(provider: &IpcProvider, address: &str) -> anyhow::Result<String> {
         let keystore = provider.evm_wallet()?;
-        let address = ethers::types::Address::from_str(&arguments.address)?;
+        let address = ethers::types::Address::from_str(address)?;
 
         let key_info = keystore
             .read()
@@ -166,7 +229,7 @@ impl CommandLineHandler for WalletPublicKey {
 
         let wallet_type = WalletType::from_str(&arguments.wallet_type)?;
         let v = match wallet_type {
-            WalletType::Evm => WalletPublicKey::pubkey_evm(&provider, arguments),
+            WalletType::Evm => WalletPublicKey::pubkey_evm(&provider, &arguments.address),
             WalletType::Fvm => WalletPublicKey::pubkey_fvm(&provider, arguments),
         }?;
         println!("{v}");
@@ -182,3 +245,29 @@ pub(crate) struct WalletPublicKeyArgs {
     #[arg(long, help = "The type of the wallet, i.e. fvm, evm")]
     pub wallet_type: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::priv_validator_key;
+
+    #[test]
+    fn test_priv_validator_key() {
+        let private_key: Vec<u8> = (1..=32).collect();
+        let key: serde_json::Value =
+            serde_json::from_str(&priv_validator_key(&private_key).unwrap()).unwrap();
+
+        // the output of `fendermint key into-tendermint` for the same secret key
+        let expected = serde_json::json!({
+            "address": "587C9BFC87A837A056F716C2F9DE891ADBC46C90",
+            "pub_key": {
+                "type": "tendermint/PubKeySecp256k1",
+                "value": "AoS/dWImK71pQAhXSPO+avpSrjFxVRgezjG2Y1HM/6Sw"
+            },
+            "priv_key": {
+                "type": "tendermint/PrivKeySecp256k1",
+                "value": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA="
+            }
+        });
+        assert_eq!(key, expected);
+    }
+}
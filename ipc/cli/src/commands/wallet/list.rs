@@ -34,7 +34,11 @@ impl CommandLineHandler for WalletList {
                     let pub_key =
                         hex::encode(libsecp256k1::PublicKey::from_secret_key(&sk).serialize())
                             .to_string();
-                    println!("\tPubKey: {}", pub_key);
+                    println!(
+                        "\tPubKey: {}\tFingerprint: {}",
+                        pub_key,
+                        key_info.fingerprint()?
+                    );
                 }
                 Ok(())
             }
@@ -6,6 +6,7 @@ use crate::commands::wallet::balances::{WalletBalances, WalletBalancesArgs};
 use crate::commands::wallet::new::{WalletNew, WalletNewArgs};
 use clap::{Args, Subcommand};
 
+use self::approve::{WalletApprove, WalletApproveArgs};
 use self::default::{
     WalletGetDefault, WalletGetDefaultArgs, WalletSetDefault, WalletSetDefaultArgs,
 };
@@ -13,14 +14,17 @@ use self::export::{WalletExport, WalletExportArgs, WalletPublicKey, WalletPublic
 use self::import::{WalletImport, WalletImportArgs};
 use self::list::{WalletList, WalletListArgs};
 use self::remove::{WalletRemove, WalletRemoveArgs};
+use self::verify::{WalletVerify, WalletVerifyArgs};
 
+mod approve;
 mod balances;
 mod default;
-mod export;
+pub(crate) mod export;
 mod import;
 mod list;
 mod new;
 mod remove;
+mod verify;
 
 #[derive(Debug, Args)]
 #[command(name = "wallet", about = "wallet related commands")]
@@ -42,6 +46,8 @@ impl WalletCommandsArgs {
             Commands::GetDefault(args) => WalletGetDefault::handle(global, args).await,
             Commands::PubKey(args) => WalletPublicKey::handle(global, args).await,
             Commands::List(args) => WalletList::handle(global, args).await,
+            Commands::Approve(args) => WalletApprove::handle(global, args).await,
+            Commands::Verify(args) => WalletVerify::handle(global, args).await,
         }
     }
 }
@@ -57,4 +63,6 @@ pub(crate) enum Commands {
     GetDefault(WalletGetDefaultArgs),
     PubKey(WalletPublicKeyArgs),
     List(WalletListArgs),
+    Approve(WalletApproveArgs),
+    Verify(WalletVerifyArgs),
 }
@@ -0,0 +1,65 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Wallet verify cli handler
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Args;
+use ipc_wallet::{EthKeyAddress, EvmKeyStore};
+use std::fmt::Debug;
+
+use crate::{get_ipc_provider, CommandLineHandler, GlobalArguments};
+
+/// The command to check the evm keystore for tampering or corruption.
+pub(crate) struct WalletVerify;
+
+#[async_trait]
+impl CommandLineHandler for WalletVerify {
+    type Arguments = WalletVerifyArgs;
+
+    async fn handle(global: &GlobalArguments, arguments: &Self::Arguments) -> anyhow::Result<()> {
+        log::debug!("verify wallet with args: {:?}", arguments);
+
+        let provider = get_ipc_provider(global)?;
+        let wallet = provider.evm_wallet()?;
+        let mut keystore = wallet.write().unwrap();
+
+        let problems = keystore.verify();
+        if problems.is_empty() {
+            for address in keystore.list()? {
+                if address == EthKeyAddress::default() {
+                    continue;
+                }
+                if let Some(info) = keystore.get(&address)? {
+                    println!("{address}\tfingerprint: {}", info.fingerprint()?);
+                }
+            }
+            println!("keystore is intact");
+            return Ok(());
+        }
+
+        for problem in &problems {
+            println!("{problem}");
+        }
+        if arguments.accept {
+            keystore.reseal()?;
+            println!("accepted the keystore as it is and wrote a new checksum");
+            return Ok(());
+        }
+        Err(anyhow!(
+            "the keystore failed verification, its keys cannot be used until it is restored or accepted with --accept"
+        ))
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Check the evm keystore against its checksum and each key against its address, and print the key fingerprints"
+)]
+pub(crate) struct WalletVerifyArgs {
+    #[arg(
+        long,
+        help = "Accept the keystore as it is, storing each key under its own address, and write a new checksum"
+    )]
+    pub accept: bool,
+}
@@ -0,0 +1,94 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! The failures scripts wrapping ipc-cli can branch on, and the exit code of each.
+
+use ipc_provider::manager::UnsupportedCapability;
+use ipc_provider::DeadlineElapsed;
+
+/// Any failure not covered by a more specific code.
+pub const EXIT_FAILURE: i32 = 1;
+/// Invalid arguments, as reported by clap.
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_CONFIG: i32 = 3;
+pub const EXIT_CONNECTION: i32 = 4;
+pub const EXIT_INSUFFICIENT_FUNDS: i32 = 5;
+pub const EXIT_NOT_BOOTSTRAPPED: i32 = 6;
+pub const EXIT_QUORUM_NOT_REACHED: i32 = 7;
+pub const EXIT_UNSUPPORTED: i32 = 8;
+
+/// A failure with its own exit code. Command handlers return it, wrapped in an
+/// [`anyhow::Error`] like any other error, where the cause is known.
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("connection error: {0}")]
+    Connection(String),
+    #[error("insufficient funds: {0}")]
+    InsufficientFunds(String),
+    #[error("subnet {0} is not bootstrapped")]
+    NotBootstrapped(String),
+    #[error("quorum not reached: {0}")]
+    QuorumNotReached(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Config(_) => EXIT_CONFIG,
+            CliError::Connection(_) => EXIT_CONNECTION,
+            CliError::InsufficientFunds(_) => EXIT_INSUFFICIENT_FUNDS,
+            CliError::NotBootstrapped(_) => EXIT_NOT_BOOTSTRAPPED,
+            CliError::QuorumNotReached(_) => EXIT_QUORUM_NOT_REACHED,
+        }
+    }
+}
+
+/// The exit code for `e`. Errors returned by the nodes are not typed, so those are recognised
+/// by their message as a last resort.
+pub fn exit_code(e: &anyhow::Error) -> i32 {
+    if let Some(e) = e.downcast_ref::<CliError>() {
+        return e.exit_code();
+    }
+    for cause in e.chain() {
+        if cause.is::<UnsupportedCapability>() {
+            return EXIT_UNSUPPORTED;
+        }
+        if cause.is::<DeadlineElapsed>() || cause.is::<reqwest::Error>() {
+            return EXIT_CONNECTION;
+        }
+    }
+
+    let message = format!("{e:#}").to_lowercase();
+    if message.contains("insufficient funds") || message.contains("insufficient balance") {
+        EXIT_INSUFFICIENT_FUNDS
+    } else if message.contains("error sending request") || message.contains("connection refused") {
+        EXIT_CONNECTION
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        exit_code, CliError, EXIT_FAILURE, EXIT_INSUFFICIENT_FUNDS, EXIT_QUORUM_NOT_REACHED,
+    };
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn test_exit_code() {
+        let e = anyhow::Error::from(CliError::QuorumNotReached("height 10".into()))
+            .context("error processing command");
+        assert_eq!(exit_code(&e), EXIT_QUORUM_NOT_REACHED);
+
+        let e = Err::<(), _>(anyhow!(
+            "(code: -32000, message: insufficient funds for gas * price + value)"
+        ))
+        .context("error processing command")
+        .unwrap_err();
+        assert_eq!(exit_code(&e), EXIT_INSUFFICIENT_FUNDS);
+
+        assert_eq!(exit_code(&anyhow!("something else")), EXIT_FAILURE);
+    }
+}
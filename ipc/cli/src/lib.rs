@@ -5,8 +5,13 @@ use async_trait::async_trait;
 use clap::Args;
 use fvm_shared::address::Network;
 use num_traits::cast::FromPrimitive;
+use std::time::Duration;
 
+use amount::{AmountFormat, Denomination};
+
+pub mod amount;
 mod commands;
+pub mod errors;
 
 pub use commands::*;
 use ipc_provider::config::Config;
@@ -46,6 +51,33 @@ pub struct GlobalArguments {
     /// Legacy env var for network
     #[arg(long = "__network", hide = true, env = "NETWORK", value_parser = parse_network)]
     __network: Option<Network>,
+
+    /// Abort calls to the subnet networks that take longer than this many seconds.
+    #[arg(long = "timeout", env = "IPC_CLI_TIMEOUT")]
+    timeout_secs: Option<u64>,
+
+    /// Write every JSON-RPC request and response exchanged with the subnets to this directory,
+    /// with credentials redacted, e.g. to attach to a bug report.
+    #[arg(long = "record-rpc", env = "IPC_CLI_RECORD_RPC")]
+    record_rpc: Option<String>,
+
+    /// Wait for keystores locked by another ipc-cli process until they are released, instead of
+    /// giving up after a few seconds.
+    #[arg(long = "wait-for-lock", env = "IPC_CLI_WAIT_FOR_LOCK")]
+    wait_for_lock: bool,
+
+    /// The unit token amounts are printed in.
+    #[arg(
+        long = "denomination",
+        value_enum,
+        default_value = "fil",
+        env = "IPC_CLI_DENOMINATION"
+    )]
+    denomination: Denomination,
+
+    /// Round printed token amounts to this many decimals, instead of showing all of them.
+    #[arg(long = "precision", env = "IPC_CLI_PRECISION")]
+    precision: Option<u32>,
 }
 
 impl GlobalArguments {
@@ -58,11 +90,31 @@ impl GlobalArguments {
     pub fn config(&self) -> Result<Config> {
         let config_path = self.config_path();
         Config::from_file(config_path)
+            .map_err(|e| errors::CliError::Config(format!("{e:#}")).into())
     }
 
     pub fn network(&self) -> Network {
         self.__network.unwrap_or(self._network)
     }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn record_rpc(&self) -> Option<&str> {
+        self.record_rpc.as_deref()
+    }
+
+    pub fn wait_for_lock(&self) -> bool {
+        self.wait_for_lock
+    }
+
+    pub fn amount_format(&self) -> AmountFormat {
+        AmountFormat {
+            denomination: self.denomination,
+            precision: self.precision,
+        }
+    }
 }
 
 /// Parse the FVM network and set the global value.
@@ -13,6 +13,6 @@ async fn main() {
 
     if let Err(e) = ipc_cli::cli().await {
         log::error!("main process failed: {e:#}");
-        std::process::exit(1);
+        std::process::exit(ipc_cli::errors::exit_code(&e));
     }
 }
@@ -0,0 +1,151 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Labels for frequently used addresses, so commands can refer to `@treasury` instead of the
+//! address itself.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use fvm_shared::address::{Address, Payload};
+use ipc_api::ethers_address_to_fil_address;
+use serde::{Deserialize, Serialize};
+
+use crate::default_repo_path;
+
+/// The name of the address book file in the ipc home directory.
+pub const ADDRESS_BOOK_NAME: &str = "addressbook.toml";
+/// The prefix that marks a label where an address is expected.
+pub const LABEL_PREFIX: char = '@';
+
+/// Addresses by label, stored as TOML.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddressBook {
+    #[serde(default)]
+    addresses: BTreeMap<String, String>,
+}
+
+impl AddressBook {
+    /// The address book in the default ipc home directory.
+    pub fn default_path() -> PathBuf {
+        Path::new(&default_repo_path()).join(ADDRESS_BOOK_NAME)
+    }
+
+    /// Loads the address book at `path`, empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs_err::read_to_string(path)?;
+        toml::from_str(&contents)
+            .with_context(|| format!("failed to parse address book {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs_err::create_dir_all(dir)?;
+        }
+        fs_err::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Adds or replaces `label`. The address can be given as an `f`/`t` address or as a `0x`
+    /// Ethereum address.
+    pub fn add(&mut self, label: &str, address: &str) -> Result<()> {
+        let label = label.strip_prefix(LABEL_PREFIX).unwrap_or(label);
+        if label.is_empty()
+            || !label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow!(
+                "invalid label {label:?}, use letters, digits, '-' and '_'"
+            ));
+        }
+        parse_address(address)?;
+        self.addresses
+            .insert(label.to_string(), address.to_string());
+        Ok(())
+    }
+
+    /// Removes `label`, returning its address if it was present.
+    pub fn remove(&mut self, label: &str) -> Option<String> {
+        let label = label.strip_prefix(LABEL_PREFIX).unwrap_or(label);
+        self.addresses.remove(label)
+    }
+
+    /// The address of `label`.
+    pub fn get(&self, label: &str) -> Result<Address> {
+        let label = label.strip_prefix(LABEL_PREFIX).unwrap_or(label);
+        let address = self.addresses.get(label).ok_or_else(|| {
+            anyhow!("no address labelled {LABEL_PREFIX}{label} in the address book")
+        })?;
+        parse_address(address)
+    }
+
+    /// The labels and their addresses as they were added, sorted by label.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.addresses.iter()
+    }
+
+    /// Resolves `s` as a label if it starts with [`LABEL_PREFIX`], or parses it as an address.
+    pub fn resolve(&self, s: &str) -> Result<Address> {
+        if s.starts_with(LABEL_PREFIX) {
+            self.get(s)
+        } else {
+            parse_address(s)
+        }
+    }
+}
+
+/// Parses an `f`/`t` address or a `0x` Ethereum address.
+pub fn parse_address(s: &str) -> Result<Address> {
+    match Address::from_str(s) {
+        Ok(addr) => Ok(addr),
+        Err(_) => {
+            let addr = ethers::types::Address::from_str(s)
+                .map_err(|_| anyhow!("{s} is neither a filecoin nor an ethereum address"))?;
+            ethers_address_to_fil_address(&addr)
+        }
+    }
+}
+
+/// The Ethereum representation of `address`, for delegated addresses.
+pub fn eth_representation(address: &Address) -> Option<String> {
+    match address.payload() {
+        Payload::Delegated(d) if d.subaddress().len() == 20 => {
+            Some(format!("0x{}", hex::encode(d.subaddress())))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eth_representation, AddressBook};
+
+    const ETH_ADDRESS: &str = "0x6be1ccf648c74800380d0520d797a170c808b624";
+
+    #[test]
+    fn test_address_book() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("addressbook.toml");
+
+        let mut book = AddressBook::load(&path).unwrap();
+        book.add("treasury", ETH_ADDRESS).unwrap();
+        book.add("@ops", "f01234").unwrap();
+        assert!(book.add("bad label", "f01234").is_err());
+        assert!(book.add("other", "not-an-address").is_err());
+        book.save(&path).unwrap();
+
+        let book = AddressBook::load(&path).unwrap();
+        let treasury = book.resolve("@treasury").unwrap();
+        assert_eq!(eth_representation(&treasury).unwrap(), ETH_ADDRESS);
+        assert_eq!(book.resolve("@ops").unwrap().to_string(), "f01234");
+        assert_eq!(book.resolve("f01234").unwrap().to_string(), "f01234");
+        assert!(book.resolve("@missing").is_err());
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Offline m-of-n approval of high-value operations.
+//!
+//! An operation covered by the [`ApprovalConfig`] is first written out as an [`Intent`] file.
+//! Keyholders sign the intent offline, and the operation only runs once the file carries enough
+//! signatures from configured approvers for exactly the same parameters. Each intent carries a
+//! random nonce and is recorded once executed, so an approved file cannot be replayed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use ethers::signers::LocalWallet;
+use ethers::types::{Address, Signature, H256};
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ApprovalConfig;
+use crate::store::{put_json, StateStore};
+
+/// How long a signed intent can be executed for after it was created.
+pub const INTENT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The directory, next to the config, in which executed intents are recorded.
+pub const APPROVALS_DIR: &str = "approvals";
+
+/// An operation waiting for approval, with the parameters it will be executed with.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Intent {
+    pub operation: String,
+    pub params: BTreeMap<String, String>,
+    /// Unix timestamp after which the intent can no longer be executed.
+    pub expires_at: u64,
+    /// Makes the digest of every intent unique, even for the same operation and parameters.
+    pub nonce: H256,
+}
+
+impl Intent {
+    pub fn new(operation: &str, params: BTreeMap<String, String>) -> Self {
+        Self {
+            operation: operation.to_string(),
+            params,
+            expires_at: now() + INTENT_TTL.as_secs(),
+            nonce: H256::random(),
+        }
+    }
+
+    /// The hash signed by the approvers.
+    pub fn digest(&self) -> Result<H256> {
+        // the fields and the sorted params serialize deterministically
+        Ok(H256(keccak256(serde_json::to_vec(self)?)))
+    }
+}
+
+/// A signature of an intent by one approver.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Approval {
+    pub signer: Address,
+    pub signature: String,
+}
+
+/// The file passed around between the keyholders.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IntentFile {
+    pub intent: Intent,
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+}
+
+impl IntentFile {
+    pub fn new(intent: Intent) -> Self {
+        Self {
+            intent,
+            approvals: vec![],
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs_err::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse intent file {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs_err::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Signs the intent with `private_key`, replacing an earlier signature of the same key.
+    pub fn approve(&mut self, private_key: &[u8]) -> Result<Address> {
+        let wallet = LocalWallet::from_bytes(private_key)?;
+        let signature = wallet.sign_hash(self.intent.digest()?)?;
+        let signer = ethers::signers::Signer::address(&wallet);
+
+        self.approvals.retain(|a| a.signer != signer);
+        self.approvals.push(Approval {
+            signer,
+            signature: signature.to_string(),
+        });
+        Ok(signer)
+    }
+
+    /// The configured approvers with a valid signature of the intent.
+    pub fn approvers(&self, config: &ApprovalConfig) -> Result<BTreeSet<Address>> {
+        let digest = self.intent.digest()?;
+        let mut approvers = BTreeSet::new();
+        for approval in &self.approvals {
+            let signature: Signature = approval
+                .signature
+                .parse()
+                .with_context(|| format!("invalid signature of {:?}", approval.signer))?;
+            let signer = signature.recover(digest)?;
+            if signer == approval.signer && config.approvers.contains(&signer) {
+                approvers.insert(signer);
+            }
+        }
+        Ok(approvers)
+    }
+
+    /// Checks that the file approves `operation` with `params` and carries enough signatures.
+    pub fn verify(
+        &self,
+        config: &ApprovalConfig,
+        operation: &str,
+        params: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        if self.intent.operation != operation || &self.intent.params != params {
+            return Err(anyhow!(
+                "the intent is for {} with {:?}, not {operation} with {params:?}",
+                self.intent.operation,
+                self.intent.params
+            ));
+        }
+        if now() > self.intent.expires_at {
+            return Err(anyhow!("the intent expired, create a new one"));
+        }
+        config.validate()?;
+        let approvers = self.approvers(config)?;
+        if approvers.len() < config.threshold {
+            return Err(anyhow!(
+                "the intent has {} of the {} required approvals",
+                approvers.len(),
+                config.threshold
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records in `store` that the intent is being executed, failing if it was executed before.
+    /// Called right before the operation runs: an intent whose operation then fails has to be
+    /// approved again.
+    pub fn consume(&self, store: &dyn StateStore) -> Result<()> {
+        let key = format!("executed/{:x}", self.intent.digest()?);
+        if store.get(&key)?.is_some() {
+            return Err(anyhow!("the intent was already executed, create a new one"));
+        }
+        put_json(store, &key, &now())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ethers::signers::{LocalWallet, Signer};
+
+    use super::{Intent, IntentFile};
+    use crate::config::ApprovalConfig;
+    use crate::store::MemoryStore;
+
+    #[test]
+    fn test_approval_threshold() {
+        let keys = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let addresses = keys
+            .iter()
+            .map(|k| LocalWallet::from_bytes(k).unwrap().address())
+            .collect::<Vec<_>>();
+        let config = ApprovalConfig {
+            threshold: 2,
+            approvers: addresses[..2].to_vec(),
+            min_release_fil: None,
+        };
+
+        let params = BTreeMap::from([("subnet".to_string(), "/r314159/t01".to_string())]);
+        let mut file = IntentFile::new(Intent::new("subnet-kill", params.clone()));
+
+        file.approve(&keys[0]).unwrap();
+        // signing twice does not count twice
+        file.approve(&keys[0]).unwrap();
+        // not a configured approver
+        file.approve(&keys[2]).unwrap();
+        assert!(file.verify(&config, "subnet-kill", &params).is_err());
+
+        file.approve(&keys[1]).unwrap();
+        file.verify(&config, "subnet-kill", &params).unwrap();
+
+        // the approvals are bound to the parameters
+        let other = BTreeMap::from([("subnet".to_string(), "/r314159/t02".to_string())]);
+        assert!(file.verify(&config, "subnet-kill", &other).is_err());
+
+        // and to the content of the intent
+        file.intent.expires_at += 1;
+        assert!(file.verify(&config, "subnet-kill", &params).is_err());
+    }
+
+    #[test]
+    fn test_intent_executes_once() {
+        let params = BTreeMap::from([("subnet".to_string(), "/r314159/t01".to_string())]);
+        let file = IntentFile::new(Intent::new("subnet-kill", params.clone()));
+        let store = MemoryStore::default();
+
+        file.consume(&store).unwrap();
+        assert!(file.consume(&store).is_err());
+
+        // a new intent for the same operation is a different intent
+        IntentFile::new(Intent::new("subnet-kill", params))
+            .consume(&store)
+            .unwrap();
+    }
+}
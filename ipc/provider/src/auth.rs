@@ -0,0 +1,113 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Resolution of the `Authorization` header sent to subnet endpoints.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use ethers::providers::Authorization;
+use ipc_observability::lazy_static;
+
+use crate::config::subnet::AuthConfig;
+
+/// How long a token printed by a command is reused when the config does not say.
+const DEFAULT_COMMAND_REFRESH: Duration = Duration::from_secs(300);
+
+lazy_static! {
+    /// Tokens printed by auth commands, keyed by command, with the time they were fetched.
+    static ref COMMAND_TOKENS: Mutex<HashMap<String, (String, Instant)>> = Mutex::new(HashMap::new());
+}
+
+/// Returns the value of the `Authorization` header for `auth`.
+///
+/// Connections are created for every provider call, so tokens printed by a command are picked up
+/// once they are older than the refresh interval, without restarting the process.
+pub fn authorization(auth: &AuthConfig) -> Result<Authorization> {
+    Ok(match auth {
+        AuthConfig::Bearer { token } => Authorization::Bearer(token.clone()),
+        AuthConfig::Basic { username, password } => {
+            Authorization::basic(username, password.as_deref().unwrap_or_default())
+        }
+        AuthConfig::Command {
+            command,
+            refresh_secs,
+        } => {
+            let refresh = refresh_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_COMMAND_REFRESH);
+            Authorization::Bearer(command_token(command, refresh)?)
+        }
+    })
+}
+
+fn command_token(command: &str, refresh: Duration) -> Result<String> {
+    let mut tokens = COMMAND_TOKENS.lock().unwrap();
+    if let Some((token, fetched)) = tokens.get(command) {
+        if fetched.elapsed() < refresh {
+            return Ok(token.clone());
+        }
+    }
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .with_context(|| format!("failed to run auth command: {command}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "auth command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let token = String::from_utf8(output.stdout)
+        .context("auth command printed a non utf-8 token")?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        return Err(anyhow!("auth command printed an empty token"));
+    }
+
+    tokens.insert(command.to_string(), (token.clone(), Instant::now()));
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::authorization;
+    use crate::config::subnet::AuthConfig;
+
+    #[test]
+    fn test_basic_auth() {
+        let auth = AuthConfig::Basic {
+            username: "user".to_string(),
+            password: Some("pass".to_string()),
+        };
+        assert_eq!(
+            authorization(&auth).unwrap().to_string(),
+            "Basic dXNlcjpwYXNz"
+        );
+    }
+
+    #[test]
+    fn test_command_auth() {
+        let auth = AuthConfig::Command {
+            command: "echo ' rotated-token '".to_string(),
+            refresh_secs: None,
+        };
+        assert_eq!(
+            authorization(&auth).unwrap().to_string(),
+            "Bearer rotated-token"
+        );
+
+        let failing = AuthConfig::Command {
+            command: "exit 1".to_string(),
+            refresh_secs: None,
+        };
+        assert!(authorization(&failing).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Programmatic construction of subnets, for users of the provider that do not go through the cli.
+
+use fvm_shared::{address::Address, clock::ChainEpoch, econ::TokenAmount};
+use ipc_api::ethers_address_to_fil_address;
+use ipc_api::subnet::{Asset, PermissionMode};
+use ipc_api::subnet_id::SubnetID;
+
+use crate::IpcProvider;
+
+/// Default cap on the number of active validators, same as the cli.
+const DEFAULT_ACTIVE_VALIDATORS: u16 = 100;
+/// Default minimum cross-net message fee of 1 microFIL, same as the cli.
+const DEFAULT_MIN_CROSS_MSG_FEE_NANO: u64 = 1_000;
+
+/// Collects the parameters of a new subnet and creates the subnet actor.
+///
+/// ```ignore
+/// let address = SubnetBuilder::on_parent(parent)
+///     .min_validators(4)
+///     .min_validator_stake(TokenAmount::from_whole(1))
+///     .bottomup_check_period(300)
+///     .create(&mut provider)
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubnetBuilder {
+    parent: SubnetID,
+    from: Option<Address>,
+    min_validators: u64,
+    min_validator_stake: TokenAmount,
+    bottomup_check_period: ChainEpoch,
+    active_validators_limit: u16,
+    min_cross_msg_fee: TokenAmount,
+    permission_mode: PermissionMode,
+    supply_source: Asset,
+    collateral_source: Asset,
+    validator_gater: Option<Address>,
+    validator_rewarder: Option<Address>,
+}
+
+impl SubnetBuilder {
+    /// Starts building a subnet to be created in `parent`.
+    pub fn on_parent(parent: SubnetID) -> Self {
+        Self {
+            parent,
+            from: None,
+            min_validators: 1,
+            min_validator_stake: TokenAmount::default(),
+            bottomup_check_period: 0,
+            active_validators_limit: DEFAULT_ACTIVE_VALIDATORS,
+            min_cross_msg_fee: TokenAmount::from_nano(DEFAULT_MIN_CROSS_MSG_FEE_NANO),
+            permission_mode: PermissionMode::Collateral,
+            supply_source: Asset::default(),
+            collateral_source: Asset::default(),
+            validator_gater: None,
+            validator_rewarder: None,
+        }
+    }
+
+    /// The address that sends the creation transaction, the provider default otherwise.
+    pub fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn min_validators(mut self, min_validators: u64) -> Self {
+        self.min_validators = min_validators;
+        self
+    }
+
+    pub fn min_validator_stake(mut self, stake: TokenAmount) -> Self {
+        self.min_validator_stake = stake;
+        self
+    }
+
+    pub fn bottomup_check_period(mut self, period: ChainEpoch) -> Self {
+        self.bottomup_check_period = period;
+        self
+    }
+
+    pub fn active_validators_limit(mut self, limit: u16) -> Self {
+        self.active_validators_limit = limit;
+        self
+    }
+
+    pub fn min_cross_msg_fee(mut self, fee: TokenAmount) -> Self {
+        self.min_cross_msg_fee = fee;
+        self
+    }
+
+    pub fn permission_mode(mut self, mode: PermissionMode) -> Self {
+        self.permission_mode = mode;
+        self
+    }
+
+    pub fn supply_source(mut self, source: Asset) -> Self {
+        self.supply_source = source;
+        self
+    }
+
+    pub fn collateral_source(mut self, source: Asset) -> Self {
+        self.collateral_source = source;
+        self
+    }
+
+    pub fn validator_gater(mut self, gater: Address) -> Self {
+        self.validator_gater = Some(gater);
+        self
+    }
+
+    pub fn validator_rewarder(mut self, rewarder: Address) -> Self {
+        self.validator_rewarder = Some(rewarder);
+        self
+    }
+
+    /// Creates the subnet actor, returning its address in the parent. The parameters are checked
+    /// with [`ipc_api::subnet::ConstructParams::validate`] before anything is sent.
+    pub async fn create(self, provider: &mut IpcProvider) -> anyhow::Result<Address> {
+        let zero = ethers_address_to_fil_address(&ethers::types::Address::zero())?;
+        provider
+            .create_subnet(
+                self.from,
+                self.parent,
+                self.min_validators,
+                self.min_validator_stake,
+                self.bottomup_check_period,
+                self.active_validators_limit,
+                self.min_cross_msg_fee,
+                self.permission_mode,
+                self.supply_source,
+                self.collateral_source,
+                self.validator_gater.unwrap_or(zero),
+                self.validator_rewarder.unwrap_or(zero),
+            )
+            .await
+            .map(|created| created.address)
+    }
+}
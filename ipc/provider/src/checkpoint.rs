@@ -3,21 +3,58 @@
 //! Bottom up checkpoint manager
 
 use crate::config::Subnet;
+use crate::history::History;
 use crate::manager::{BottomUpCheckpointRelayer, EthSubnetManager};
+use crate::notify::{Notification, Notifier};
 use crate::observe::CheckpointSubmitted;
+use crate::store::{get_json, put_json, StateStore};
 use anyhow::{anyhow, Result};
 use futures_util::future::try_join_all;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
 use ipc_api::checkpoint::{BottomUpCheckpointBundle, QuorumReachedEvent};
+use ipc_api::subnet_id::SubnetID;
 use ipc_observability::{emit, serde::HexEncodableBlockHash};
 use ipc_wallet::{EthKeyAddress, PersistentKeyStore};
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Semaphore;
 
+/// A submission recorded by a previous run is retried once it is older than this, in case its
+/// transaction never made it into the parent.
+const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The balance the submitter has to keep in the parent to pay for its submissions.
+#[derive(Debug, Clone)]
+pub struct BalanceThreshold {
+    pub minimum: TokenAmount,
+    /// Stop submitting, rather than only warning, while the balance is below the minimum.
+    pub refuse: bool,
+}
+
+/// Holds back checkpoint submissions while the parent's base fee is high, so they go out once
+/// fees drop, together with the checkpoints that became ready in the meantime.
+#[derive(Debug, Clone)]
+pub struct FeeWindow {
+    /// The base fee per gas in the parent above which submissions are held back.
+    pub max_base_fee: TokenAmount,
+    /// How long submissions are held back at most, after which they are sent whatever the fee.
+    pub max_delay: Duration,
+}
+
+impl FeeWindow {
+    /// Whether to hold back submissions at `base_fee`, having held them back for `waited`.
+    fn defers(&self, base_fee: &TokenAmount, waited: Duration) -> bool {
+        *base_fee > self.max_base_fee && waited < self.max_delay
+    }
+}
+
 /// Tracks the config required for bottom up checkpoint submissions
 /// parent/child subnet and checkpoint period.
 pub struct CheckpointConfig {
@@ -36,6 +73,23 @@ pub struct BottomUpCheckpointManager<T> {
     /// The number of blocks away from the chain head that is considered final
     finalization_blocks: ChainEpoch,
     submission_semaphore: Arc<Semaphore>,
+    /// Where submissions are recorded while in flight, so a restarted relayer does not submit
+    /// them a second time.
+    store: Option<Arc<dyn StateStore>>,
+    /// Where the submissions are recorded for the operator, if anywhere.
+    history: Option<History>,
+    /// Where alerts about submissions are sent, if anywhere.
+    notifier: Option<Notifier>,
+    /// The last checkpoint seen committed in the parent, to notify when it moves up.
+    last_confirmed: AtomicI64,
+    /// The balance below which the submitter is reported, if any.
+    balance_threshold: Option<BalanceThreshold>,
+    /// Whether the submitter was below the threshold in the last round, to notify only once.
+    low_balance: AtomicBool,
+    /// The base fee above which submissions are held back, if any.
+    fee_window: Option<FeeWindow>,
+    /// Since when submissions are held back for the base fee.
+    deferred_since: Mutex<Option<Instant>>,
 }
 
 impl<T: BottomUpCheckpointRelayer> BottomUpCheckpointManager<T> {
@@ -60,6 +114,14 @@ impl<T: BottomUpCheckpointRelayer> BottomUpCheckpointManager<T> {
             child_handler,
             finalization_blocks: 0,
             submission_semaphore: Arc::new(Semaphore::new(max_parallelism)),
+            store: None,
+            history: None,
+            notifier: None,
+            last_confirmed: AtomicI64::new(-1),
+            balance_threshold: None,
+            low_balance: AtomicBool::new(false),
+            fee_window: None,
+            deferred_since: Mutex::new(None),
         })
     }
 
@@ -67,6 +129,31 @@ impl<T: BottomUpCheckpointRelayer> BottomUpCheckpointManager<T> {
         self.finalization_blocks = finalization_blocks;
         self
     }
+
+    pub fn with_state_store(mut self, store: Arc<dyn StateStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    pub fn with_balance_threshold(mut self, threshold: BalanceThreshold) -> Self {
+        self.balance_threshold = Some(threshold);
+        self
+    }
+
+    pub fn with_fee_window(mut self, window: FeeWindow) -> Self {
+        self.fee_window = Some(window);
+        self
+    }
 }
 
 impl BottomUpCheckpointManager<EthSubnetManager> {
@@ -119,13 +206,32 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
 
     /// Run the bottom up checkpoint submission daemon in the foreground
     pub async fn run(self, submitter: Address, submission_interval: Duration) {
+        self.run_until(submitter, submission_interval, std::future::pending())
+            .await
+    }
+
+    /// Run the daemon until `shutdown` resolves. A submission round in progress is finished
+    /// first, so no checkpoint is left half submitted.
+    pub async fn run_until(
+        self,
+        submitter: Address,
+        submission_interval: Duration,
+        shutdown: impl Future<Output = ()>,
+    ) {
         tracing::info!("launching {self} for {submitter}");
+        tokio::pin!(shutdown);
 
         loop {
             if let Err(e) = self.submit_next_epoch(submitter).await {
                 tracing::error!("cannot submit checkpoint for submitter: {submitter} due to {e}");
             }
-            tokio::time::sleep(submission_interval).await;
+            tokio::select! {
+                _ = &mut shutdown => {
+                    tracing::info!("stopping {self}");
+                    return;
+                }
+                _ = tokio::time::sleep(submission_interval) => {}
+            }
         }
     }
 
@@ -140,6 +246,18 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
             })?;
         tracing::info!("last submission height: {last_checkpoint_epoch}");
 
+        let previous = self
+            .last_confirmed
+            .swap(last_checkpoint_epoch, Ordering::Relaxed);
+        if previous >= 0 && last_checkpoint_epoch > previous {
+            self.notify(Notification::CheckpointConfirmed {
+                subnet: self.metadata.child.id.to_string(),
+                height: last_checkpoint_epoch,
+            });
+        }
+
+        self.prune_in_flight(last_checkpoint_epoch)?;
+
         let current_height = self.child_handler.current_epoch().await?;
         let finalized_height = max(1, current_height - self.finalization_blocks);
 
@@ -149,6 +267,12 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
             return Ok(());
         }
 
+        self.check_balance(submitter).await?;
+
+        if self.defer_for_fees().await? {
+            return Ok(());
+        }
+
         let start = last_checkpoint_epoch + 1;
         tracing::debug!(
             "start querying quorum reached events from : {start} to {finalized_height}"
@@ -176,6 +300,14 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
                     continue;
                 }
 
+                if self.is_in_flight(event.height)? {
+                    tracing::debug!(
+                        "checkpoint at height {} was submitted by a previous run",
+                        event.height
+                    );
+                    continue;
+                }
+
                 let bundle = self
                     .child_handler
                     .checkpoint_bundle_at(event.height)
@@ -200,6 +332,11 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
                     .acquire_owned()
                     .await
                     .unwrap();
+                self.record_in_flight(event.height)?;
+                let store = self.store.clone();
+                let history = self.history.clone();
+                let notifier = self.notifier.clone();
+                let child = self.metadata.child.id.clone();
                 all_submit_tasks.push(tokio::task::spawn(async move {
                     let height = event.height;
                     let hash = bundle.checkpoint.block_hash.clone();
@@ -219,8 +356,38 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
                                 );
                             });
 
+                    if let Some(notifier) = notifier {
+                        notifier.notify(match &result {
+                            Ok(epoch) => Notification::CheckpointSubmitted {
+                                subnet: child.to_string(),
+                                height,
+                                parent_epoch: *epoch,
+                            },
+                            Err(e) => Notification::CheckpointFailed {
+                                subnet: child.to_string(),
+                                height,
+                                error: e.to_string(),
+                            },
+                        });
+                    }
+
+                    if let Some(history) = history {
+                        let params = BTreeMap::from([("height".to_string(), height.to_string())]);
+                        if let Err(e) = history.record("checkpoint", &child, params, &result) {
+                            tracing::warn!("cannot record checkpoint {height} in the history: {e}");
+                        }
+                    }
+
+                    // once the submission is done the parent is the source of truth, and a failed
+                    // one must be retried in the next round
+                    if let Some(store) = store {
+                        if let Err(e) = store.delete(&in_flight_key(&child, height)) {
+                            tracing::warn!("cannot clear in flight submission {height}: {e}");
+                        }
+                    }
+
                     drop(submission_permit);
-                    result
+                    result.map(|_| ())
                 }));
 
                 count += 1;
@@ -235,12 +402,130 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
         Ok(())
     }
 
+    /// Warns when the submitter's balance in the parent is below the threshold, so the checkpoint
+    /// pipeline does not stall silently once it cannot pay for gas, and fails if configured to
+    /// refuse submitting.
+    async fn check_balance(&self, submitter: Address) -> Result<()> {
+        let Some(threshold) = &self.balance_threshold else {
+            return Ok(());
+        };
+
+        let balance = self.parent_handler.balance(&submitter).await?;
+        if balance >= threshold.minimum {
+            self.low_balance.store(false, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        tracing::warn!(
+            "submitter {submitter} balance {balance} is below the minimum {}",
+            threshold.minimum
+        );
+        if !self.low_balance.swap(true, Ordering::Relaxed) {
+            self.notify(Notification::LowBalance {
+                subnet: self.metadata.child.id.to_string(),
+                address: submitter.to_string(),
+                balance: balance.to_string(),
+                minimum: threshold.minimum.to_string(),
+            });
+        }
+
+        if threshold.refuse {
+            return Err(anyhow!(
+                "submitter {submitter} balance {balance} is below the minimum {}, not submitting",
+                threshold.minimum
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether to skip this round because the parent's base fee is above the fee window. The
+    /// checkpoints skipped are all submitted in the first round the window lets through.
+    async fn defer_for_fees(&self) -> Result<bool> {
+        let Some(window) = &self.fee_window else {
+            return Ok(false);
+        };
+
+        let base_fee = self.parent_handler.base_fee().await?;
+        let mut deferred_since = self
+            .deferred_since
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let waited = deferred_since.map_or(Duration::ZERO, |since| since.elapsed());
+
+        if window.defers(&base_fee, waited) {
+            tracing::info!(
+                "parent base fee {} is above {}, holding back checkpoints for {}s",
+                base_fee.atto(),
+                window.max_base_fee.atto(),
+                waited.as_secs()
+            );
+            deferred_since.get_or_insert_with(Instant::now);
+            return Ok(true);
+        }
+
+        if deferred_since.take().is_some() && base_fee > window.max_base_fee {
+            tracing::warn!(
+                "submitting checkpoints held back for {}s at parent base fee {}",
+                waited.as_secs(),
+                base_fee.atto()
+            );
+        }
+        Ok(false)
+    }
+
+    fn notify(&self, notification: Notification) {
+        if let Some(notifier) = &self.notifier {
+            notifier.notify(notification);
+        }
+    }
+
+    /// Whether the checkpoint at `height` was submitted by a previous run recently enough that it
+    /// may still be included in the parent.
+    fn is_in_flight(&self, height: ChainEpoch) -> Result<bool> {
+        let Some(store) = &self.store else {
+            return Ok(false);
+        };
+        let key = in_flight_key(&self.metadata.child.id, height);
+        let Some(submitted_at) = get_json::<u64>(store.as_ref(), &key)? else {
+            return Ok(false);
+        };
+        Ok(unix_now().saturating_sub(submitted_at) < IN_FLIGHT_TIMEOUT.as_secs())
+    }
+
+    fn record_in_flight(&self, height: ChainEpoch) -> Result<()> {
+        match &self.store {
+            Some(store) => put_json(
+                store.as_ref(),
+                &in_flight_key(&self.metadata.child.id, height),
+                &unix_now(),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Forgets the submissions at or below the last checkpoint committed in the parent.
+    fn prune_in_flight(&self, last_checkpoint_epoch: ChainEpoch) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let prefix = in_flight_key_prefix(&self.metadata.child.id);
+        for key in store.keys(&prefix)? {
+            let committed = key[prefix.len()..]
+                .parse::<ChainEpoch>()
+                .map_or(true, |h| h <= last_checkpoint_epoch);
+            if committed {
+                store.delete(&key)?;
+            }
+        }
+        Ok(())
+    }
+
     async fn submit_checkpoint(
         parent_handler: Arc<T>,
         submitter: Address,
         bundle: BottomUpCheckpointBundle,
         event: QuorumReachedEvent,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<ChainEpoch> {
         let epoch = parent_handler
             .submit_checkpoint(
                 &submitter,
@@ -261,6 +546,42 @@ impl<T: BottomUpCheckpointRelayer + Send + Sync + 'static> BottomUpCheckpointMan
             event.height,
             epoch
         );
-        Ok(())
+        Ok(epoch)
+    }
+}
+
+fn in_flight_key_prefix(child: &SubnetID) -> String {
+    format!("relayer/{child}/in-flight/")
+}
+
+fn in_flight_key(child: &SubnetID, height: ChainEpoch) -> String {
+    format!("{}{height}", in_flight_key_prefix(child))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeeWindow;
+    use fvm_shared::econ::TokenAmount;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fee_window() {
+        let window = FeeWindow {
+            max_base_fee: TokenAmount::from_atto(100),
+            max_delay: Duration::from_secs(600),
+        };
+
+        assert!(!window.defers(&TokenAmount::from_atto(100), Duration::ZERO));
+        assert!(window.defers(&TokenAmount::from_atto(101), Duration::ZERO));
+        assert!(window.defers(&TokenAmount::from_atto(101), Duration::from_secs(599)));
+        // held back long enough, submitted whatever the fee
+        assert!(!window.defers(&TokenAmount::from_atto(101), Duration::from_secs(600)));
     }
 }
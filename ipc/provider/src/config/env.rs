@@ -0,0 +1,139 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Resolution of environment variables in the config, so secrets such as auth tokens do not have
+//! to be stored in the file.
+//!
+//! Two mechanisms are supported:
+//! * `${VAR}` references inside string values are replaced by the value of `VAR`.
+//! * Variables named `IPC_<KEY>__<KEY>...` override the value at that path, where array elements
+//!   are addressed by their index, e.g. `IPC_SUBNETS__0__CONFIG__AUTH_TOKEN`.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use toml::Value;
+
+/// The prefix of the variables that override config values.
+pub const OVERRIDE_PREFIX: &str = "IPC_";
+const PATH_SEPARATOR: &str = "__";
+/// The top-level keys of the config that can be overridden. Other `IPC_*` variables, such as the cli's own settings, are
+/// not overrides.
+const TOP_LEVEL_KEYS: [&str; 3] = ["keystore_path", "proxy", "subnets"];
+
+/// Replaces `${VAR}` references in every string of the document.
+pub(crate) fn interpolate(value: &mut Value, env: &HashMap<String, String>) -> Result<()> {
+    interpolate_at(value, env, &mut vec![])
+}
+
+fn interpolate_at(
+    value: &mut Value,
+    env: &HashMap<String, String>,
+    path: &mut Vec<String>,
+) -> Result<()> {
+    match value {
+        Value::String(s) => {
+            *s = interpolate_str(s, env).with_context(|| format!("in {}", path.join(".")))?
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                path.push(i.to_string());
+                interpolate_at(item, env, path)?;
+                path.pop();
+            }
+        }
+        Value::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                path.push(key.clone());
+                interpolate_at(item, env, path)?;
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(s: &str, env: &HashMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("unterminated variable reference in {s:?}"))?;
+        let name = &rest[start + 2..start + end];
+        let value = env
+            .get(name)
+            .ok_or_else(|| anyhow!("environment variable {name} is referenced but not set"))?;
+        out.push_str(value);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+/// Applies the `IPC_*` overrides found in `env`. The parent of each overridden value must exist;
+/// the value itself is created if missing.
+pub(crate) fn apply_overrides(value: &mut Value, env: &HashMap<String, String>) -> Result<()> {
+    let mut overrides = env
+        .iter()
+        .filter_map(|(k, v)| Some((k.strip_prefix(OVERRIDE_PREFIX)?, v)))
+        .collect::<Vec<_>>();
+    // apply in a stable order so a failure is reported the same way on every run
+    overrides.sort();
+
+    for (key, new_value) in overrides {
+        let path = key
+            .split(PATH_SEPARATOR)
+            .map(|s| s.to_lowercase())
+            .collect::<Vec<_>>();
+        if !TOP_LEVEL_KEYS.contains(&path[0].as_str()) {
+            continue;
+        }
+        set_path(value, &path, new_value)
+            .with_context(|| format!("failed to apply {OVERRIDE_PREFIX}{key}"))?;
+    }
+
+    Ok(())
+}
+
+fn set_path(root: &mut Value, path: &[String], new_value: &str) -> Result<()> {
+    let (last, parents) = path
+        .split_last()
+        .ok_or_else(|| anyhow!("empty override path"))?;
+
+    let mut current = root;
+    for segment in parents {
+        current = match current {
+            Value::Table(table) => table
+                .get_mut(segment)
+                .ok_or_else(|| anyhow!("no such key: {segment}"))?,
+            Value::Array(items) => {
+                let index: usize = segment
+                    .parse()
+                    .map_err(|_| anyhow!("expected an array index, got {segment}"))?;
+                items
+                    .get_mut(index)
+                    .ok_or_else(|| anyhow!("no element at index {index}"))?
+            }
+            _ => return Err(anyhow!("cannot descend into {segment}, it is not a table")),
+        };
+    }
+
+    let Value::Table(table) = current else {
+        return Err(anyhow!("cannot set {last}, its parent is not a table"));
+    };
+
+    // keep the type of the value being replaced, so numbers and booleans can be overridden too
+    let parsed = match table.get(last) {
+        Some(Value::Integer(_)) => Value::Integer(new_value.parse()?),
+        Some(Value::Float(_)) => Value::Float(new_value.parse()?),
+        Some(Value::Boolean(_)) => Value::Boolean(new_value.parse()?),
+        _ => Value::String(new_value.to_string()),
+    };
+    table.insert(last.clone(), parsed);
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Upgrades configs written for older versions of the provider to the current schema.
+//!
+//! Configs without a `version` key are version 0. Each migration upgrades the document by one
+//! version, and they are applied in order until [`CONFIG_VERSION`] is reached.
+
+use anyhow::{anyhow, Result};
+use toml::{Table, Value};
+
+/// The version of the config schema understood by this provider.
+pub const CONFIG_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "version";
+
+type Migration = fn(&mut Table) -> Result<()>;
+
+/// `MIGRATIONS[n]` upgrades a config from version `n` to `n + 1`.
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [v0_to_v1];
+
+/// Returns the schema version of the document.
+pub fn version_of(value: &Value) -> Result<u32> {
+    match value.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(Value::Integer(v)) => {
+            u32::try_from(*v).map_err(|_| anyhow!("invalid config version: {v}"))
+        }
+        Some(v) => Err(anyhow!("invalid config version: {v}")),
+    }
+}
+
+/// Upgrades the document in place, returning whether anything was migrated.
+pub fn migrate(value: &mut Value) -> Result<bool> {
+    let from = version_of(value)?;
+    if from > CONFIG_VERSION {
+        return Err(anyhow!(
+            "config version {from} is newer than the supported version {CONFIG_VERSION}, upgrade ipc-cli"
+        ));
+    }
+    if from == CONFIG_VERSION {
+        return Ok(false);
+    }
+
+    let table = value
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("config is not a table"))?;
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        migration(table).map_err(|e| anyhow!("failed to migrate config from v{version}: {e}"))?;
+        table.insert(VERSION_KEY.to_string(), Value::Integer(version as i64 + 1));
+    }
+
+    Ok(true)
+}
+
+/// Drops the keys left over from the agent daemon configs and moves the endpoint of single-endpoint
+/// subnets to `provider_http`.
+fn v0_to_v1(config: &mut Table) -> Result<()> {
+    let Some(subnets) = config.get_mut("subnets").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    for subnet in subnets.iter_mut().filter_map(Value::as_table_mut) {
+        subnet.remove("network_name");
+        let id = subnet
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or("<unknown>")
+            .to_string();
+
+        let Some(config) = subnet.get_mut("config").and_then(Value::as_table_mut) else {
+            continue;
+        };
+        config.remove("accounts");
+        config.remove("private_key");
+
+        if config.get("network_type").and_then(Value::as_str) == Some("fvm") {
+            return Err(anyhow!(
+                "subnet {id} uses network_type \"fvm\", which is no longer supported; point it to an FEVM endpoint with network_type = \"fevm\""
+            ));
+        }
+
+        if !config.contains_key("provider_http") {
+            if let Some(endpoint) = config.remove("jsonrpc_api_http") {
+                config.insert("provider_http".to_string(), endpoint);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate, version_of, CONFIG_VERSION};
+    use crate::config::Config;
+    use toml::Value;
+
+    const V0: &str = r#"
+    keystore_path = "~/.ipc"
+
+    [[subnets]]
+    id = "/r1234"
+    network_name = "root"
+
+    [subnets.config]
+    network_type = "fevm"
+    jsonrpc_api_http = "http://127.0.0.1:3030/rpc/v1"
+    registry_addr = "0x6be1ccf648c74800380d0520d797a170c808b624"
+    gateway_addr = "0x6be1ccf648c74800380d0520d797a170c808b624"
+    accounts = ["0x6be1ccf648c74800380d0520d797a170c808b624"]
+    "#;
+
+    #[test]
+    fn test_migrate_v0() {
+        let mut value: Value = toml::from_str(V0).unwrap();
+        assert!(migrate(&mut value).unwrap());
+        assert_eq!(version_of(&value).unwrap(), CONFIG_VERSION);
+
+        let subnet = &value["subnets"][0];
+        assert!(subnet.get("network_name").is_none());
+        assert!(subnet["config"].get("accounts").is_none());
+        assert_eq!(
+            subnet["config"]["provider_http"].as_str(),
+            Some("http://127.0.0.1:3030/rpc/v1")
+        );
+
+        // migrating again is a no-op
+        assert!(!migrate(&mut value).unwrap());
+    }
+
+    #[test]
+    fn test_migrate_file_only_on_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, V0).unwrap();
+
+        // loading migrates in memory and leaves the file, and its comments, alone
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V0);
+
+        assert_eq!(Config::migrate_file(&path).unwrap(), Some(0));
+        assert_eq!(
+            std::fs::read_to_string(path.with_extension("toml.bak")).unwrap(),
+            V0
+        );
+        assert_eq!(Config::migrate_file(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut value: Value =
+            toml::from_str(&format!("version = {}", CONFIG_VERSION + 1)).unwrap();
+        assert!(migrate(&mut value).is_err());
+    }
+
+    #[test]
+    fn test_migrate_rejects_fvm_subnets() {
+        let mut value: Value = toml::from_str(&V0.replace("\"fevm\"", "\"fvm\"")).unwrap();
+        let err = migrate(&mut value).unwrap_err();
+        assert!(err.to_string().contains("/r1234"));
+    }
+}
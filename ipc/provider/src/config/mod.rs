@@ -6,6 +6,8 @@
 //! [`Config`] struct.
 
 pub mod deserialize;
+pub mod env;
+pub mod migrate;
 pub mod subnet;
 
 pub mod serialize;
@@ -13,12 +15,13 @@ pub mod serialize;
 mod tests;
 
 use fs_err as fs;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use deserialize::deserialize_subnets_from_vec;
 use ipc_api::subnet_id::SubnetID;
+use migrate::CONFIG_VERSION;
 use serde::{Deserialize, Serialize};
 use serialize::serialize_subnets_to_str;
 pub use subnet::Subnet;
@@ -27,6 +30,7 @@ pub const JSON_RPC_VERSION: &str = "2.0";
 
 /// DefaulDEFAULT_CHAIN_IDSUBNET_e
 pub const DEFAULT_CONFIG_TEMPLATE: &str = r#"
+version = 1
 keystore_path = "~/.ipc"
 
 # Filecoin Calibration
@@ -48,35 +52,185 @@ registry_addr = "0x0b4e239FF21b40120cDa817fba77bD1B366c1bcD"
 # provider_http = "https://<RPC_ADDR>/"
 # gateway_addr = "0x77aa40b105843728088c0132e43fc44348881da8"
 # registry_addr = "0x74539671a1d2f1c8f200826baba665179f53a1b7"
+# Optional keystore used for this subnet instead of the top-level one
+# keystore_path = "~/.ipc/<SUBNET_ID>"
+# Optional proxy for this subnet's endpoint, e.g. "socks5h://127.0.0.1:9050" for Tor
+# proxy = "socks5h://127.0.0.1:9050"
+
+# Optional authentication of the endpoint, instead of auth_token. Schemes: bearer (token),
+# basic (username, password) and command (command, refresh_secs) to fetch rotating tokens
+# [subnets.config.auth]
+# scheme = "command"
+# command = "cat /run/secrets/ipc-token"
+# refresh_secs = 300
+
+# Optional TLS settings for endpoints behind mutual TLS or a private CA
+# [subnets.config.tls]
+# client_cert = "/path/to/client.pem"
+# client_key = "/path/to/client.key"
+# ca = "/path/to/ca.pem"
+
+# Optional tuning of the connections kept open to the endpoint
+# [subnets.config.pool]
+# max_idle = 8
+# idle_timeout_secs = 90
+# tcp_keepalive_secs = 60
+# max_concurrent_requests = 16
+
+# Optional cost controls for transactions sent to the subnet, fees in attoFIL
+# [subnets.config.fee_policy]
+# max_fee_per_gas = 1000000000
+# max_priority_fee_per_gas = 100000000
+# confirmations = 1
+
+# Optional m-of-n approval of subnet kill, set-federated-power and large releases
+# [approvals]
+# threshold = 2
+# approvers = ["0x...", "0x...", "0x..."]
+# min_release_fil = 1000
 "#;
 
 /// The top-level struct representing the config. Calls to [`Config::from_file`] deserialize into
 /// this struct.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
 pub struct Config {
+    /// The schema version, see [`migrate`].
+    #[serde(default)]
+    pub version: u32,
     /// Directory of the keystore that wants to be made available by the provider.
     pub keystore_path: Option<String>,
+    /// Proxy for the requests to every subnet endpoint, e.g. `socks5h://127.0.0.1:9050` to go
+    /// through Tor and resolve `.onion` endpoints. Subnets can override it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Operations that need m-of-n approval before they are executed, see [`crate::approval`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approvals: Option<ApprovalConfig>,
     #[serde(deserialize_with = "deserialize_subnets_from_vec", default)]
     #[serde(serialize_with = "serialize_subnets_to_str")]
     pub subnets: HashMap<SubnetID, Subnet>,
 }
 
+/// Requires `threshold` signatures out of `approvers` for killing subnets, setting federated
+/// power and, from `min_release_fil` whole FIL on, releasing funds.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalConfig {
+    pub threshold: usize,
+    /// The ethereum addresses of the keyholders.
+    pub approvers: Vec<ethers::types::Address>,
+    /// Releases below this amount go through without approval. All releases need approval when
+    /// not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_release_fil: Option<u64>,
+}
+
+impl ApprovalConfig {
+    /// Checks that the threshold can be met by distinct approvers and is not zero, which would
+    /// approve intents without any signature.
+    pub fn validate(&self) -> Result<()> {
+        if self.threshold == 0 || self.threshold > self.approvers.len() {
+            return Err(anyhow!(
+                "approval threshold must be between 1 and the {} approvers, not {}",
+                self.approvers.len(),
+                self.threshold
+            ));
+        }
+        let mut seen = HashSet::new();
+        if let Some(dup) = self.approvers.iter().find(|a| !seen.insert(*a)) {
+            return Err(anyhow!("approver {dup:?} is listed more than once"));
+        }
+        Ok(())
+    }
+
+    /// Whether releasing `amount` whole FIL needs approval.
+    pub fn covers_release(&self, amount: f64) -> bool {
+        self.min_release_fil
+            .map_or(true, |min| amount >= min as f64)
+    }
+}
+
 impl Config {
     /// Returns an empty config to be populated further
     pub fn new() -> Self {
         Config {
+            version: CONFIG_VERSION,
             keystore_path: None,
+            proxy: None,
+            approvals: None,
             subnets: Default::default(),
         }
     }
 
-    /// Reads a TOML configuration in the `s` string and returns a [`Config`] struct.
+    /// Reads a TOML configuration in the `s` string and returns a [`Config`] struct. Older
+    /// schema versions are migrated in memory.
     pub fn from_toml_str(s: &str) -> Result<Self> {
-        let config = toml::from_str(s)?;
+        let mut value: toml::Value = toml::from_str(s)?;
+        migrate::migrate(&mut value)?;
+        let config: Self = value.try_into()?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Reads a TOML configuration, resolving `${VAR}` references and `IPC_*` overrides (see
+    /// [`env`]) from the given environment variables.
+    pub fn from_toml_str_with_env(s: &str, env: &HashMap<String, String>) -> Result<Self> {
+        let mut value: toml::Value = toml::from_str(s)?;
+        migrate::migrate(&mut value)?;
+        Self::from_value_with_env(value, env)
+    }
+
+    fn from_value_with_env(mut value: toml::Value, env: &HashMap<String, String>) -> Result<Self> {
+        env::interpolate(&mut value, env)?;
+        env::apply_overrides(&mut value, env)?;
+        let config: Self = value.try_into()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(approvals) = &self.approvals {
+            approvals.validate().context("invalid [approvals] config")?;
+        }
+        Ok(())
+    }
+
+    /// Parses the contents of the config file at `path`. A config with an older schema version is
+    /// upgraded in memory only; the file is left as it is until [`Config::migrate_file`].
+    fn from_file_contents(contents: &str, path: &Path) -> Result<Self> {
+        let mut value: toml::Value = toml::from_str(contents)?;
+        let from = migrate::version_of(&value)?;
+
+        if migrate::migrate(&mut value)? {
+            tracing::info!(
+                "config {} uses schema v{from}, upgraded to v{CONFIG_VERSION} in memory; run `ipc-cli config migrate` to upgrade the file",
+                path.display()
+            );
+        }
+
+        Self::from_value_with_env(value, &std::env::vars().collect())
+    }
+
+    /// Upgrades the config file at `path` to the current schema version, after copying the
+    /// original to `<path>.bak`. The file is rewritten from the parsed document, so comments and
+    /// the order of keys are not preserved. Returns the version the file was migrated from, or
+    /// `None` if it was already current.
+    pub fn migrate_file(path: impl AsRef<Path>) -> Result<Option<u32>> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config from {}", path.display()))?;
+        let mut value: toml::Value = toml::from_str(&contents)?;
+        let from = migrate::version_of(&value)?;
+        if !migrate::migrate(&mut value)? {
+            return Ok(None);
+        }
+
+        fs::copy(path, path.with_extension("toml.bak"))?;
+        fs::write(path, toml::to_string(&value)?)?;
+        Ok(Some(from))
+    }
+
     /// Reads a TOML configuration file specified in the `path` and returns a [`Config`] struct.
+    /// Environment variables are resolved as in [`Config::from_toml_str_with_env`].
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let contents = fs::read_to_string(&path).with_context(|| {
             format!(
@@ -85,16 +239,17 @@ impl Config {
             )
         })?;
 
-        let config: Config =
-            Config::from_toml_str(contents.as_str()).context("failed to parse config TOML")?;
+        let config: Config = Config::from_file_contents(contents.as_str(), path.as_ref())
+            .context("failed to parse config TOML")?;
 
         Ok(config)
     }
 
     /// Reads a TOML configuration file specified in the `path` and returns a [`Config`] struct.
+    /// Environment variables are resolved as in [`Config::from_toml_str_with_env`].
     pub async fn from_file_async(path: impl AsRef<Path>) -> Result<Self> {
-        let contents = tokio::fs::read_to_string(path).await?;
-        Config::from_toml_str(contents.as_str())
+        let contents = tokio::fs::read_to_string(&path).await?;
+        Config::from_file_contents(contents.as_str(), path.as_ref())
     }
 
     pub async fn write_to_file_async(&self, path: impl AsRef<Path>) -> Result<()> {
@@ -103,6 +258,13 @@ impl Config {
         Ok(())
     }
 
+    /// Returns the config of a subnet with the top-level defaults applied.
+    pub fn subnet(&self, id: &SubnetID) -> Option<Subnet> {
+        let mut subnet = self.subnets.get(id)?.clone();
+        subnet.inherit_proxy(self.proxy.as_deref());
+        Some(subnet)
+    }
+
     pub fn add_subnet(&mut self, subnet: Subnet) {
         self.subnets.insert(subnet.id.clone(), subnet);
     }
@@ -8,7 +8,7 @@ use fvm_shared::address::{Address, Payload};
 use ipc_api::subnet_id::SubnetID;
 use ipc_types::EthAddress;
 use serde::ser::{Error, SerializeSeq};
-use serde::Serializer;
+use serde::{Serialize, Serializer};
 use std::collections::HashMap;
 
 /// A serde serialization method to serialize a hashmap of subnets with subnet id as key and
@@ -51,6 +51,16 @@ where
     s.serialize_str(&format!("0x{:?}", addr))
 }
 
+/// Serializes `subnet` as a `[[subnets]]` stanza, so it can be appended to a config file without
+/// rewriting the rest of it.
+pub fn subnet_to_toml(subnet: &Subnet) -> anyhow::Result<String> {
+    #[derive(Serialize)]
+    struct Stanza<'a> {
+        subnets: [&'a Subnet; 1],
+    }
+    Ok(toml::to_string(&Stanza { subnets: [subnet] })?)
+}
+
 fn address_to_eth_address(addr: &Address) -> anyhow::Result<EthAddress> {
     match addr.payload() {
         Payload::Delegated(inner) => {
@@ -65,6 +75,8 @@ fn address_to_eth_address(addr: &Address) -> anyhow::Result<EthAddress> {
 
 #[cfg(test)]
 mod tests {
+    use super::subnet_to_toml;
+    use crate::config::migrate::CONFIG_VERSION;
     use crate::config::subnet::{EVMSubnet, SubnetConfig};
     use crate::config::{Config, Subnet};
     use fvm_shared::address::Address;
@@ -117,10 +129,25 @@ mod tests {
         assert_eq!(from_str, config);
     }
 
+    #[test]
+    fn test_subnet_to_toml() {
+        let config = Config::from_toml_str(STR).unwrap();
+        let mut subnet = config.subnets.values().next().unwrap().clone();
+        subnet.id = SubnetID::from_str("/r1234/t01").unwrap();
+
+        let appended = format!("{STR}\n{}", subnet_to_toml(&subnet).unwrap());
+        let config = Config::from_toml_str(&appended).unwrap();
+        assert_eq!(config.subnets.len(), 2);
+        assert_eq!(config.subnets.get(&subnet.id), Some(&subnet));
+    }
+
     #[test]
     fn test_serialization() {
         let mut config = Config {
+            version: CONFIG_VERSION,
             keystore_path: Some(String::from("~/.ipc")),
+            proxy: None,
+            approvals: None,
             subnets: Default::default(),
         };
 
@@ -133,6 +160,12 @@ mod tests {
                 provider_timeout: None,
                 auth_token: None,
                 registry_addr: Address::from(eth_addr1),
+                fee_policy: None,
+                keystore_path: None,
+                proxy: None,
+                tls: None,
+                auth: None,
+                pool: None,
             }),
         };
         config.add_subnet(subnet2);
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 // Copyright 2022-2024 Protocol Labs
@@ -50,6 +51,17 @@ impl Subnet {
         }
     }
 
+    /// The authentication of the endpoint, with a plain `auth_token` read as a bearer token.
+    pub fn auth(&self) -> Option<AuthConfig> {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.auth.clone().or_else(|| {
+                s.auth_token
+                    .clone()
+                    .map(|token| AuthConfig::Bearer { token })
+            }),
+        }
+    }
+
     pub fn rpc_http(&self) -> &Url {
         match &self.config {
             SubnetConfig::Fevm(s) => &s.provider_http,
@@ -67,6 +79,47 @@ impl Subnet {
             SubnetConfig::Fevm(s) => s.gateway_addr,
         }
     }
+
+    pub fn proxy(&self) -> Option<&str> {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.proxy.as_deref(),
+        }
+    }
+
+    /// Uses `proxy` for this subnet unless it sets its own.
+    pub fn inherit_proxy(&mut self, proxy: Option<&str>) {
+        match &mut self.config {
+            SubnetConfig::Fevm(s) => {
+                if s.proxy.is_none() {
+                    s.proxy = proxy.map(String::from);
+                }
+            }
+        }
+    }
+
+    pub fn tls(&self) -> Option<&TlsConfig> {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.tls.as_ref(),
+        }
+    }
+
+    pub fn pool(&self) -> Option<&PoolConfig> {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.pool.as_ref(),
+        }
+    }
+
+    pub fn keystore_path(&self) -> Option<&str> {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.keystore_path.as_deref(),
+        }
+    }
+
+    pub fn fee_policy(&self) -> FeePolicy {
+        match &self.config {
+            SubnetConfig::Fevm(s) => s.fee_policy.clone().unwrap_or_default(),
+        }
+    }
 }
 
 /// The FVM subnet config parameters
@@ -95,4 +148,82 @@ pub struct EVMSubnet {
     #[serde(deserialize_with = "deserialize_eth_address_from_str")]
     #[serde(serialize_with = "serialize_eth_address_to_str")]
     pub gateway_addr: Address,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_policy: Option<FeePolicy>,
+
+    /// Directory of the keystore holding the keys used on this subnet, instead of the top-level
+    /// `keystore_path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore_path: Option<String>,
+
+    /// Proxy for the requests to `provider_http`, overriding the top-level `proxy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+
+    /// How requests to `provider_http` are authenticated, takes precedence over `auth_token`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<PoolConfig>,
+}
+
+/// Tuning of the connections kept open to the endpoint, for processes sending many requests.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Max number of idle connections kept open to the endpoint.
+    pub max_idle: Option<usize>,
+    /// Seconds after which an idle connection is closed.
+    pub idle_timeout_secs: Option<u64>,
+    /// Interval of the TCP keep-alive probes, in seconds.
+    pub tcp_keepalive_secs: Option<u64>,
+    /// Max number of requests in flight to the endpoint at once, across every subnet using it.
+    pub max_concurrent_requests: Option<usize>,
+}
+
+/// Authentication schemes supported for subnet endpoints.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+pub enum AuthConfig {
+    /// A static bearer token.
+    Bearer { token: String },
+    /// HTTP basic authentication.
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// A bearer token printed by a shell command, re-run once the token is older than
+    /// `refresh_secs`, so rotated tokens are picked up without a restart.
+    Command {
+        command: String,
+        refresh_secs: Option<u64>,
+    },
+}
+
+/// TLS settings for endpoints behind mutual TLS or a private certificate authority. Paths point to
+/// PEM files.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// Certificate presented to the endpoint, must be set together with `client_key`.
+    pub client_cert: Option<PathBuf>,
+    /// PKCS#8 private key of `client_cert`.
+    pub client_key: Option<PathBuf>,
+    /// Additional root certificate trusted when verifying the endpoint.
+    pub ca: Option<PathBuf>,
+}
+
+/// Cost controls applied to the transactions the provider sends to a subnet.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeePolicy {
+    /// Upper bound of the max fee per gas, in attoFIL. Transactions are refused while the base fee
+    /// is above it.
+    pub max_fee_per_gas: Option<u64>,
+    /// Upper bound of the priority fee per gas, in attoFIL.
+    pub max_priority_fee_per_gas: Option<u64>,
+    /// The number of blocks to wait for on top of the one including a transaction.
+    pub confirmations: Option<usize>,
 }
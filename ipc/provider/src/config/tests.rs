@@ -1,5 +1,6 @@
 // Copyright 2022-2024 Protocol Labs
 // SPDX-License-Identifier: MIT
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use fvm_shared::address::Address;
@@ -8,6 +9,7 @@ use ipc_api::subnet_id::SubnetID;
 use ipc_types::EthAddress;
 use url::Url;
 
+use crate::config::subnet::{AuthConfig, FeePolicy};
 use crate::config::Config;
 
 // Arguments for the config's fields
@@ -40,6 +42,31 @@ fn check_subnets_config() {
     );
     assert_eq!(*child.rpc_http(), Url::from_str(PROVIDER_HTTP).unwrap(),);
     assert_eq!(child.auth_token().as_ref().unwrap(), CHILD_AUTH_TOKEN);
+    assert_eq!(child.fee_policy(), FeePolicy::default());
+}
+
+#[test]
+fn check_fee_policy_config() {
+    let config = Config::from_toml_str(&formatdoc!(
+        r#"
+        {}
+        [subnets.config.fee_policy]
+        max_priority_fee_per_gas = 1000
+        confirmations = 3
+        "#,
+        config_str()
+    ))
+    .unwrap();
+
+    let child = &config.subnets[&SubnetID::from_str(CHILD_ID).unwrap()];
+    assert_eq!(
+        child.fee_policy(),
+        FeePolicy {
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: Some(1000),
+            confirmations: Some(3),
+        }
+    );
 }
 
 fn config_str() -> String {
@@ -63,3 +90,120 @@ fn config_str() -> String {
 fn read_config() -> Config {
     Config::from_toml_str(config_str().as_str()).unwrap()
 }
+
+#[test]
+fn check_env_interpolation() {
+    let config_str = config_str().replace(CHILD_AUTH_TOKEN, "${TOKEN}");
+    let env = HashMap::from([("TOKEN".to_string(), "secret".to_string())]);
+
+    let config = Config::from_toml_str_with_env(&config_str, &env).unwrap();
+    let child = &config.subnets[&SubnetID::from_str(CHILD_ID).unwrap()];
+    assert_eq!(child.auth_token().unwrap(), "secret");
+
+    let err = Config::from_toml_str_with_env(&config_str, &HashMap::new()).unwrap_err();
+    assert!(format!("{err:#}").contains("TOKEN"));
+}
+
+#[test]
+fn check_env_overrides() {
+    let env = HashMap::from([
+        (
+            "IPC_SUBNETS__0__CONFIG__AUTH_TOKEN".to_string(),
+            "overridden".to_string(),
+        ),
+        ("IPC_KEYSTORE_PATH".to_string(), "/keys".to_string()),
+        // not a config key, ignored
+        ("IPC_CLI_TIMEOUT".to_string(), "10".to_string()),
+    ]);
+
+    let config = Config::from_toml_str_with_env(&config_str(), &env).unwrap();
+    let child = &config.subnets[&SubnetID::from_str(CHILD_ID).unwrap()];
+    assert_eq!(child.auth_token().unwrap(), "overridden");
+    assert_eq!(config.keystore_path.unwrap(), "/keys");
+
+    let env = HashMap::from([(
+        "IPC_SUBNETS__1__CONFIG__AUTH_TOKEN".to_string(),
+        "x".to_string(),
+    )]);
+    assert!(Config::from_toml_str_with_env(&config_str(), &env).is_err());
+}
+
+#[test]
+fn check_proxy_inherited_by_subnets() {
+    let child_id = SubnetID::from_str(CHILD_ID).unwrap();
+
+    let mut config = read_config();
+    config.proxy = Some("socks5h://127.0.0.1:9050".to_string());
+    assert_eq!(
+        config.subnet(&child_id).unwrap().proxy(),
+        Some("socks5h://127.0.0.1:9050")
+    );
+
+    let config = Config::from_toml_str(&format!(
+        "proxy = \"http://global:8080\"\n{}proxy = \"http://subnet:8080\"\n",
+        config_str()
+    ))
+    .unwrap();
+    assert_eq!(
+        config.subnet(&child_id).unwrap().proxy(),
+        Some("http://subnet:8080")
+    );
+}
+
+#[test]
+fn check_auth_config() {
+    let child_id = SubnetID::from_str(CHILD_ID).unwrap();
+
+    let config = read_config();
+    assert_eq!(
+        config.subnet(&child_id).unwrap().auth(),
+        Some(AuthConfig::Bearer {
+            token: CHILD_AUTH_TOKEN.to_string()
+        })
+    );
+
+    let config = Config::from_toml_str(&formatdoc!(
+        r#"
+        {}
+        [subnets.config.auth]
+        scheme = "basic"
+        username = "user"
+        "#,
+        config_str()
+    ))
+    .unwrap();
+    assert_eq!(
+        config.subnet(&child_id).unwrap().auth(),
+        Some(AuthConfig::Basic {
+            username: "user".to_string(),
+            password: None,
+        })
+    );
+}
+
+#[test]
+fn check_approval_config() {
+    let with_approvals = |threshold: usize, approvers: &[&str]| {
+        Config::from_toml_str(&formatdoc!(
+            r#"
+            [approvals]
+            threshold = {threshold}
+            approvers = {approvers:?}
+
+            {}
+            "#,
+            config_str()
+        ))
+    };
+    let other = "0x1be1ccf648c74800380d0520d797a170c808b624";
+
+    let config = with_approvals(2, &[ETH_ADDRESS, other]).unwrap();
+    assert_eq!(config.approvals.unwrap().threshold, 2);
+
+    // a threshold of zero approves intents without signatures
+    assert!(with_approvals(0, &[ETH_ADDRESS, other]).is_err());
+    // a threshold above the approvers can never be met
+    assert!(with_approvals(3, &[ETH_ADDRESS, other]).is_err());
+    // nor can one counting the same approver twice
+    assert!(with_approvals(2, &[ETH_ADDRESS, ETH_ADDRESS]).is_err());
+}
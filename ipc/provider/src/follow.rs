@@ -0,0 +1,25 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Following the parent of a subnet block by block, with everything the child needs from each
+//! block in a single update.
+
+use std::time::Duration;
+
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::cross::IpcEnvelope;
+use ipc_api::staking::StakingChangeRequest;
+
+/// How often the head of the parent is polled once the follower has caught up with it.
+pub const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What a final block of the parent carries for a subnet.
+#[derive(Debug, Clone)]
+pub struct ParentBlockUpdate {
+    pub height: ChainEpoch,
+    pub block_hash: Vec<u8>,
+    pub parent_block_hash: Vec<u8>,
+    /// The top-down messages, such as deposits, for the subnet.
+    pub top_down_msgs: Vec<IpcEnvelope>,
+    /// The changes to the validators of the subnet.
+    pub validator_changes: Vec<StakingChangeRequest>,
+}
@@ -0,0 +1,193 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! A record of the operations broadcast to subnets, with their parameters and outcome, so
+//! operators do not have to reconstruct them from logs.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use ipc_api::subnet_id::SubnetID;
+use serde::{Deserialize, Serialize};
+
+use crate::store::{get_json, put_json, FileStore, StateStore};
+
+/// The directory of the history, next to the config file.
+pub const HISTORY_DIR: &str = "history";
+
+const KEY_PREFIX: &str = "history/";
+
+/// Orders the entries recorded by this process within the same nanosecond.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// An operation broadcast to a subnet.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Unix timestamp of the time the operation completed.
+    pub timestamp: u64,
+    /// The kind of operation, e.g. `fund` or `checkpoint`.
+    pub operation: String,
+    /// The subnet the operation is about.
+    pub subnet: String,
+    pub params: BTreeMap<String, String>,
+    pub status: HistoryStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryStatus {
+    /// The operation was included, `result` is the epoch or the address it returned.
+    Succeeded {
+        result: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// Which entries to list, all of them by default.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub subnet: Option<SubnetID>,
+    pub operation: Option<String>,
+    /// Only entries at or after this unix timestamp.
+    pub since: Option<u64>,
+    /// Only entries at or before this unix timestamp.
+    pub until: Option<u64>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        self.subnet
+            .as_ref()
+            .map_or(true, |s| s.to_string() == entry.subnet)
+            && self
+                .operation
+                .as_ref()
+                .map_or(true, |o| o == &entry.operation)
+            && self.since.map_or(true, |t| entry.timestamp >= t)
+            && self.until.map_or(true, |t| entry.timestamp <= t)
+    }
+}
+
+/// The history of operations, stored in a [`StateStore`].
+#[derive(Clone)]
+pub struct History {
+    store: Arc<dyn StateStore>,
+}
+
+impl History {
+    pub fn new(store: Arc<dyn StateStore>) -> Self {
+        Self { store }
+    }
+
+    /// Opens the history in `dir`. The directory is only created once an operation is
+    /// recorded, so commands that do not broadcast anything work with a read-only config dir.
+    pub fn open(dir: impl AsRef<Path>) -> Self {
+        Self::new(Arc::new(FileStore::open_lazy(dir)))
+    }
+
+    /// Records the outcome of `operation`.
+    pub fn record<T: ToString>(
+        &self,
+        operation: &str,
+        subnet: &SubnetID,
+        params: BTreeMap<String, String>,
+        result: &Result<T>,
+    ) -> Result<()> {
+        let status = match result {
+            Ok(r) => HistoryStatus::Succeeded {
+                result: r.to_string(),
+            },
+            Err(e) => HistoryStatus::Failed {
+                error: format!("{e:#}"),
+            },
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let entry = HistoryEntry {
+            timestamp: now.as_secs(),
+            operation: operation.to_string(),
+            subnet: subnet.to_string(),
+            params,
+            status,
+        };
+        // keys sort in the order the operations completed
+        let seq = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let key = format!("{KEY_PREFIX}{:020}-{seq:010}", now.as_nanos());
+        put_json(self.store.as_ref(), &key, &entry)
+    }
+
+    /// The entries matching `filter`, oldest first.
+    pub fn list(&self, filter: &HistoryFilter) -> Result<Vec<HistoryEntry>> {
+        let mut entries = vec![];
+        for key in self.store.keys(KEY_PREFIX)? {
+            if let Some(entry) = get_json::<HistoryEntry>(self.store.as_ref(), &key)? {
+                if filter.matches(&entry) {
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use anyhow::anyhow;
+    use ipc_api::subnet_id::SubnetID;
+
+    use super::{History, HistoryFilter, HistoryStatus};
+    use crate::store::MemoryStore;
+
+    #[test]
+    fn test_history_filter() {
+        let history = History::new(Arc::new(MemoryStore::default()));
+        let a = SubnetID::from_str("/r314159/t01").unwrap();
+        let b = SubnetID::from_str("/r314159/t02").unwrap();
+        let params = BTreeMap::from([("amount".to_string(), "10".to_string())]);
+
+        history
+            .record("fund", &a, params.clone(), &Ok(100))
+            .unwrap();
+        history
+            .record::<i64>("release", &a, params.clone(), &Err(anyhow!("reverted")))
+            .unwrap();
+        history.record("fund", &b, params, &Ok(200)).unwrap();
+
+        let all = history.list(&HistoryFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(
+            all[0].status,
+            HistoryStatus::Succeeded {
+                result: "100".to_string()
+            }
+        );
+
+        let funds = history
+            .list(&HistoryFilter {
+                operation: Some("fund".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(funds.len(), 2);
+
+        let of_a = history
+            .list(&HistoryFilter {
+                subnet: Some(a),
+                since: Some(all[0].timestamp),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(of_a.len(), 2);
+        assert!(matches!(of_a[1].status, HistoryStatus::Failed { .. }));
+    }
+}
@@ -27,6 +27,11 @@ const DEFAULT_JSON_RPC_ID: u8 = 1;
 /// Request timeout of the RPC client. It should be enough to accommodate
 /// the time required to validate transaction on-chain.
 const DEFAULT_REQ_TIMEOUT: Duration = Duration::from_secs(250);
+/// Responses larger than this are rejected, so a misbehaving endpoint cannot exhaust the memory
+/// of long-running processes.
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 64 * 1024 * 1024;
+/// How much of a response body is included in errors and logs.
+const MAX_LOGGED_BODY: usize = 1024;
 
 /// A convenience constant that represents empty params in a JSON-RPC request.
 pub const NO_PARAMS: Value = json!([]);
@@ -50,6 +55,7 @@ pub struct JsonRpcClientImpl {
     http_client: Client,
     url: Url,
     bearer_token: Option<String>,
+    max_response_size: usize,
 }
 
 impl JsonRpcClientImpl {
@@ -59,8 +65,39 @@ impl JsonRpcClientImpl {
             http_client: Client::default(),
             url,
             bearer_token: bearer_token.map(String::from),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
         }
     }
+
+    /// Sets the size in bytes above which responses are rejected.
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Reads the body of the response chunk by chunk, failing as soon as it is too large.
+    async fn read_body(&self, mut response: reqwest::Response) -> Result<Vec<u8>> {
+        if let Some(len) = response.content_length() {
+            if len > self.max_response_size as u64 {
+                return Err(anyhow!(
+                    "json rpc response of {len} bytes exceeds the limit of {} bytes",
+                    self.max_response_size
+                ));
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > self.max_response_size {
+                return Err(anyhow!(
+                    "json rpc response exceeds the limit of {} bytes",
+                    self.max_response_size
+                ));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
 }
 
 #[async_trait]
@@ -77,24 +114,8 @@ impl JsonRpcClient for JsonRpcClientImpl {
 
         let response = builder.send().await?;
 
-        let response_body = response.text().await?;
-        tracing::debug!("received raw response body: {:?}", response_body);
-
-        let value =
-            serde_json::from_str::<JsonRpcResponse<T>>(response_body.as_ref()).map_err(|e| {
-                tracing::error!("cannot parse json rpc client response: {:?}", response_body);
-                anyhow!(
-                    "cannot parse json rpc response: {:} due to {:}",
-                    response_body,
-                    e.to_string()
-                )
-            })?;
-
-        if value.id != DEFAULT_JSON_RPC_ID || value.jsonrpc != DEFAULT_JSON_RPC_VERSION {
-            return Err(anyhow!("json_rpc id or version not matching."));
-        }
-
-        Result::from(value)
+        let response_body = self.read_body(response).await?;
+        parse_response(&response_body)
     }
 
     async fn subscribe(&self, method: &str) -> Result<Receiver<Value>> {
@@ -120,6 +141,35 @@ impl JsonRpcClient for JsonRpcClientImpl {
     }
 }
 
+/// Decodes the body of a response straight into the expected type, checking its id and version.
+fn parse_response<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    tracing::debug!("received raw response body: {}", truncated(body));
+
+    let value = serde_json::from_slice::<JsonRpcResponse<T>>(body).map_err(|e| {
+        tracing::error!("cannot parse json rpc client response: {}", truncated(body));
+        anyhow!(
+            "cannot parse json rpc response: {} due to {e}",
+            truncated(body)
+        )
+    })?;
+
+    if value.id != DEFAULT_JSON_RPC_ID || value.jsonrpc != DEFAULT_JSON_RPC_VERSION {
+        return Err(anyhow!("json_rpc id or version not matching."));
+    }
+
+    Result::from(value)
+}
+
+/// The beginning of a response body, for errors and logs.
+fn truncated(body: &[u8]) -> String {
+    let s = String::from_utf8_lossy(&body[..body.len().min(MAX_LOGGED_BODY)]);
+    if body.len() > MAX_LOGGED_BODY {
+        format!("{s}... ({} bytes)", body.len())
+    } else {
+        s.into_owned()
+    }
+}
+
 /// JsonRpcResponse wraps the json rpc response.
 /// We could have encountered success or error, this struct handles the error and result and convert
 /// them into Result.
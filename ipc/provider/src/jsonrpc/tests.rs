@@ -4,7 +4,7 @@ use futures_util::StreamExt;
 use serde_json::json;
 use url::Url;
 
-use crate::jsonrpc::{JsonRpcClient, JsonRpcClientImpl, NO_PARAMS};
+use crate::jsonrpc::{parse_response, JsonRpcClient, JsonRpcClientImpl, NO_PARAMS};
 
 /// The default endpoints for public lotus node. If the urls fail in running tests, need to
 /// check these endpoints again.
@@ -56,3 +56,22 @@ async fn test_subscribe() {
         chan.next().await.unwrap();
     }
 }
+
+#[test]
+fn test_parse_response() {
+    let ok = br#"{"jsonrpc":"2.0","id":1,"result":[1,2,3]}"#;
+    assert_eq!(parse_response::<Vec<u64>>(ok).unwrap(), vec![1, 2, 3]);
+
+    let err = br#"{"jsonrpc":"2.0","id":1,"error":{"code":1,"message":"boom"}}"#;
+    assert!(parse_response::<Vec<u64>>(err).is_err());
+
+    let wrong_id = br#"{"jsonrpc":"2.0","id":2,"result":[]}"#;
+    assert!(parse_response::<Vec<u64>>(wrong_id).is_err());
+
+    // the body included in the error is truncated
+    let garbage = vec![b'x'; 10_000];
+    let e = parse_response::<Vec<u64>>(&garbage)
+        .unwrap_err()
+        .to_string();
+    assert!(e.len() < 2_000);
+}
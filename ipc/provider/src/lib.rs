@@ -6,48 +6,80 @@ use crate::manager::{GetBlockHashResult, TopDownQueryPayload};
 use anyhow::anyhow;
 use base64::Engine;
 use config::Config;
+use follow::{ParentBlockUpdate, FOLLOW_POLL_INTERVAL};
+use futures_util::Stream;
 use fvm_shared::{
     address::Address, clock::ChainEpoch, crypto::signature::SignatureType, econ::TokenAmount,
 };
+use history::History;
 use ipc_api::checkpoint::consensus::ValidatorData;
 use ipc_api::checkpoint::{BottomUpCheckpointBundle, QuorumReachedEvent};
 use ipc_api::evm::payload_to_evm_address;
+use ipc_api::quorum::QuorumCertificate;
 use ipc_api::staking::{StakingChangeRequest, ValidatorInfo};
 use ipc_api::subnet::{Asset, PermissionMode};
+use ipc_api::validator::Validator;
 use ipc_api::{
     cross::IpcEnvelope,
     subnet::{ConsensusType, ConstructParams},
     subnet_id::SubnetID,
 };
+use ipc_observability::emit;
 use ipc_wallet::{
     EthKeyAddress, EvmKeyStore, KeyStore, KeyStoreConfig, PersistentKeyStore, Wallet,
 };
+use liveness::ValidatorLiveness;
 use lotus::message::wallet::WalletKeyType;
-use manager::{EthSubnetManager, SubnetGenesisInfo, SubnetInfo, SubnetManager};
+use manager::evm::RpcRecorder;
+use manager::{
+    BootstrapStatus, Capabilities, Capability, CreatedSubnet, EthSubnetManager, FeeEstimate,
+    Operation, StakeHistoryEntry, SubnetCreation, SubnetGenesisInfo, SubnetInfo, SubnetManager,
+    TokenInfo, TxRef,
+};
+use num_traits::Zero;
+use observe::ConfigReloaded;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Borrow,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    future::Future,
     path::{Path, PathBuf},
     str::FromStr,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
 };
+use topdown::TopDownNonceGuard;
+use validator_diff::{diff_validator_set, ValidatorSetDiff};
 use zeroize::Zeroize;
 
+pub mod addressbook;
+pub mod approval;
+pub mod auth;
+pub mod builder;
 pub mod checkpoint;
 pub mod config;
+pub mod follow;
+pub mod history;
 pub mod jsonrpc;
+pub mod liveness;
 pub mod lotus;
 pub mod manager;
+pub mod notify;
 pub mod observe;
+pub mod parallel;
+pub mod route;
+pub mod store;
+pub mod topdown;
+pub mod validator_diff;
 
 const DEFAULT_REPO_PATH: &str = ".ipc";
 const DEFAULT_CONFIG_NAME: &str = "config.toml";
 
 /// The subnet manager connection that holds the subnet config and the manager instance.
+#[derive(Clone)]
 pub struct Connection {
     subnet: config::Subnet,
-    manager: Box<dyn SubnetManager + 'static>,
+    manager: Arc<dyn SubnetManager + 'static>,
 }
 
 impl Connection {
@@ -62,14 +94,41 @@ impl Connection {
     }
 }
 
+/// Returned when a provider call does not complete before the deadline set with
+/// [`IpcProvider::with_deadline`].
+#[derive(Debug, thiserror::Error)]
+#[error("operation did not complete within {0:?}")]
+pub struct DeadlineElapsed(pub Duration);
+
 #[derive(Clone)]
 pub struct IpcProvider {
     sender: Option<Address>,
-    config: Arc<Config>,
+    deadline: Option<Duration>,
+    /// Shared by all clones of the provider, so a reload is seen by every one of them.
+    config: Arc<RwLock<Arc<Config>>>,
     fvm_wallet: Option<Arc<RwLock<Wallet>>>,
     evm_keystore: Option<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>,
+    /// Keystores of the subnets that set their own `keystore_path`, opened on first use.
+    subnet_evm_keystores: SubnetKeyStores,
+    /// Managers created so far, reused so their http clients keep their connections open.
+    managers: Managers,
+    /// Where the broadcast operations are recorded, if anywhere.
+    history: Option<History>,
+    /// Captures the JSON-RPC traffic of the managers, if enabled.
+    rpc_recorder: Option<Arc<RpcRecorder>>,
+}
+
+type SubnetKeyStores = Arc<Mutex<HashMap<String, Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>>>;
+
+/// A cached manager, along with the config and the authorization it was created with, so it is
+/// replaced when either changes.
+struct CachedManager {
+    connection: Connection,
+    authorization: Option<String>,
 }
 
+type Managers = Arc<Mutex<HashMap<SubnetID, CachedManager>>>;
+
 impl IpcProvider {
     fn new(
         config: Arc<Config>,
@@ -78,9 +137,14 @@ impl IpcProvider {
     ) -> Self {
         Self {
             sender: None,
-            config,
+            deadline: None,
+            config: Arc::new(RwLock::new(config)),
             fvm_wallet: Some(fvm_wallet),
             evm_keystore: Some(evm_keystore),
+            subnet_evm_keystores: Default::default(),
+            managers: Default::default(),
+            history: None,
+            rpc_recorder: None,
         }
     }
 
@@ -114,9 +178,14 @@ impl IpcProvider {
         } else {
             Ok(Self {
                 sender: None,
-                config,
+                deadline: None,
+                config: Arc::new(RwLock::new(config)),
                 fvm_wallet: None,
                 evm_keystore: None,
+                subnet_evm_keystores: Default::default(),
+                managers: Default::default(),
+                history: None,
+                rpc_recorder: None,
             })
         }
     }
@@ -126,30 +195,63 @@ impl IpcProvider {
         Self::new_from_config(default_config_path())
     }
 
-    /// Get the connection instance for the subnet.
+    /// Get the connection instance for the subnet. Managers are cached per subnet and recreated
+    /// when the subnet config or its authorization change, e.g. after a config reload or a token
+    /// rotation.
     pub fn connection(&self, subnet: &SubnetID) -> Option<Connection> {
-        let subnets = &self.config.subnets;
-
-        match subnets.get(subnet) {
-            Some(subnet) => match &subnet.config {
-                config::subnet::SubnetConfig::Fevm(_) => {
-                    let wallet = self.evm_keystore.clone();
-                    let manager =
-                        match EthSubnetManager::from_subnet_with_wallet_store(subnet, wallet) {
-                            Ok(w) => Some(w),
-                            Err(e) => {
-                                tracing::warn!("error initializing evm manager: {e}");
-                                return None;
-                            }
-                        };
-                    Some(Connection {
-                        manager: Box::new(manager.unwrap()),
-                        subnet: subnet.clone(),
-                    })
+        let config = self.config();
+        let subnet = config.subnet(subnet)?;
+
+        let authorization = match subnet.auth().map(|a| auth::authorization(&a)).transpose() {
+            Ok(a) => a.map(|a| a.to_string()),
+            Err(e) => {
+                tracing::warn!("error resolving authorization of subnet {}: {e}", subnet.id);
+                return None;
+            }
+        };
+
+        let mut managers = self.managers.lock().unwrap();
+        if let Some(cached) = managers.get(&subnet.id) {
+            if cached.connection.subnet == subnet && cached.authorization == authorization {
+                return Some(cached.connection.clone());
+            }
+        }
+
+        let connection = match &subnet.config {
+            config::subnet::SubnetConfig::Fevm(_) => {
+                let wallet = match self.subnet_evm_keystore(&subnet) {
+                    Ok(w) => w,
+                    Err(e) => {
+                        tracing::warn!("error opening keystore of subnet {}: {e}", subnet.id);
+                        return None;
+                    }
+                };
+                let manager = match EthSubnetManager::from_subnet_with_wallet_store(&subnet, wallet)
+                {
+                    Ok(m) => match &self.rpc_recorder {
+                        Some(recorder) => m.with_rpc_recorder(recorder.clone()),
+                        None => m,
+                    },
+                    Err(e) => {
+                        tracing::warn!("error initializing evm manager: {e}");
+                        return None;
+                    }
+                };
+                Connection {
+                    manager: Arc::new(manager),
+                    subnet: subnet.clone(),
                 }
+            }
+        };
+
+        managers.insert(
+            subnet.id.clone(),
+            CachedManager {
+                connection: connection.clone(),
+                authorization,
             },
-            None => None,
-        }
+        );
+        Some(connection)
     }
 
     /// Get the connection of a subnet, or return an error.
@@ -157,7 +259,7 @@ impl IpcProvider {
         match self.connection(subnet) {
             None => Err(anyhow!(
                 "subnet not found: {subnet}; known subnets: {:?}",
-                self.config
+                self.config()
                     .subnets
                     .keys()
                     .map(|id| id.to_string())
@@ -172,6 +274,57 @@ impl IpcProvider {
         self.sender = Some(from);
     }
 
+    /// Bound every subsequent call to a subnet manager to `deadline`. Calls that exceed it are
+    /// cancelled and fail with [`DeadlineElapsed`].
+    pub fn with_deadline(&mut self, deadline: Duration) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Record the operations broadcast by the provider in `history`.
+    pub fn with_history(&mut self, history: History) {
+        self.history = Some(history);
+    }
+
+    /// Write every JSON-RPC request and response exchanged with the subnets to `dir`, with
+    /// credentials redacted.
+    pub fn with_rpc_recording(&mut self, dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.rpc_recorder = Some(Arc::new(RpcRecorder::new(dir)?));
+        // managers created before recording was enabled would bypass it
+        self.managers.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// Records the outcome of `operation` in the history, if the provider has one.
+    fn record<T: ToString>(
+        &self,
+        operation: &str,
+        subnet: &SubnetID,
+        params: BTreeMap<String, String>,
+        result: &anyhow::Result<T>,
+    ) {
+        if let Some(history) = &self.history {
+            if let Err(e) = history.record(operation, subnet, params, result) {
+                tracing::warn!("cannot record {operation} in the history: {e}");
+            }
+        }
+    }
+
+    /// Drives `fut` to completion, or drops it once the provider deadline elapses.
+    ///
+    /// Dropping a manager future is safe: the worst case is a transaction that was already
+    /// broadcast but whose receipt we stopped waiting for, which the caller can look up later.
+    async fn within_deadline<T>(
+        &self,
+        fut: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        match self.deadline {
+            None => fut.await,
+            Some(deadline) => tokio::time::timeout(deadline, fut)
+                .await
+                .map_err(|_| anyhow::Error::new(DeadlineElapsed(deadline)))?,
+        }
+    }
+
     /// Returns the evm wallet if it is configured, and throws an error if no wallet configured.
     ///
     /// This method should be used when we want the wallet retrieval to throw an error
@@ -184,6 +337,33 @@ impl IpcProvider {
         }
     }
 
+    /// Returns the evm wallet used for `subnet`: its own keystore if the subnet config sets
+    /// `keystore_path`, the provider's one otherwise.
+    pub fn evm_wallet_for(
+        &self,
+        subnet: &config::Subnet,
+    ) -> anyhow::Result<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>> {
+        self.subnet_evm_keystore(subnet)?
+            .ok_or_else(|| anyhow!("No evm wallet found in provider"))
+    }
+
+    fn subnet_evm_keystore(
+        &self,
+        subnet: &config::Subnet,
+    ) -> anyhow::Result<Option<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>> {
+        let Some(path) = subnet.keystore_path() else {
+            return Ok(self.evm_keystore.clone());
+        };
+
+        let mut keystores = self.subnet_evm_keystores.lock().unwrap();
+        if let Some(keystore) = keystores.get(path) {
+            return Ok(Some(keystore.clone()));
+        }
+        let keystore = Arc::new(RwLock::new(new_evm_keystore_from_path(path)?));
+        keystores.insert(path.to_string(), keystore.clone());
+        Ok(Some(keystore))
+    }
+
     // FIXME: Reconcile these into a single wallet method that
     // accepts an `ipc_wallet::WalletType` as an input.
     pub fn fvm_wallet(&self) -> anyhow::Result<Arc<RwLock<Wallet>>> {
@@ -214,7 +394,7 @@ impl IpcProvider {
         match &subnet.config {
             config::subnet::SubnetConfig::Fevm(_) => {
                 if self.sender.is_none() {
-                    let wallet = self.evm_wallet()?;
+                    let wallet = self.evm_wallet_for(subnet)?;
                     let addr = match wallet.write().unwrap().get_default()? {
                         None => return Err(anyhow!("no default evm account configured")),
                         Some(addr) => Address::try_from(addr)?,
@@ -228,9 +408,94 @@ impl IpcProvider {
         Err(anyhow!("error fetching a valid sender"))
     }
 
+    /// Returns the address that signs the messages sent to `subnet`: `from` if set, the
+    /// default sender otherwise.
+    pub fn resolve_sender(
+        &mut self,
+        subnet: &SubnetID,
+        from: Option<Address>,
+    ) -> anyhow::Result<Address> {
+        let conn = self.get_connection(subnet)?;
+        self.check_sender(conn.subnet(), from)
+    }
+
+    /// Returns the uncompressed secp256k1 public key of an address in the evm keystore used for
+    /// `subnet`.
+    fn evm_public_key(&self, subnet: &config::Subnet, addr: &Address) -> anyhow::Result<[u8; 65]> {
+        let addr = payload_to_evm_address(addr.payload())?;
+        let keystore = self.evm_wallet_for(subnet)?;
+        let key_info = keystore
+            .read()
+            .unwrap()
+            .get(&addr.into())?
+            .ok_or_else(|| anyhow!("key does not exists"))?;
+        let sk = libsecp256k1::SecretKey::parse_slice(key_info.private_key())?;
+        Ok(libsecp256k1::PublicKey::from_secret_key(&sk).serialize())
+    }
+
     /// Lists available subnet connections
     pub fn list_connections(&self) -> HashMap<SubnetID, config::Subnet> {
-        self.config.subnets.clone()
+        self.config().subnets.clone()
+    }
+
+    /// The config currently in use. Connections are created from it on every call, so a reloaded
+    /// config applies from the next call on.
+    pub fn config(&self) -> Arc<Config> {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replaces the config of this provider and all its clones.
+    pub fn reload_config(&self, config: Config) {
+        *self.config.write().unwrap() = Arc::new(config);
+        // drop the managers of subnets that were removed, changed ones are replaced on use
+        self.managers.lock().unwrap().clear();
+        emit(ConfigReloaded {
+            subnets: self.config().subnets.len(),
+        });
+    }
+
+    /// Polls the modification time of the config file at `path` every `interval` and reloads the
+    /// config when it changes. A config that fails to load is logged and the previous one is kept.
+    pub fn watch_config(
+        &self,
+        path: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let provider = self.clone();
+        let path = path.into();
+
+        tokio::spawn(async move {
+            let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            let mut last = modified(&path);
+
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let current = modified(&path);
+                if current == last {
+                    continue;
+                }
+                last = current;
+
+                match Config::from_file_async(&path).await {
+                    Ok(config) => {
+                        tracing::info!("reloaded config from {}", path.display());
+                        provider.reload_config(config);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "failed to reload config from {}, keeping the previous one: {e:#}",
+                            path.display()
+                        );
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns the optional operations supported by the backend configured for `subnet`.
+    pub fn capabilities(&self, subnet: &SubnetID) -> anyhow::Result<Capabilities> {
+        Ok(self.get_connection(subnet)?.manager().capabilities())
     }
 }
 
@@ -258,7 +523,7 @@ impl IpcProvider {
         collateral_source: Asset,
         validator_gater: Address,
         validator_rewarder: Address,
-    ) -> anyhow::Result<Address> {
+    ) -> anyhow::Result<CreatedSubnet> {
         let conn = self.get_connection(&parent)?;
 
         let subnet_config = conn.subnet();
@@ -279,9 +544,67 @@ impl IpcProvider {
             validator_gater,
             validator_rewarder,
         };
+        constructor_params.validate()?;
+
+        let params = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            ("min_validators".to_string(), min_validators.to_string()),
+            (
+                "min_validator_stake".to_string(),
+                min_validator_stake.to_string(),
+            ),
+            (
+                "bottomup_check_period".to_string(),
+                bottomup_check_period.to_string(),
+            ),
+        ]);
+        let parent = constructor_params.parent.clone();
+        let result = self
+            .within_deadline(conn.manager().create_subnet(sender, constructor_params))
+            .await;
+        let recorded = result
+            .as_ref()
+            .map(|created| created.address)
+            .map_err(|e| anyhow!("{e}"));
+        self.record("create", &parent, params, &recorded);
+        result
+    }
 
-        conn.manager()
-            .create_subnet(sender, constructor_params)
+    /// Sends the creation of a subnet with `params` and returns the transaction hash without
+    /// waiting for it to be included, see [`IpcProvider::create_subnet_status`].
+    pub async fn submit_create_subnet(
+        &mut self,
+        from: Option<Address>,
+        params: ConstructParams,
+    ) -> anyhow::Result<[u8; 32]> {
+        params.validate()?;
+        let conn = self.get_connection(&params.parent)?;
+        let sender = self.check_sender(conn.subnet(), from)?;
+
+        let parent = params.parent.clone();
+        let history = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            (
+                "min_validators".to_string(),
+                params.min_validators.to_string(),
+            ),
+        ]);
+        let result = self
+            .within_deadline(conn.manager().submit_create_subnet(sender, params))
+            .await;
+        let recorded = result.as_ref().map(hex::encode).map_err(|e| anyhow!("{e}"));
+        self.record("submit-create", &parent, history, &recorded);
+        result
+    }
+
+    /// Where the subnet creation sent in `tx_hash` to `parent` stands.
+    pub async fn create_subnet_status(
+        &self,
+        parent: &SubnetID,
+        tx_hash: &[u8; 32],
+    ) -> anyhow::Result<SubnetCreation> {
+        let conn = self.get_connection(parent)?;
+        self.within_deadline(conn.manager().create_subnet_status(tx_hash))
             .await
     }
 
@@ -296,20 +619,100 @@ impl IpcProvider {
 
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
-        let addr = payload_to_evm_address(sender.payload())?;
-        let keystore = self.evm_wallet()?;
-        let key_info = keystore
-            .read()
-            .unwrap()
-            .get(&addr.into())?
-            .ok_or_else(|| anyhow!("key does not exists"))?;
-        let sk = libsecp256k1::SecretKey::parse_slice(key_info.private_key())?;
-        let public_key = libsecp256k1::PublicKey::from_secret_key(&sk).serialize();
+        let public_key = self.evm_public_key(conn.subnet(), &sender)?;
+        self.within_deadline(check_join(conn.manager(), &subnet, &sender, &collateral))
+            .await?;
         let hex_public_key = hex::encode(public_key);
         log::info!("joining subnet with public key: {hex_public_key:?}");
 
-        conn.manager()
-            .join_subnet(subnet, sender, collateral, public_key.into())
+        let params = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            ("collateral".to_string(), collateral.to_string()),
+        ]);
+        let result = self
+            .within_deadline(conn.manager().join_subnet(
+                subnet.clone(),
+                sender,
+                collateral,
+                public_key.into(),
+            ))
+            .await;
+        self.record("join", &subnet, params, &result);
+        result
+    }
+
+    /// Previews the cost of creating a subnet with `params` without sending the transaction.
+    pub async fn estimate_create_subnet(
+        &mut self,
+        from: Option<Address>,
+        params: ConstructParams,
+    ) -> anyhow::Result<FeeEstimate> {
+        params.validate()?;
+        let conn = self.get_connection(&params.parent)?;
+        let sender = self.check_sender(conn.subnet(), from)?;
+
+        self.within_deadline(
+            conn.manager()
+                .estimate(sender, Operation::CreateSubnet(params)),
+        )
+        .await
+    }
+
+    /// Previews the cost of [`IpcProvider::join_subnet`] without sending the transaction.
+    pub async fn estimate_join_subnet(
+        &mut self,
+        subnet: SubnetID,
+        from: Option<Address>,
+        collateral: TokenAmount,
+    ) -> anyhow::Result<FeeEstimate> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        let sender = self.check_sender(conn.subnet(), from)?;
+        let public_key = self.evm_public_key(conn.subnet(), &sender)?;
+
+        let operation = Operation::Join {
+            subnet,
+            collateral,
+            public_key: public_key.into(),
+        };
+        self.within_deadline(conn.manager().estimate(sender, operation))
+            .await
+    }
+
+    /// Previews the cost of [`IpcProvider::stake`] without sending the transaction.
+    pub async fn estimate_stake(
+        &mut self,
+        subnet: SubnetID,
+        from: Option<Address>,
+        collateral: TokenAmount,
+    ) -> anyhow::Result<FeeEstimate> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        let sender = self.check_sender(conn.subnet(), from)?;
+
+        let operation = Operation::Stake { subnet, collateral };
+        self.within_deadline(conn.manager().estimate(sender, operation))
+            .await
+    }
+
+    /// Previews the cost of [`IpcProvider::fund`] without sending the transaction.
+    pub async fn estimate_fund(
+        &mut self,
+        subnet: SubnetID,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: TokenAmount,
+    ) -> anyhow::Result<FeeEstimate> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        let sender = self.check_sender(conn.subnet(), from)?;
+
+        let operation = Operation::Fund {
+            subnet,
+            to: to.unwrap_or(sender),
+            amount,
+        };
+        self.within_deadline(conn.manager().estimate(sender, operation))
             .await
     }
 
@@ -324,7 +727,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().pre_fund(subnet, sender, balance).await
+        self.within_deadline(conn.manager().pre_fund(subnet, sender, balance))
+            .await
     }
 
     pub async fn pre_release(
@@ -339,7 +743,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().pre_release(subnet, sender, amount).await
+        self.within_deadline(conn.manager().pre_release(subnet, sender, amount))
+            .await
     }
 
     pub async fn stake(
@@ -354,7 +759,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().stake(subnet, sender, collateral).await
+        self.within_deadline(conn.manager().stake(subnet, sender, collateral))
+            .await
     }
 
     pub async fn unstake(
@@ -369,7 +775,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().unstake(subnet, sender, collateral).await
+        self.within_deadline(conn.manager().unstake(subnet, sender, collateral))
+            .await
     }
 
     pub async fn leave_subnet(
@@ -383,7 +790,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().leave_subnet(subnet, sender).await
+        self.within_deadline(conn.manager().leave_subnet(subnet, sender))
+            .await
     }
 
     pub async fn claim_collateral(
@@ -397,7 +805,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().claim_collateral(subnet, sender).await
+        self.within_deadline(conn.manager().claim_collateral(subnet, sender))
+            .await
     }
 
     pub async fn kill_subnet(
@@ -411,7 +820,8 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().kill_subnet(subnet, sender).await
+        self.within_deadline(conn.manager().kill_subnet(subnet, sender))
+            .await
     }
 
     pub async fn list_child_subnets(
@@ -428,7 +838,18 @@ impl IpcProvider {
             Some(addr) => addr,
         };
 
-        conn.manager().list_child_subnets(gateway_addr).await
+        self.within_deadline(conn.manager().list_child_subnets(gateway_addr))
+            .await
+    }
+
+    /// Lists the cross-net messages waiting in the postbox of `subnet` to be propagated.
+    pub async fn list_postbox(
+        &self,
+        subnet: &SubnetID,
+    ) -> anyhow::Result<Vec<([u8; 32], IpcEnvelope)>> {
+        let conn = self.get_connection(subnet)?;
+
+        self.within_deadline(conn.manager().list_postbox()).await
     }
 
     /// Funds an account in a child subnet, if `to` is `None`, the self account
@@ -452,9 +873,99 @@ impl IpcProvider {
             Some(addr) => addr,
         };
 
-        conn.manager()
-            .fund(subnet, gateway_addr, sender, to.unwrap_or(sender), amount)
-            .await
+        let to = to.unwrap_or(sender);
+        let params = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            ("to".to_string(), to.to_string()),
+            ("amount".to_string(), amount.to_string()),
+        ]);
+        let result = self
+            .within_deadline(
+                conn.manager()
+                    .fund(subnet.clone(), gateway_addr, sender, to, amount),
+            )
+            .await;
+        self.record("fund", &subnet, params, &result);
+        result
+    }
+
+    /// Funds an account in `destination`, any number of levels below `source`, one hop at a
+    /// time. The intermediate hops fund the sender itself, and each waits for the deposit to be
+    /// executed in the intermediate subnet before funding the next one, so the sender needs
+    /// enough balance in the intermediate subnets to pay for gas.
+    pub async fn fund_route(
+        &mut self,
+        source: &SubnetID,
+        destination: &SubnetID,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: TokenAmount,
+        mut progress: impl FnMut(route::FundProgress),
+    ) -> anyhow::Result<ChainEpoch> {
+        let hops = route::plan_fund_route(source, destination)?;
+        let sender = {
+            let conn = self.get_connection(source)?;
+            self.check_sender(conn.subnet(), from)?
+        };
+        let to = to.unwrap_or(sender);
+
+        let (last, intermediate) = hops.split_last().expect("a route has at least one hop");
+        for subnet in intermediate {
+            let before = self.wallet_balance(subnet, &sender).await?;
+            let epoch = self
+                .fund(
+                    subnet.clone(),
+                    None,
+                    Some(sender),
+                    Some(sender),
+                    amount.clone(),
+                )
+                .await?;
+            progress(route::FundProgress::Funded {
+                subnet: subnet.clone(),
+                epoch,
+            });
+
+            progress(route::FundProgress::Waiting {
+                subnet: subnet.clone(),
+            });
+            self.wait_for_deposit(subnet, &sender, &(before + &amount))
+                .await?;
+            progress(route::FundProgress::Arrived {
+                subnet: subnet.clone(),
+            });
+        }
+
+        let epoch = self
+            .fund(last.clone(), None, Some(sender), Some(to), amount)
+            .await?;
+        progress(route::FundProgress::Funded {
+            subnet: last.clone(),
+            epoch,
+        });
+        Ok(epoch)
+    }
+
+    /// Polls the balance of `address` in `subnet` until it reaches `expected`.
+    async fn wait_for_deposit(
+        &self,
+        subnet: &SubnetID,
+        address: &Address,
+        expected: &TokenAmount,
+    ) -> anyhow::Result<()> {
+        let started = std::time::Instant::now();
+        loop {
+            if &self.wallet_balance(subnet, address).await? >= expected {
+                return Ok(());
+            }
+            if started.elapsed() > route::ARRIVAL_TIMEOUT {
+                return Err(anyhow!(
+                    "deposit did not arrive in {subnet} within {:?}",
+                    route::ARRIVAL_TIMEOUT
+                ));
+            }
+            tokio::time::sleep(route::ARRIVAL_POLL_INTERVAL).await;
+        }
     }
 
     /// Funds an account in a child subnet with erc20 token, provided that the supply source kind is
@@ -470,11 +981,49 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
+        conn.manager()
+            .capabilities()
+            .ensure(Capability::FundWithToken, &subnet)?;
+
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager()
-            .fund_with_token(subnet, sender, to.unwrap_or(sender), amount)
+        let to = to.unwrap_or(sender);
+        let params = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            ("to".to_string(), to.to_string()),
+            ("amount".to_string(), amount.to_string()),
+            ("token".to_string(), "true".to_string()),
+        ]);
+        let result = self
+            .within_deadline(
+                conn.manager()
+                    .fund_with_token(subnet.clone(), sender, to, amount),
+            )
+            .await;
+        self.record("fund", &subnet, params, &result);
+        result
+    }
+
+    /// The metadata of the ERC20 supply source of `subnet`, read from its parent.
+    pub async fn supply_token_info(&self, subnet: &SubnetID) -> anyhow::Result<TokenInfo> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+
+        self.within_deadline(conn.manager().get_supply_token_info(subnet))
+            .await
+    }
+
+    /// The balance of `address` in the ERC20 supply source of `subnet`, on its parent.
+    pub async fn supply_token_balance(
+        &self,
+        subnet: &SubnetID,
+        address: &Address,
+    ) -> anyhow::Result<TokenAmount> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+
+        self.within_deadline(conn.manager().supply_token_balance(subnet, address))
             .await
     }
 
@@ -492,11 +1041,15 @@ impl IpcProvider {
             None => return Err(anyhow!("target parent subnet not found")),
             Some(conn) => conn,
         };
+        conn.manager()
+            .capabilities()
+            .ensure(Capability::ApproveToken, &subnet)?;
 
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager().approve_token(subnet, sender, amount).await
+        self.within_deadline(conn.manager().approve_token(subnet, sender, amount))
+            .await
     }
 
     /// Release to an account in a child subnet, if `to` is `None`, the self account
@@ -522,9 +1075,17 @@ impl IpcProvider {
             Some(addr) => addr,
         };
 
-        conn.manager()
-            .release(gateway_addr, sender, to.unwrap_or(sender), amount)
-            .await
+        let to = to.unwrap_or(sender);
+        let params = BTreeMap::from([
+            ("from".to_string(), sender.to_string()),
+            ("to".to_string(), to.to_string()),
+            ("amount".to_string(), amount.to_string()),
+        ]);
+        let result = self
+            .within_deadline(conn.manager().release(gateway_addr, sender, to, amount))
+            .await;
+        self.record("release", &subnet, params, &result);
+        result
     }
 
     /// Send value between two addresses in a subnet
@@ -553,7 +1114,8 @@ impl IpcProvider {
         //     }
         // };
 
-        conn.manager().send_value(sender, to, amount).await
+        self.within_deadline(conn.manager().send_value(sender, to, amount))
+            .await
     }
 
     /// Get the balance of an address
@@ -564,20 +1126,23 @@ impl IpcProvider {
     ) -> anyhow::Result<TokenAmount> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().wallet_balance(address).await
+        self.within_deadline(conn.manager().wallet_balance(address))
+            .await
     }
 
     pub async fn chain_head(&self, subnet: &SubnetID) -> anyhow::Result<ChainEpoch> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().chain_head_height().await
+        self.within_deadline(conn.manager().chain_head_height())
+            .await
     }
 
     /// Obtain the genesis epoch of the input subnet.
     pub async fn genesis_epoch(&self, subnet: &SubnetID) -> anyhow::Result<ChainEpoch> {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
-        conn.manager().genesis_epoch(subnet).await
+        self.within_deadline(conn.manager().genesis_epoch(subnet))
+            .await
     }
 
     /// Get the validator information.
@@ -589,7 +1154,8 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager().get_validator_info(subnet, validator).await
+        self.within_deadline(conn.manager().get_validator_info(subnet, validator))
+            .await
     }
 
     pub async fn list_validators(
@@ -599,7 +1165,31 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager().list_validators(subnet).await
+        self.within_deadline(conn.manager().list_validators(subnet))
+            .await
+    }
+
+    /// The validators that joined, left, or changed their stake in `subnet` between heights `from`
+    /// and `to` of its parent.
+    pub async fn diff_validator_set(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> anyhow::Result<ValidatorSetDiff> {
+        if from > to {
+            return Err(anyhow!("height {from} is after height {to}"));
+        }
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+
+        let before = self
+            .within_deadline(conn.manager().list_validators_at(subnet, from))
+            .await?;
+        let after = self
+            .within_deadline(conn.manager().list_validators_at(subnet, to))
+            .await?;
+        Ok(diff_validator_set(from, &before, to, &after))
     }
 
     /// Get the changes in subnet validators. This is fetched from parent.
@@ -611,7 +1201,80 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager().get_validator_changeset(subnet, epoch).await
+        self.within_deadline(conn.manager().get_validator_changeset(subnet, epoch))
+            .await
+    }
+
+    /// Follows the parent of `subnet` from `from_height`, yielding one update per block once it
+    /// is `finalization_blocks` behind the head, with its hashes, top-down messages and validator
+    /// changes. A failed query is yielded as an error and retried from the same height on the
+    /// next poll, so the stream never skips a block.
+    pub fn follow_parent(
+        &self,
+        subnet: &SubnetID,
+        from_height: ChainEpoch,
+        finalization_blocks: ChainEpoch,
+    ) -> impl Stream<Item = anyhow::Result<ParentBlockUpdate>> + Send + 'static {
+        let state = (
+            self.clone(),
+            subnet.clone(),
+            from_height,
+            false,
+            TopDownNonceGuard::default(),
+        );
+        futures_util::stream::unfold(
+            state,
+            move |(provider, subnet, height, wait, mut guard)| async move {
+                if wait {
+                    tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+                }
+                loop {
+                    let update = provider
+                        .parent_block_update(&subnet, height, finalization_blocks)
+                        .await
+                        .and_then(|update| {
+                            update
+                                .map(|mut update| {
+                                    // a message already yielded for an earlier height is dropped
+                                    update.top_down_msgs = guard.admit(update.top_down_msgs)?;
+                                    Ok(update)
+                                })
+                                .transpose()
+                        });
+                    match update {
+                        Ok(None) => tokio::time::sleep(FOLLOW_POLL_INTERVAL).await,
+                        Ok(Some(update)) => {
+                            return Some((Ok(update), (provider, subnet, height + 1, false, guard)))
+                        }
+                        Err(e) => return Some((Err(e), (provider, subnet, height, true, guard))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// The update of the parent block at `height`, or `None` if it is not final yet.
+    async fn parent_block_update(
+        &self,
+        subnet: &SubnetID,
+        height: ChainEpoch,
+        finalization_blocks: ChainEpoch,
+    ) -> anyhow::Result<Option<ParentBlockUpdate>> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        if height > self.chain_head(&parent).await? - finalization_blocks {
+            return Ok(None);
+        }
+
+        let hashes = self.get_block_hash(&parent, height).await?;
+        let msgs = self.get_top_down_msgs(subnet, height).await?;
+        let changes = self.get_validator_changeset(subnet, height).await?;
+        Ok(Some(ParentBlockUpdate {
+            height,
+            block_hash: hashes.block_hash,
+            parent_block_hash: hashes.parent_block_hash,
+            top_down_msgs: msgs.value,
+            validator_changes: changes.value,
+        }))
     }
 
     /// Get genesis info for a child subnet. This can be used to deterministically
@@ -619,7 +1282,16 @@ impl IpcProvider {
     pub async fn get_genesis_info(&self, subnet: &SubnetID) -> anyhow::Result<SubnetGenesisInfo> {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
-        conn.manager().get_genesis_info(subnet).await
+        self.within_deadline(conn.manager().get_genesis_info(subnet))
+            .await
+    }
+
+    /// Get the progress of a child subnet towards bootstrapping.
+    pub async fn bootstrap_status(&self, subnet: &SubnetID) -> anyhow::Result<BootstrapStatus> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        self.within_deadline(conn.manager().bootstrap_status(subnet))
+            .await
     }
 
     pub async fn get_top_down_msgs(
@@ -630,7 +1302,8 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager().get_top_down_msgs(subnet, epoch).await
+        self.within_deadline(conn.manager().get_top_down_msgs(subnet, epoch))
+            .await
     }
 
     pub async fn get_block_hash(
@@ -640,25 +1313,27 @@ impl IpcProvider {
     ) -> anyhow::Result<GetBlockHashResult> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().get_block_hash(height).await
+        self.within_deadline(conn.manager().get_block_hash(height))
+            .await
     }
 
     pub async fn get_chain_id(&self, subnet: &SubnetID) -> anyhow::Result<String> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().get_chain_id().await
+        self.within_deadline(conn.manager().get_chain_id()).await
     }
 
     pub async fn get_commit_sha(&self, subnet: &SubnetID) -> anyhow::Result<[u8; 32]> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().get_commit_sha().await
+        self.within_deadline(conn.manager().get_commit_sha()).await
     }
 
     pub async fn get_chain_head_height(&self, subnet: &SubnetID) -> anyhow::Result<ChainEpoch> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().chain_head_height().await
+        self.within_deadline(conn.manager().chain_head_height())
+            .await
     }
 
     pub async fn get_bottom_up_bundle(
@@ -671,7 +1346,8 @@ impl IpcProvider {
             Some(conn) => conn,
         };
 
-        conn.manager().checkpoint_bundle_at(height).await
+        self.within_deadline(conn.manager().checkpoint_bundle_at(height))
+            .await
     }
 
     pub async fn last_bottom_up_checkpoint_height(
@@ -681,8 +1357,16 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager()
-            .last_bottom_up_checkpoint_height(subnet)
+        self.within_deadline(conn.manager().last_bottom_up_checkpoint_height(subnet))
+            .await
+    }
+
+    /// The number of child blocks between two bottom-up checkpoints of `subnet`.
+    pub async fn checkpoint_period(&self, subnet: &SubnetID) -> anyhow::Result<ChainEpoch> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+
+        self.within_deadline(conn.manager().checkpoint_period(subnet))
             .await
     }
 
@@ -693,7 +1377,83 @@ impl IpcProvider {
     ) -> anyhow::Result<Vec<QuorumReachedEvent>> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().quorum_reached_events(height).await
+        self.within_deadline(conn.manager().quorum_reached_events(height))
+            .await
+    }
+
+    /// Assembles the quorum certificate of the bottom-up checkpoint of `subnet` at `height` from
+    /// the signature bundle and the quorum reached event in the subnet. Returns `None` if there is
+    /// no checkpoint at that height or it has not reached quorum.
+    pub async fn quorum_certificate(
+        &self,
+        subnet: &SubnetID,
+        height: ChainEpoch,
+    ) -> anyhow::Result<Option<QuorumCertificate>> {
+        let Some(bundle) = self.get_bottom_up_bundle(subnet, height).await? else {
+            return Ok(None);
+        };
+        let Some(event) = self
+            .quorum_reached_events(subnet, height)
+            .await?
+            .into_iter()
+            .find(|e| e.height == height)
+        else {
+            return Ok(None);
+        };
+        QuorumCertificate::new(event, bundle).map(Some)
+    }
+
+    /// The current membership of `subnet`, the power table its checkpoints are signed against.
+    pub async fn current_membership(&self, subnet: &SubnetID) -> anyhow::Result<Vec<Validator>> {
+        let conn = self.get_connection(subnet)?;
+
+        self.within_deadline(conn.manager().current_membership())
+            .await
+    }
+
+    /// The power table of `subnet` at `height`, i.e. the validators and weights the checkpoint
+    /// of that height had to be signed by. The endpoint of the subnet must serve historical state.
+    pub async fn get_power_table_at(
+        &self,
+        subnet: &SubnetID,
+        height: ChainEpoch,
+    ) -> anyhow::Result<Vec<Validator>> {
+        let conn = self.get_connection(subnet)?;
+
+        self.within_deadline(conn.manager().power_table_at(height))
+            .await
+    }
+
+    /// The liveness of the current validators of `subnet` over its last `checkpoints` bottom-up
+    /// checkpoints, judged by who signed each of them.
+    pub async fn validator_liveness(
+        &self,
+        subnet: &SubnetID,
+        checkpoints: usize,
+    ) -> anyhow::Result<Vec<ValidatorLiveness>> {
+        let period = self.checkpoint_period(subnet).await?;
+        if period <= 0 {
+            return Err(anyhow!("invalid checkpoint period {period} for {subnet}"));
+        }
+        let last = self.last_bottom_up_checkpoint_height(subnet).await?;
+
+        let mut signatories = Vec::with_capacity(checkpoints);
+        let first = (last - period * (checkpoints as ChainEpoch - 1)).max(period);
+        for height in (first..=last).step_by(period as usize) {
+            let signed = match self.get_bottom_up_bundle(subnet, height).await? {
+                Some(bundle) => bundle.signatories,
+                None => vec![],
+            };
+            signatories.push((height, signed));
+        }
+
+        let validators = self
+            .current_membership(subnet)
+            .await?
+            .into_iter()
+            .map(|v| v.addr)
+            .collect::<Vec<_>>();
+        Ok(liveness::validator_liveness(&validators, &signatories))
     }
 
     /// Advertises the endpoint of a bootstrap node for the subnet.
@@ -709,8 +1469,7 @@ impl IpcProvider {
         let subnet_config = conn.subnet();
         let sender = self.check_sender(subnet_config, from)?;
 
-        conn.manager()
-            .add_bootstrap(subnet, &sender, endpoint)
+        self.within_deadline(conn.manager().add_bootstrap(subnet, &sender, endpoint))
             .await
     }
 
@@ -719,14 +1478,16 @@ impl IpcProvider {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
 
-        conn.manager().list_bootstrap_nodes(subnet).await
+        self.within_deadline(conn.manager().list_bootstrap_nodes(subnet))
+            .await
     }
 
     /// Returns the latest finality from the parent committed in a child subnet.
     pub async fn latest_parent_finality(&self, subnet: &SubnetID) -> anyhow::Result<ChainEpoch> {
         let conn = self.get_connection(subnet)?;
 
-        conn.manager().latest_parent_finality().await
+        self.within_deadline(conn.manager().latest_parent_finality())
+            .await
     }
 
     pub async fn set_federated_power(
@@ -739,9 +1500,14 @@ impl IpcProvider {
     ) -> anyhow::Result<ChainEpoch> {
         let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
-        conn.manager()
-            .set_federated_power(from, subnet, validators, public_keys, federated_power)
-            .await
+        self.within_deadline(conn.manager().set_federated_power(
+            from,
+            subnet,
+            validators,
+            public_keys,
+            federated_power,
+        ))
+        .await
     }
 
     pub async fn list_validator_activities(
@@ -752,8 +1518,63 @@ impl IpcProvider {
         to: ChainEpoch,
     ) -> anyhow::Result<Vec<(u64, ValidatorData)>> {
         let conn = self.get_connection(subnet)?;
-        conn.manager()
-            .query_validator_rewards(validator, from, to)
+        self.within_deadline(conn.manager().query_validator_rewards(validator, from, to))
+            .await
+    }
+
+    /// Lists the staking changes of a validator in a child subnet, as recorded in its parent.
+    pub async fn stake_history(
+        &self,
+        subnet: &SubnetID,
+        validator: &Address,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> anyhow::Result<Vec<StakeHistoryEntry>> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        self.within_deadline(conn.manager().stake_history(subnet, validator, from, to))
+            .await
+    }
+
+    /// Lists the top-down messages sent to a child subnet between two heights of its parent,
+    /// with the parent transactions that sent them.
+    pub async fn top_down_msg_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> anyhow::Result<Vec<(IpcEnvelope, TxRef)>> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        self.within_deadline(conn.manager().top_down_msg_txs(subnet, from, to))
+            .await
+    }
+
+    /// Lists the subnet transactions that queued bottom-up messages between two heights of the
+    /// subnet, by the tracing id of the message.
+    pub async fn bottom_up_msg_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> anyhow::Result<HashMap<[u8; 32], TxRef>> {
+        let conn = self.get_connection(subnet)?;
+        self.within_deadline(conn.manager().bottom_up_msg_txs(from, to))
+            .await
+    }
+
+    /// Lists the bottom-up checkpoints of a child subnet committed between two heights of its
+    /// parent, by the subnet height of the checkpoint, with the parent transactions that
+    /// committed them.
+    pub async fn checkpoint_commit_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> anyhow::Result<Vec<(ChainEpoch, TxRef)>> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let conn = self.get_connection(&parent)?;
+        self.within_deadline(conn.manager().checkpoint_commit_txs(subnet, from, to))
             .await
     }
 
@@ -767,21 +1588,153 @@ impl IpcProvider {
     ) -> anyhow::Result<()> {
         let conn = self.get_connection(reward_source_subnet)?;
 
-        let claims = conn
-            .manager()
-            .query_reward_claims(validator, from, to)
+        let claims = self
+            .within_deadline(conn.manager().query_reward_claims(validator, from, to))
             .await?;
 
         let parent = reward_claim_subnet
             .parent()
             .ok_or_else(|| anyhow!("no parent found"))?;
         let conn = self.get_connection(&parent)?;
-        conn.manager()
-            .batch_subnet_claim(validator, reward_claim_subnet, reward_source_subnet, claims)
-            .await
+        self.within_deadline(conn.manager().batch_subnet_claim(
+            validator,
+            reward_claim_subnet,
+            reward_source_subnet,
+            claims,
+        ))
+        .await
+    }
+
+    /// Compares the top-down messages committed for `subnet` in its parent with the ones the
+    /// subnet executed. Both the subnet and its parent must be configured.
+    pub async fn top_down_queue(&self, subnet: &SubnetID) -> anyhow::Result<TopDownQueue> {
+        let parent = subnet.parent().ok_or_else(|| anyhow!("no parent found"))?;
+        let parent_conn = self.get_connection(&parent)?;
+        let conn = self.get_connection(subnet)?;
+
+        let committed = self
+            .within_deadline(parent_conn.manager().top_down_nonce(subnet))
+            .await?;
+        let applied = self
+            .within_deadline(conn.manager().applied_top_down_nonce())
+            .await?;
+        Ok(TopDownQueue { committed, applied })
+    }
+
+    /// Traces the cross-net messages committed by the transaction `tx_hash` sent in `subnet`:
+    /// top-down messages are followed into the child subnet, bottom-up messages to the
+    /// checkpoint that carries them to the parent. Returns `None` if the transaction is not
+    /// found or still pending.
+    pub async fn cross_msg_status(
+        &self,
+        subnet: &SubnetID,
+        tx_hash: &[u8; 32],
+    ) -> anyhow::Result<Option<CrossMsgStatus>> {
+        let conn = self.get_connection(subnet)?;
+        let Some(tx) = self
+            .within_deadline(conn.manager().cross_msg_tx(tx_hash))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut deliveries = vec![];
+        for msg in tx.top_down {
+            let destination = msg.to.subnet()?;
+            let child = destination.down(subnet).ok_or_else(|| {
+                anyhow!("top-down message to {destination} does not go through a child of {subnet}")
+            })?;
+            // we can only tell if the message was executed if we can reach the child subnet
+            let executed = match self.connection(&child) {
+                Some(child_conn) => Some(
+                    self.within_deadline(child_conn.manager().applied_top_down_nonce())
+                        .await?
+                        > msg.local_nonce,
+                ),
+                None => None,
+            };
+            deliveries.push(CrossMsgDelivery::TopDown {
+                subnet: child,
+                nonce: msg.local_nonce,
+                executed,
+            });
+        }
+
+        if tx.bottom_up > 0 {
+            let parent = subnet
+                .parent()
+                .ok_or_else(|| anyhow!("subnet {subnet} has no parent to checkpoint to"))?;
+            let parent_conn = self.get_connection(&parent)?;
+            let period = self
+                .within_deadline(parent_conn.manager().checkpoint_period(subnet))
+                .await?;
+            if period <= 0 {
+                return Err(anyhow!("invalid checkpoint period {period} for {subnet}"));
+            }
+            let checkpoint_height = (tx.height / period + 1) * period;
+            let last_submitted = self
+                .within_deadline(
+                    parent_conn
+                        .manager()
+                        .last_bottom_up_checkpoint_height(subnet),
+                )
+                .await?;
+            deliveries.push(CrossMsgDelivery::BottomUp {
+                messages: tx.bottom_up,
+                checkpoint_height,
+                submitted: last_submitted >= checkpoint_height,
+            });
+        }
+
+        Ok(Some(CrossMsgStatus {
+            height: tx.height,
+            deliveries,
+        }))
+    }
+}
+
+/// The top-down messages of a subnet, see [`IpcProvider::top_down_queue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopDownQueue {
+    /// The messages committed for the subnet in the parent.
+    pub committed: u64,
+    /// The messages executed in the subnet.
+    pub applied: u64,
+}
+
+impl TopDownQueue {
+    /// The messages waiting to be executed in the subnet.
+    pub fn pending(&self) -> u64 {
+        self.committed.saturating_sub(self.applied)
     }
 }
 
+/// The cross-net messages committed by a transaction, see [`IpcProvider::cross_msg_status`].
+#[derive(Debug, Clone)]
+pub struct CrossMsgStatus {
+    /// The height of the block that included the transaction.
+    pub height: ChainEpoch,
+    pub deliveries: Vec<CrossMsgDelivery>,
+}
+
+/// How far a cross-net message has travelled towards its destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrossMsgDelivery {
+    /// A top-down message with `nonce` for the child `subnet`. `executed` is `None` when the
+    /// child subnet is not configured and cannot be queried.
+    TopDown {
+        subnet: SubnetID,
+        nonce: u64,
+        executed: Option<bool>,
+    },
+    /// Bottom-up messages carried to the parent by the checkpoint at `checkpoint_height`.
+    BottomUp {
+        messages: usize,
+        checkpoint_height: ChainEpoch,
+        submitted: bool,
+    },
+}
+
 /// Lotus JSON keytype format
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -867,6 +1820,50 @@ impl IpcProvider {
     }
 }
 
+/// Fails with the reason the subnet actor would reject `sender` joining `subnet` with
+/// `collateral`, before the transaction is signed and sent.
+async fn check_join(
+    manager: &dyn SubnetManager,
+    subnet: &SubnetID,
+    sender: &Address,
+    collateral: &TokenAmount,
+) -> anyhow::Result<()> {
+    if collateral.is_zero() {
+        return Err(anyhow!(
+            "the collateral to join {subnet} must be above zero"
+        ));
+    }
+
+    let status = manager.bootstrap_status(subnet).await?;
+    if status.bootstrapped {
+        let mode = manager.permission_mode(subnet).await?;
+        if mode != PermissionMode::Collateral {
+            return Err(anyhow!(
+                "{subnet} is bootstrapped in {} mode, new validators can no longer join",
+                format!("{mode:?}").to_lowercase()
+            ));
+        }
+    }
+
+    let info = manager.get_validator_info(subnet, sender).await?;
+    if !info.staking.total_collateral().is_zero() {
+        return Err(anyhow!(
+            "{sender} already joined {subnet}, use stake to add collateral"
+        ));
+    }
+
+    if !status.bootstrapped {
+        let collateral = &status.collateral + collateral;
+        if collateral < status.min_collateral {
+            log::info!(
+                "{subnet} bootstraps once its collateral reaches {}, it will be {collateral} after this join",
+                status.min_collateral
+            );
+        }
+    }
+    Ok(())
+}
+
 fn new_fvm_wallet_from_config(config: Arc<Config>) -> anyhow::Result<KeyStore> {
     let repo_str = &config.keystore_path;
     if let Some(repo_str) = repo_str {
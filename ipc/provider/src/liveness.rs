@@ -0,0 +1,88 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Liveness of the validators of a subnet, judged by which of them signed its recent bottom-up
+//! checkpoints.
+
+use std::collections::HashSet;
+
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use serde::Serialize;
+
+/// How a validator took part in the checkpoints looked at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidatorLiveness {
+    pub validator: Address,
+    /// The number of checkpoints the validator signed.
+    pub signed: usize,
+    /// The number of checkpoints looked at.
+    pub checkpoints: usize,
+    /// The number of the most recent checkpoints the validator did not sign, back to the last one
+    /// it did.
+    pub missed_in_a_row: usize,
+    /// The height of the most recent checkpoint the validator signed.
+    pub last_signed: Option<ChainEpoch>,
+}
+
+impl ValidatorLiveness {
+    /// Whether the validator missed more than `max_missed` checkpoints in a row.
+    pub fn is_lagging(&self, max_missed: usize) -> bool {
+        self.missed_in_a_row > max_missed
+    }
+}
+
+/// The liveness of each of `validators` given the signatories of recent checkpoints, which are
+/// ordered by height.
+pub fn validator_liveness(
+    validators: &[Address],
+    checkpoints: &[(ChainEpoch, Vec<Address>)],
+) -> Vec<ValidatorLiveness> {
+    let signatories = checkpoints
+        .iter()
+        .map(|(height, signatories)| (*height, signatories.iter().collect::<HashSet<_>>()))
+        .collect::<Vec<_>>();
+
+    validators
+        .iter()
+        .map(|validator| {
+            let signed_at = signatories
+                .iter()
+                .filter(|(_, s)| s.contains(validator))
+                .map(|(height, _)| *height)
+                .collect::<Vec<_>>();
+            let missed_in_a_row = signatories
+                .iter()
+                .rev()
+                .take_while(|(_, s)| !s.contains(validator))
+                .count();
+            ValidatorLiveness {
+                validator: *validator,
+                signed: signed_at.len(),
+                checkpoints: checkpoints.len(),
+                missed_in_a_row,
+                last_signed: signed_at.last().copied(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::liveness::validator_liveness;
+    use fvm_shared::address::Address;
+
+    #[test]
+    fn test_validator_liveness() {
+        let (a, b, c) = (Address::new_id(1), Address::new_id(2), Address::new_id(3));
+        let checkpoints = vec![(10, vec![a, b, c]), (20, vec![a, b]), (30, vec![a])];
+
+        let liveness = validator_liveness(&[a, b, c], &checkpoints);
+        assert_eq!(liveness[0].missed_in_a_row, 0);
+        assert_eq!(liveness[0].signed, 3);
+        assert_eq!(liveness[1].missed_in_a_row, 1);
+        assert_eq!(liveness[1].last_signed, Some(20));
+        assert_eq!(liveness[2].missed_in_a_row, 2);
+        assert!(liveness[2].is_lagging(1));
+        assert!(!liveness[2].is_lagging(2));
+    }
+}
@@ -9,6 +9,8 @@ use ethers::{
 use serde_json::json;
 use thiserror::Error;
 
+use crate::config::subnet::FeePolicy;
+
 #[derive(Error, Debug)]
 pub enum Eip1559GasEstimatorError<M: Middleware> {
     #[error("{0}")]
@@ -48,11 +50,21 @@ pub struct GasFeeEstimate {
 #[derive(Debug)]
 pub struct Eip1559GasEstimatorMiddleware<M: Middleware> {
     inner: M,
+    policy: FeePolicy,
 }
 
 impl<M: Middleware> Eip1559GasEstimatorMiddleware<M> {
     pub fn new(inner: M) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            policy: FeePolicy::default(),
+        }
+    }
+
+    /// Caps the estimated fees with the limits of the subnet's fee policy.
+    pub fn with_fee_policy(mut self, policy: FeePolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     pub async fn max_priority_fee_per_gas(&self) -> Result<U256, ProviderError> {
@@ -89,10 +101,36 @@ impl<M: Middleware> Eip1559GasEstimatorMiddleware<M> {
         let base_fee_per_gas_surged = base_fee_per_gas * 2;
         let max_fee_per_gas = max_priority_fee_per_gas + base_fee_per_gas_surged;
 
-        Ok(GasFeeEstimate {
-            max_priority_fee_per_gas,
-            max_fee_per_gas,
-        })
+        self.apply_policy(
+            base_fee_per_gas,
+            GasFeeEstimate {
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+            },
+        )
+    }
+
+    fn apply_policy(
+        &self,
+        base_fee_per_gas: U256,
+        mut estimate: GasFeeEstimate,
+    ) -> Result<GasFeeEstimate, Eip1559GasEstimatorError<M>> {
+        if let Some(cap) = self.policy.max_priority_fee_per_gas {
+            estimate.max_priority_fee_per_gas = estimate.max_priority_fee_per_gas.min(cap.into());
+        }
+        if let Some(cap) = self.policy.max_fee_per_gas {
+            let cap = U256::from(cap);
+            if base_fee_per_gas > cap {
+                return Err(Eip1559GasEstimatorError::FailedToEstimateGas(format!(
+                    "base fee per gas {base_fee_per_gas} is above the configured max fee per gas {cap}"
+                )));
+            }
+            estimate.max_fee_per_gas = estimate.max_fee_per_gas.min(cap);
+            estimate.max_priority_fee_per_gas = estimate
+                .max_priority_fee_per_gas
+                .min(estimate.max_fee_per_gas);
+        }
+        Ok(estimate)
     }
 }
 
@@ -158,3 +196,48 @@ impl<M: Middleware> Middleware for Eip1559GasEstimatorMiddleware<M> {
             .map_err(Eip1559GasEstimatorError::MiddlewareError)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Eip1559GasEstimatorMiddleware, GasFeeEstimate};
+    use crate::config::subnet::FeePolicy;
+    use ethers::providers::{MockProvider, Provider};
+    use ethers::types::U256;
+
+    fn estimator(policy: FeePolicy) -> Eip1559GasEstimatorMiddleware<Provider<MockProvider>> {
+        let (provider, _) = Provider::mocked();
+        Eip1559GasEstimatorMiddleware::new(provider).with_fee_policy(policy)
+    }
+
+    fn estimate() -> GasFeeEstimate {
+        GasFeeEstimate {
+            max_priority_fee_per_gas: U256::from(100),
+            max_fee_per_gas: U256::from(300),
+        }
+    }
+
+    #[test]
+    fn test_fee_policy_caps_fees() {
+        let capped = estimator(FeePolicy {
+            max_fee_per_gas: Some(150),
+            max_priority_fee_per_gas: Some(50),
+            confirmations: None,
+        })
+        .apply_policy(U256::from(100), estimate())
+        .unwrap();
+
+        assert_eq!(capped.max_priority_fee_per_gas, U256::from(50));
+        assert_eq!(capped.max_fee_per_gas, U256::from(150));
+    }
+
+    #[test]
+    fn test_fee_policy_rejects_base_fee_above_cap() {
+        let policy = FeePolicy {
+            max_fee_per_gas: Some(80),
+            ..Default::default()
+        };
+        assert!(estimator(policy)
+            .apply_policy(U256::from(100), estimate())
+            .is_err());
+    }
+}
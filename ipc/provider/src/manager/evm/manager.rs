@@ -6,7 +6,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
-use ethers_contract::{ContractError, EthLogDecode, LogMeta};
+use ethers_contract::{ContractCall, ContractError, EthLogDecode, LogMeta};
 use ipc_actors_abis::{
     checkpointing_facet, gateway_getter_facet, gateway_manager_facet, lib_gateway, lib_quorum,
     lib_staking_change_log, register_subnet_facet, subnet_actor_activity_facet,
@@ -14,7 +14,7 @@ use ipc_actors_abis::{
     subnet_actor_reward_facet,
 };
 use ipc_api::evm::{fil_to_eth_amount, payload_to_evm_address, subnet_id_to_evm_addresses};
-use ipc_api::validator::from_contract_validators;
+use ipc_api::validator::{from_contract_validators, Validator};
 use reqwest::header::HeaderValue;
 use reqwest::Client;
 use std::net::{IpAddr, SocketAddr};
@@ -22,13 +22,16 @@ use std::net::{IpAddr, SocketAddr};
 use ipc_api::subnet::{Asset, AssetKind, PermissionMode};
 use ipc_api::{eth_to_fil_amount, ethers_address_to_fil_address};
 
-use crate::config::subnet::SubnetConfig;
+use crate::config::subnet::{FeePolicy, SubnetConfig, TlsConfig};
 use crate::config::Subnet;
 use crate::lotus::message::ipc::SubnetInfo;
 use crate::manager::subnet::{
-    BottomUpCheckpointRelayer, GetBlockHashResult, SubnetGenesisInfo, TopDownFinalityQuery,
-    TopDownQueryPayload, ValidatorRewarder,
+    BootstrapStatus, BottomUpCheckpointRelayer, Capabilities, CreatedSubnet, CrossMsgTx,
+    FeeEstimate, GetBlockHashResult, Operation, StakeHistoryEntry, SubnetCreation,
+    SubnetGenesisInfo, TokenInfo, TopDownFinalityQuery, TopDownQueryPayload, TxRef,
+    ValidatorRewarder,
 };
+use crate::topdown::dedup_top_down_msgs;
 
 use crate::manager::{EthManager, SubnetManager};
 use anyhow::{anyhow, Context, Result};
@@ -37,11 +40,13 @@ use ethers::abi::Tokenizable;
 use ethers::contract::abigen;
 use ethers::prelude::k256::ecdsa::SigningKey;
 use ethers::prelude::{Signer, SignerMiddleware};
-use ethers::providers::{Authorization, Http, Provider};
+use ethers::providers::{Http, Provider};
 use ethers::signers::{LocalWallet, Wallet};
 use ethers::types::{Eip1559TransactionRequest, ValueOrArray, H256, U256};
 
 use super::gas_estimator_middleware::Eip1559GasEstimatorMiddleware;
+use super::nonce_middleware::NonceTrackingMiddleware;
+use super::rpc_recorder::{RecordingHttp, RpcRecorder};
 use ethers::middleware::Middleware;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::{address::Address, econ::TokenAmount};
@@ -60,8 +65,9 @@ use ipc_wallet::{EthKeyAddress, EvmKeyStore, PersistentKeyStore};
 use num_traits::ToPrimitive;
 use std::result;
 
-pub type SignerWithFeeEstimatorMiddleware =
-    Eip1559GasEstimatorMiddleware<SignerMiddleware<Provider<Http>, Wallet<SigningKey>>>;
+pub type SignerWithFeeEstimatorMiddleware = NonceTrackingMiddleware<
+    Eip1559GasEstimatorMiddleware<SignerMiddleware<Provider<RecordingHttp>, Wallet<SigningKey>>>,
+>;
 
 /// Default polling time used by the Ethers provider to check for pending
 /// transactions and events. Default is 7, and for our child subnets we
@@ -82,6 +88,7 @@ const SUBNET_MAJORITY_PERCENTAGE: u8 = 67;
 pub struct EthSubnetManager {
     keystore: Option<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>,
     ipc_contract_info: IPCContractInfo,
+    fee_policy: FeePolicy,
 }
 
 /// Keep track of the on chain information for the subnet manager
@@ -89,7 +96,7 @@ struct IPCContractInfo {
     gateway_addr: ethers::types::Address,
     registry_addr: ethers::types::Address,
     chain_id: u64,
-    provider: Provider<Http>,
+    provider: Provider<RecordingHttp>,
 }
 
 //TODO receive clarity on this implementation
@@ -97,6 +104,10 @@ abigen!(
     IERC20,
     r#"[
         function approve(address spender, uint256 amount) external returns (bool)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+        function symbol() external view returns (string)
+        function decimals() external view returns (uint8)
         event Transfer(address indexed from, address indexed to, uint256 value)
         event Approval(address indexed owner, address indexed spender, uint256 value)
     ]"#,
@@ -175,7 +186,7 @@ impl TopDownFinalityQuery for EthSubnetManager {
             self.get_block_hash(epoch).await?.block_hash
         };
         Ok(TopDownQueryPayload {
-            value: messages,
+            value: dedup_top_down_msgs(messages)?,
             block_hash,
         })
     }
@@ -255,82 +266,66 @@ impl TopDownFinalityQuery for EthSubnetManager {
 
 #[async_trait]
 impl SubnetManager for EthSubnetManager {
-    async fn create_subnet(&self, from: Address, params: ConstructParams) -> Result<Address> {
-        self.ensure_same_gateway(&params.ipc_gateway_addr)?;
-
-        let min_validator_stake = params
-            .min_validator_stake
-            .atto()
-            .to_u128()
-            .ok_or_else(|| anyhow!("invalid min validator stake"))?;
-
-        tracing::debug!("calling create subnet for EVM manager");
-
-        let route = subnet_id_to_evm_addresses(&params.parent)?;
-        tracing::debug!("root SubnetID as Ethereum type: {route:?}");
-
-        let params = register_subnet_facet::ConstructorParams {
-            parent_id: register_subnet_facet::SubnetID {
-                root: params.parent.root_id(),
-                route,
-            },
-            ipc_gateway_addr: self.ipc_contract_info.gateway_addr,
-            consensus: params.consensus as u64 as u8,
-            min_activation_collateral: ethers::types::U256::from(min_validator_stake),
-            min_validators: params.min_validators,
-            bottom_up_check_period: params.bottomup_check_period as u64,
-            majority_percentage: SUBNET_MAJORITY_PERCENTAGE,
-            active_validators_limit: params.active_validators_limit,
-            power_scale: 3,
-            permission_mode: params.permission_mode as u8,
-            supply_source: register_subnet_facet::Asset::try_from(params.supply_source)?,
-            collateral_source: register_subnet_facet::Asset::try_from(params.collateral_source)?,
-            validator_gater: payload_to_evm_address(params.validator_gater.payload())?,
-            validator_rewarder: payload_to_evm_address(params.validator_rewarder.payload())?,
-        };
-
-        tracing::info!("creating subnet on evm with params: {params:?}");
-
-        let signer = self.get_signer_with_fee_estimator(&from)?;
-        let signer = Arc::new(signer);
-        let registry_contract = register_subnet_facet::RegisterSubnetFacet::new(
-            self.ipc_contract_info.registry_addr,
-            signer.clone(),
-        );
-
-        let call =
-            extend_call_with_pending_block(registry_contract.new_subnet_actor(params)).await?;
+    async fn create_subnet(&self, from: Address, params: ConstructParams) -> Result<CreatedSubnet> {
+        let call = self.new_subnet_actor_call(&from, params).await?;
         // TODO: Edit call to get estimate premium
         let pending_tx = call.send().await?;
         // We need the retry to parse the deployment event. At the time of this writing, it's a bug
         // in current FEVM that without the retries, events are not picked up.
         // See https://github.com/filecoin-project/community/discussions/638 for more info and updates.
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
-        match receipt {
-            Some(r) => {
-                for log in r.logs {
-                    tracing::debug!("log: {log:?}");
-
-                    match ethers_contract::parse_log::<register_subnet_facet::SubnetDeployedFilter>(
-                        log,
-                    ) {
-                        Ok(subnet_deploy) => {
-                            let register_subnet_facet::SubnetDeployedFilter { subnet_addr } =
-                                subnet_deploy;
-
-                            tracing::debug!("subnet deployed at {subnet_addr:?}");
-                            return ethers_address_to_fil_address(&subnet_addr);
-                        }
-                        Err(_) => {
-                            tracing::debug!("no event for subnet actor published yet, continue");
-                            continue;
-                        }
-                    }
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
+        let Some(receipt) = receipt else {
+            return Err(anyhow!("no receipt for event, txn not successful"));
+        };
+        let tx_hash = receipt.transaction_hash.0;
+        let height = receipt
+            .block_number
+            .ok_or_else(|| anyhow!("cannot get block number"))?
+            .as_u64() as ChainEpoch;
+        Ok(CreatedSubnet {
+            address: deployed_subnet_address(receipt)?,
+            tx_hash,
+            height,
+        })
+    }
+
+    async fn submit_create_subnet(
+        &self,
+        from: Address,
+        params: ConstructParams,
+    ) -> Result<[u8; 32]> {
+        let call = self.new_subnet_actor_call(&from, params).await?;
+        let pending_tx = call.send().await?;
+        Ok(pending_tx.tx_hash().0)
+    }
+
+    async fn create_subnet_status(&self, tx_hash: &[u8; 32]) -> Result<SubnetCreation> {
+        let provider = &self.ipc_contract_info.provider;
+        let hash = H256::from(*tx_hash);
+
+        let Some(receipt) = provider.get_transaction_receipt(hash).await? else {
+            return Ok(match provider.get_transaction(hash).await? {
+                Some(_) => SubnetCreation::Pending,
+                None => {
+                    SubnetCreation::Failed("transaction not found, it may have been dropped".into())
                 }
-                Err(anyhow!("no logs receipt"))
-            }
-            None => Err(anyhow!("no receipt for event, txn not successful")),
+            });
+        };
+        if receipt.status == Some(0.into()) {
+            return Ok(SubnetCreation::Failed("transaction reverted".into()));
         }
+        let Some(included) = receipt.block_number else {
+            return Ok(SubnetCreation::Pending);
+        };
+        let head = provider.get_block_number().await?;
+        if head.as_u64() + 1 < included.as_u64() + self.confirmations() as u64 {
+            return Ok(SubnetCreation::Pending);
+        }
+
+        deployed_subnet_address(receipt).map(SubnetCreation::Created)
     }
 
     async fn join_subnet(
@@ -340,27 +335,12 @@ impl SubnetManager for EthSubnetManager {
         collateral: TokenAmount,
         pub_key: Vec<u8>,
     ) -> Result<ChainEpoch> {
-        let collateral = collateral
-            .atto()
-            .to_u128()
-            .ok_or_else(|| anyhow!("invalid min validator stake"))?;
-
-        let address = contract_address_from_subnet(&subnet)?;
-        tracing::info!(
-            "interacting with evm subnet contract: {address:} with collateral: {collateral:}"
-        );
-
-        let signer = Arc::new(self.get_signer_with_fee_estimator(&from)?);
-        let contract =
-            subnet_actor_manager_facet::SubnetActorManagerFacet::new(address, signer.clone());
-
-        let mut txn = contract.join(ethers::types::Bytes::from(pub_key), U256::from(collateral));
-        txn = self.handle_txn_token(&subnet, txn, collateral, 0).await?;
-
-        let txn = extend_call_with_pending_block(txn).await?;
-
+        let txn = self.join_call(&subnet, &from, collateral, pub_key).await?;
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -408,32 +388,18 @@ impl SubnetManager for EthSubnetManager {
             .await?
             .send()
             .await?
+            .confirmations(self.confirmations())
             .await?;
 
         Ok(())
     }
 
     async fn stake(&self, subnet: SubnetID, from: Address, collateral: TokenAmount) -> Result<()> {
-        let collateral = collateral
-            .atto()
-            .to_u128()
-            .ok_or_else(|| anyhow!("invalid collateral amount"))?;
-
-        let address = contract_address_from_subnet(&subnet)?;
-        tracing::info!(
-            "interacting with evm subnet contract: {address:} with collateral: {collateral:}"
-        );
-
-        let signer = Arc::new(self.get_signer_with_fee_estimator(&from)?);
-        let contract =
-            subnet_actor_manager_facet::SubnetActorManagerFacet::new(address, signer.clone());
-
-        let mut txn = contract.stake(U256::from(collateral));
-        txn = self.handle_txn_token(&subnet, txn, collateral, 0).await?;
-
-        let txn = extend_call_with_pending_block(txn).await?;
-
-        txn.send().await?.await?;
+        let txn = self.stake_call(&subnet, &from, collateral).await?;
+        txn.send()
+            .await?
+            .confirmations(self.confirmations())
+            .await?;
 
         Ok(())
     }
@@ -459,7 +425,10 @@ impl SubnetManager for EthSubnetManager {
             subnet_actor_manager_facet::SubnetActorManagerFacet::new(address, signer.clone());
 
         let txn = extend_call_with_pending_block(contract.unstake(collateral.into())).await?;
-        txn.send().await?.await?;
+        txn.send()
+            .await?
+            .confirmations(self.confirmations())
+            .await?;
 
         Ok(())
     }
@@ -476,6 +445,7 @@ impl SubnetManager for EthSubnetManager {
             .await?
             .send()
             .await?
+            .confirmations(self.confirmations())
             .await?;
 
         Ok(())
@@ -493,6 +463,7 @@ impl SubnetManager for EthSubnetManager {
             .await?
             .send()
             .await?
+            .confirmations(self.confirmations())
             .await?;
 
         Ok(())
@@ -522,6 +493,25 @@ impl SubnetManager for EthSubnetManager {
         Ok(s)
     }
 
+    async fn list_postbox(&self) -> Result<Vec<([u8; 32], IpcEnvelope)>> {
+        let gateway_contract = gateway_getter_facet::GatewayGetterFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        let keys = gateway_contract.postbox_msgs().call().await?;
+        let envelopes = futures_util::future::try_join_all(
+            keys.iter()
+                .map(|key| async { gateway_contract.postbox(*key).call().await }),
+        )
+        .await?;
+
+        keys.into_iter()
+            .zip(envelopes)
+            .map(|(key, envelope)| Ok((key, IpcEnvelope::try_from(envelope)?)))
+            .collect()
+    }
+
     async fn claim_collateral(&self, subnet: SubnetID, from: Address) -> Result<()> {
         let address = contract_address_from_subnet(&subnet)?;
         tracing::info!("claim collateral evm subnet: {subnet:} at contract: {address:}");
@@ -534,6 +524,7 @@ impl SubnetManager for EthSubnetManager {
             .await?
             .send()
             .await?
+            .confirmations(self.confirmations())
             .await?;
 
         Ok(())
@@ -549,31 +540,12 @@ impl SubnetManager for EthSubnetManager {
     ) -> Result<ChainEpoch> {
         self.ensure_same_gateway(&gateway_addr)?;
 
-        let value = amount
-            .atto()
-            .to_u128()
-            .ok_or_else(|| anyhow!("invalid value to fund"))?;
-
-        tracing::info!("fund with evm gateway contract: {gateway_addr:} with value: {value:}, original: {amount:?}");
-
-        let evm_subnet_id = gateway_manager_facet::SubnetID::try_from(&subnet)?;
-        tracing::debug!("evm subnet id to fund: {evm_subnet_id:?}");
-
-        let signer = Arc::new(self.get_signer_with_fee_estimator(&from)?);
-        let gateway_contract = gateway_manager_facet::GatewayManagerFacet::new(
-            self.ipc_contract_info.gateway_addr,
-            signer.clone(),
-        );
-
-        let mut txn = gateway_contract.fund(
-            evm_subnet_id,
-            gateway_manager_facet::FvmAddress::try_from(to)?,
-        );
-        txn.tx.set_value(value);
-        let txn = extend_call_with_pending_block(txn).await?;
-
+        let txn = self.fund_call(&subnet, &from, &to, amount).await?;
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -590,24 +562,17 @@ impl SubnetManager for EthSubnetManager {
 
         let signer = Arc::new(self.get_signer_with_fee_estimator(&from)?);
 
-        let subnet_supply_source = self.get_subnet_supply_source(&subnet).await?;
-        if subnet_supply_source.kind != AssetKind::ERC20 {
-            return Err(anyhow!("Invalid operation: Expected the subnet's supply source to be ERC20, but found a different kind."));
-        }
-
-        let token_address = payload_to_evm_address(
-            subnet_supply_source
-                .token_address
-                .ok_or_else(|| anyhow!("zero adress not erc20"))?
-                .payload(),
-        )?;
+        let token_address = self.erc20_supply_source(&subnet).await?;
         let token_contract = IERC20::new(token_address, signer.clone());
 
         let txn = token_contract.approve(self.ipc_contract_info.gateway_addr, value);
         let txn = extend_call_with_pending_block(txn).await?;
 
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -625,6 +590,29 @@ impl SubnetManager for EthSubnetManager {
         let value = fil_amount_to_eth_amount(&amount)?;
         let evm_subnet_id = gateway_manager_facet::SubnetID::try_from(&subnet)?;
 
+        // Check upfront what the gateway would otherwise revert on without a reason.
+        let token_address = self.erc20_supply_source(&subnet).await?;
+        let token_contract = IERC20::new(
+            token_address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let sender = payload_to_evm_address(from.payload())?;
+        let balance = token_contract.balance_of(sender).call().await?;
+        if balance < value {
+            return Err(anyhow!(
+                "insufficient balance of token {token_address:?}: has {balance}, needs {value}"
+            ));
+        }
+        let allowance = token_contract
+            .allowance(sender, self.ipc_contract_info.gateway_addr)
+            .call()
+            .await?;
+        if allowance < value {
+            return Err(anyhow!(
+                "gateway allowance for token {token_address:?} is {allowance}, needs {value}; approve the gateway first"
+            ));
+        }
+
         let signer = Arc::new(self.get_signer_with_fee_estimator(&from)?);
         let gateway_contract = gateway_manager_facet::GatewayManagerFacet::new(
             self.ipc_contract_info.gateway_addr,
@@ -639,7 +627,10 @@ impl SubnetManager for EthSubnetManager {
         let txn = extend_call_with_pending_block(txn).await?;
 
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -669,7 +660,10 @@ impl SubnetManager for EthSubnetManager {
         let txn = extend_call_with_pending_block(txn).await?;
 
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -742,6 +736,38 @@ impl SubnetManager for EthSubnetManager {
         Ok(Asset::try_from(raw)?)
     }
 
+    async fn get_supply_token_info(&self, subnet: &SubnetID) -> Result<TokenInfo> {
+        let token_address = self.erc20_supply_source(subnet).await?;
+        let token_contract = IERC20::new(
+            token_address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let symbol = token_contract.symbol().call().await?;
+        let decimals = token_contract.decimals().call().await?;
+        Ok(TokenInfo {
+            address: ethers_address_to_fil_address(&token_address)?,
+            symbol,
+            decimals,
+        })
+    }
+
+    async fn supply_token_balance(
+        &self,
+        subnet: &SubnetID,
+        address: &Address,
+    ) -> Result<TokenAmount> {
+        let token_address = self.erc20_supply_source(subnet).await?;
+        let token_contract = IERC20::new(
+            token_address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let balance = token_contract
+            .balance_of(payload_to_evm_address(address.payload())?)
+            .call()
+            .await?;
+        eth_to_fil_amount(&balance)
+    }
+
     async fn get_subnet_collateral_source(&self, subnet: &SubnetID) -> Result<Asset> {
         let address = contract_address_from_subnet(subnet)?;
         let contract = subnet_actor_getter_facet::SubnetActorGetterFacet::new(
@@ -805,6 +831,7 @@ impl SubnetManager for EthSubnetManager {
             .await?
             .send()
             .await?
+            .confirmations(self.confirmations())
             .await?;
 
         Ok(())
@@ -843,47 +870,15 @@ impl SubnetManager for EthSubnetManager {
     }
 
     async fn list_validators(&self, subnet: &SubnetID) -> Result<Vec<(Address, ValidatorInfo)>> {
-        let address = contract_address_from_subnet(subnet)?;
-        let contract = subnet_actor_getter_facet::SubnetActorGetterFacet::new(
-            address,
-            Arc::new(self.ipc_contract_info.provider.clone()),
-        );
-
-        let mut addresses: Vec<Address> = vec![];
-        let mut validators: Vec<ValidatorInfo> = vec![];
-
-        let active = contract.get_active_validators().call().await?;
-        addresses.extend(
-            active
-                .iter()
-                .map(ethers_address_to_fil_address)
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-        for addr in active {
-            let info = contract.get_validator(addr).call().await?;
-            validators.push(ValidatorInfo {
-                staking: ValidatorStakingInfo::try_from(info)?,
-                is_active: true,
-                is_waiting: false,
-            });
-        }
-        let waiting = contract.get_waiting_validators().call().await?;
-        addresses.extend(
-            waiting
-                .iter()
-                .map(ethers_address_to_fil_address)
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-        for addr in waiting {
-            let info = contract.get_validator(addr).call().await?;
-            validators.push(ValidatorInfo {
-                staking: ValidatorStakingInfo::try_from(info)?,
-                is_active: false,
-                is_waiting: true,
-            });
-        }
+        self.validators_at(subnet, None).await
+    }
 
-        Ok(addresses.into_iter().zip(validators).collect())
+    async fn list_validators_at(
+        &self,
+        subnet: &SubnetID,
+        height: ChainEpoch,
+    ) -> Result<Vec<(Address, ValidatorInfo)>> {
+        self.validators_at(subnet, Some(height)).await
     }
 
     async fn set_federated_power(
@@ -924,9 +919,244 @@ impl SubnetManager for EthSubnetManager {
         let call = contract.set_federated_power(addresses, pubkeys, power_u256);
         let txn = extend_call_with_pending_block(call).await?;
         let pending_tx = txn.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::all()
+    }
+
+    async fn estimate(&self, from: Address, operation: Operation) -> Result<FeeEstimate> {
+        match operation {
+            Operation::CreateSubnet(params) => {
+                self.estimate_call(self.new_subnet_actor_call(&from, params).await?)
+                    .await
+            }
+            Operation::Join {
+                subnet,
+                collateral,
+                public_key,
+            } => {
+                self.estimate_call(
+                    self.join_call(&subnet, &from, collateral, public_key)
+                        .await?,
+                )
+                .await
+            }
+            Operation::Stake { subnet, collateral } => {
+                self.estimate_call(self.stake_call(&subnet, &from, collateral).await?)
+                    .await
+            }
+            Operation::Fund { subnet, to, amount } => {
+                self.estimate_call(self.fund_call(&subnet, &from, &to, amount).await?)
+                    .await
+            }
+        }
+    }
+
+    async fn cross_msg_tx(&self, tx_hash: &[u8; 32]) -> Result<Option<CrossMsgTx>> {
+        let Some(receipt) = self
+            .ipc_contract_info
+            .provider
+            .get_transaction_receipt(H256::from(*tx_hash))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let Some(height) = receipt.block_number else {
+            return Ok(None);
+        };
+
+        let mut top_down = vec![];
+        let mut bottom_up = 0;
+        for log in receipt.logs {
+            if log.address != self.ipc_contract_info.gateway_addr {
+                continue;
+            }
+            if let Ok(event) =
+                ethers_contract::parse_log::<lib_gateway::NewTopDownMessageFilter>(log.clone())
+            {
+                top_down.push(IpcEnvelope::try_from(event.message)?);
+            } else if ethers_contract::parse_log::<lib_gateway::QueuedBottomUpMessageFilter>(log)
+                .is_ok()
+            {
+                bottom_up += 1;
+            }
+        }
+
+        Ok(Some(CrossMsgTx {
+            height: height.as_u64() as ChainEpoch,
+            top_down,
+            bottom_up,
+        }))
+    }
+
+    async fn applied_top_down_nonce(&self) -> Result<u64> {
+        let contract = gateway_getter_facet::GatewayGetterFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        Ok(contract.applied_top_down_nonce().call().await?)
+    }
+
+    async fn top_down_nonce(&self, subnet: &SubnetID) -> Result<u64> {
+        self.get_applied_top_down_nonce(subnet).await
+    }
+
+    async fn bootstrap_status(&self, subnet: &SubnetID) -> Result<BootstrapStatus> {
+        let address = contract_address_from_subnet(subnet)?;
+        let contract = subnet_actor_getter_facet::SubnetActorGetterFacet::new(
+            address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        let bootstrapped = contract.bootstrapped().call().await?;
+        let genesis_epoch = if bootstrapped {
+            Some(self.genesis_epoch(subnet).await?)
+        } else {
+            None
+        };
+
+        Ok(BootstrapStatus {
+            bootstrapped,
+            validators: contract.get_total_validators_number().call().await? as u64,
+            min_validators: contract.min_validators().call().await?,
+            collateral: eth_to_fil_amount(&contract.get_total_collateral().call().await?)?,
+            min_collateral: eth_to_fil_amount(&contract.min_activation_collateral().call().await?)?,
+            genesis_epoch,
+        })
+    }
+
+    async fn permission_mode(&self, subnet: &SubnetID) -> Result<PermissionMode> {
+        let address = contract_address_from_subnet(subnet)?;
+        let contract = subnet_actor_getter_facet::SubnetActorGetterFacet::new(
+            address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        match contract.permission_mode().call().await? {
+            0 => Ok(PermissionMode::Collateral),
+            1 => Ok(PermissionMode::Federated),
+            2 => Ok(PermissionMode::Static),
+            mode => Err(anyhow!("unknown permission mode {mode} in subnet {subnet}")),
+        }
+    }
+
+    async fn stake_history(
+        &self,
+        subnet: &SubnetID,
+        validator: &Address,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<StakeHistoryEntry>> {
+        let address = contract_address_from_subnet(subnet)?;
+        let contract = subnet_actor_manager_facet::SubnetActorManagerFacet::new(
+            address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        let ev = contract
+            .event::<lib_staking_change_log::NewStakingChangeRequestFilter>()
+            .from_block(from as u64)
+            .to_block(to as u64)
+            .address(ValueOrArray::Value(contract.address()));
+
+        // the validator is not an indexed field of the event, so filter here
+        let validator = payload_to_evm_address(validator.payload())?;
+
+        let mut history = vec![];
+        for (event, meta) in query_with_meta(ev, contract.client()).await? {
+            if event.validator != validator {
+                continue;
+            }
+            history.push(StakeHistoryEntry {
+                height: meta.block_number.as_u64() as ChainEpoch,
+                tx_hash: meta.transaction_hash.0,
+                change: StakingChangeRequest::try_from(event)?,
+            });
+        }
+        Ok(history)
+    }
+
+    async fn top_down_msg_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<(IpcEnvelope, TxRef)>> {
+        let contract = gateway_manager_facet::GatewayManagerFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let ev = contract
+            .event::<lib_gateway::NewTopDownMessageFilter>()
+            .from_block(from as u64)
+            .to_block(to as u64)
+            .topic1(contract_address_from_subnet(subnet)?)
+            .address(ValueOrArray::Value(contract.address()));
+
+        let mut timestamps = HashMap::new();
+        let mut msgs = vec![];
+        for (event, meta) in query_with_meta(ev, contract.client()).await? {
+            let tx = self.tx_ref(&meta, &mut timestamps).await?;
+            msgs.push((IpcEnvelope::try_from(event.message)?, tx));
+        }
+        Ok(msgs)
+    }
+
+    async fn bottom_up_msg_txs(
+        &self,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<HashMap<[u8; 32], TxRef>> {
+        let contract = gateway_manager_facet::GatewayManagerFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let ev = contract
+            .event::<lib_gateway::QueuedBottomUpMessageFilter>()
+            .from_block(from as u64)
+            .to_block(to as u64)
+            .address(ValueOrArray::Value(contract.address()));
+
+        let mut timestamps = HashMap::new();
+        let mut txs = HashMap::new();
+        for (event, meta) in query_with_meta(ev, contract.client()).await? {
+            let tx = self.tx_ref(&meta, &mut timestamps).await?;
+            txs.insert(event.id, tx);
+        }
+        Ok(txs)
+    }
+
+    async fn checkpoint_commit_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<(ChainEpoch, TxRef)>> {
+        let contract = checkpointing_facet::CheckpointingFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+        let ev = contract
+            .event::<checkpointing_facet::CheckpointCommittedFilter>()
+            .from_block(from as u64)
+            .to_block(to as u64)
+            .topic1(contract_address_from_subnet(subnet)?)
+            .address(ValueOrArray::Value(contract.address()));
+
+        let mut timestamps = HashMap::new();
+        let mut commits = vec![];
+        for (event, meta) in query_with_meta(ev, contract.client()).await? {
+            let tx = self.tx_ref(&meta, &mut timestamps).await?;
+            commits.push((event.subnet_height.as_u64() as ChainEpoch, tx));
+        }
+        Ok(commits)
+    }
 }
 
 #[async_trait]
@@ -1035,7 +1265,7 @@ impl EthSubnetManager {
         gateway_addr: ethers::types::Address,
         registry_addr: ethers::types::Address,
         chain_id: u64,
-        provider: Provider<Http>,
+        provider: Provider<RecordingHttp>,
         keystore: Option<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>,
     ) -> Self {
         Self {
@@ -1046,7 +1276,144 @@ impl EthSubnetManager {
                 chain_id,
                 provider,
             },
+            fee_policy: FeePolicy::default(),
+        }
+    }
+
+    /// The transaction of an event log, with the timestamp of its block. Timestamps are cached
+    /// in `timestamps` by height, as the events of a query often share blocks.
+    async fn tx_ref(
+        &self,
+        meta: &LogMeta,
+        timestamps: &mut HashMap<ChainEpoch, u64>,
+    ) -> Result<TxRef> {
+        let height = meta.block_number.as_u64() as ChainEpoch;
+        let timestamp = match timestamps.get(&height) {
+            Some(timestamp) => *timestamp,
+            None => {
+                let block = self
+                    .ipc_contract_info
+                    .provider
+                    .get_block(meta.block_hash)
+                    .await?
+                    .ok_or_else(|| anyhow!("block {height} does not exist"))?;
+                let timestamp = block.timestamp.as_u64();
+                timestamps.insert(height, timestamp);
+                timestamp
+            }
+        };
+        Ok(TxRef {
+            height,
+            tx_hash: meta.transaction_hash.0,
+            timestamp,
+        })
+    }
+
+    /// Records every JSON-RPC call this manager makes with `recorder`.
+    pub fn with_rpc_recorder(mut self, recorder: Arc<RpcRecorder>) -> Self {
+        let client = self
+            .ipc_contract_info
+            .provider
+            .as_ref()
+            .clone()
+            .with_recorder(recorder);
+        self.ipc_contract_info.provider = Provider::new(client).interval(ETH_PROVIDER_POLLING_TIME);
+        self
+    }
+
+    /// The membership in the gateway at `height`, or at the head if no height is given. Reading
+    /// past heights needs an endpoint that keeps historical state.
+    async fn gateway_membership(&self, height: Option<ChainEpoch>) -> Result<Vec<Validator>> {
+        let contract = gateway_getter_facet::GatewayGetterFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        let mut call = contract.get_current_membership();
+        if let Some(height) = height {
+            call = call.block(height as u64);
+        }
+
+        call.call()
+            .await?
+            .validators
+            .into_iter()
+            .map(|v| {
+                Ok(Validator {
+                    addr: ethers_address_to_fil_address(&v.addr)?,
+                    weight: eth_to_fil_amount(&v.weight)?,
+                    metadata: v.metadata.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// The active and waiting validators of `subnet`, at `height` of the parent if given.
+    async fn validators_at(
+        &self,
+        subnet: &SubnetID,
+        height: Option<ChainEpoch>,
+    ) -> Result<Vec<(Address, ValidatorInfo)>> {
+        let address = contract_address_from_subnet(subnet)?;
+        let contract = subnet_actor_getter_facet::SubnetActorGetterFacet::new(
+            address,
+            Arc::new(self.ipc_contract_info.provider.clone()),
+        );
+
+        let mut addresses: Vec<Address> = vec![];
+        let mut validators: Vec<ValidatorInfo> = vec![];
+
+        let active = at_height(contract.get_active_validators(), height)
+            .call()
+            .await?;
+        addresses.extend(
+            active
+                .iter()
+                .map(ethers_address_to_fil_address)
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        for addr in active {
+            let info = at_height(contract.get_validator(addr), height)
+                .call()
+                .await?;
+            validators.push(ValidatorInfo {
+                staking: ValidatorStakingInfo::try_from(info)?,
+                is_active: true,
+                is_waiting: false,
+            });
+        }
+        let waiting = at_height(contract.get_waiting_validators(), height)
+            .call()
+            .await?;
+        addresses.extend(
+            waiting
+                .iter()
+                .map(ethers_address_to_fil_address)
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        for addr in waiting {
+            let info = at_height(contract.get_validator(addr), height)
+                .call()
+                .await?;
+            validators.push(ValidatorInfo {
+                staking: ValidatorStakingInfo::try_from(info)?,
+                is_active: false,
+                is_waiting: true,
+            });
         }
+
+        Ok(addresses.into_iter().zip(validators).collect())
+    }
+
+    /// Applies the cost controls of the subnet config to the transactions sent by this manager.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.fee_policy = fee_policy;
+        self
+    }
+
+    /// The number of blocks to wait for on top of the one including a transaction.
+    fn confirmations(&self) -> usize {
+        self.fee_policy.confirmations.unwrap_or(1)
     }
 
     /// This method handles the "msg.value" based on different collateral/supply source
@@ -1089,6 +1456,182 @@ impl EthSubnetManager {
             .ok_or(anyhow!("no evm keystore available"))
     }
 
+    /// Builds the registry call that deploys a new subnet actor.
+    async fn new_subnet_actor_call(
+        &self,
+        from: &Address,
+        params: ConstructParams,
+    ) -> Result<ContractCall<SignerWithFeeEstimatorMiddleware, ethers::types::Address>> {
+        self.ensure_same_gateway(&params.ipc_gateway_addr)?;
+
+        let min_validator_stake = params
+            .min_validator_stake
+            .atto()
+            .to_u128()
+            .ok_or_else(|| anyhow!("invalid min validator stake"))?;
+
+        tracing::debug!("calling create subnet for EVM manager");
+
+        let route = subnet_id_to_evm_addresses(&params.parent)?;
+        tracing::debug!("root SubnetID as Ethereum type: {route:?}");
+
+        let params = register_subnet_facet::ConstructorParams {
+            parent_id: register_subnet_facet::SubnetID {
+                root: params.parent.root_id(),
+                route,
+            },
+            ipc_gateway_addr: self.ipc_contract_info.gateway_addr,
+            consensus: params.consensus as u64 as u8,
+            min_activation_collateral: ethers::types::U256::from(min_validator_stake),
+            min_validators: params.min_validators,
+            bottom_up_check_period: params.bottomup_check_period as u64,
+            majority_percentage: SUBNET_MAJORITY_PERCENTAGE,
+            active_validators_limit: params.active_validators_limit,
+            power_scale: 3,
+            permission_mode: params.permission_mode as u8,
+            supply_source: register_subnet_facet::Asset::try_from(params.supply_source)?,
+            collateral_source: register_subnet_facet::Asset::try_from(params.collateral_source)?,
+            validator_gater: payload_to_evm_address(params.validator_gater.payload())?,
+            validator_rewarder: payload_to_evm_address(params.validator_rewarder.payload())?,
+        };
+
+        tracing::info!("creating subnet on evm with params: {params:?}");
+
+        let signer = Arc::new(self.get_signer_with_fee_estimator(from)?);
+        let registry_contract = register_subnet_facet::RegisterSubnetFacet::new(
+            self.ipc_contract_info.registry_addr,
+            signer,
+        );
+
+        extend_call_with_pending_block(registry_contract.new_subnet_actor(params)).await
+    }
+
+    /// Builds the subnet actor call that joins `from` as a validator.
+    async fn join_call(
+        &self,
+        subnet: &SubnetID,
+        from: &Address,
+        collateral: TokenAmount,
+        pub_key: Vec<u8>,
+    ) -> Result<ContractCall<SignerWithFeeEstimatorMiddleware, ()>> {
+        let collateral = collateral
+            .atto()
+            .to_u128()
+            .ok_or_else(|| anyhow!("invalid min validator stake"))?;
+
+        let address = contract_address_from_subnet(subnet)?;
+        tracing::info!(
+            "interacting with evm subnet contract: {address:} with collateral: {collateral:}"
+        );
+
+        let signer = Arc::new(self.get_signer_with_fee_estimator(from)?);
+        let contract = subnet_actor_manager_facet::SubnetActorManagerFacet::new(address, signer);
+
+        let mut txn = contract.join(ethers::types::Bytes::from(pub_key), U256::from(collateral));
+        txn = self.handle_txn_token(subnet, txn, collateral, 0).await?;
+
+        extend_call_with_pending_block(txn).await
+    }
+
+    /// Builds the subnet actor call that adds collateral for `from`.
+    async fn stake_call(
+        &self,
+        subnet: &SubnetID,
+        from: &Address,
+        collateral: TokenAmount,
+    ) -> Result<ContractCall<SignerWithFeeEstimatorMiddleware, ()>> {
+        let collateral = collateral
+            .atto()
+            .to_u128()
+            .ok_or_else(|| anyhow!("invalid collateral amount"))?;
+
+        let address = contract_address_from_subnet(subnet)?;
+        tracing::info!(
+            "interacting with evm subnet contract: {address:} with collateral: {collateral:}"
+        );
+
+        let signer = Arc::new(self.get_signer_with_fee_estimator(from)?);
+        let contract = subnet_actor_manager_facet::SubnetActorManagerFacet::new(address, signer);
+
+        let mut txn = contract.stake(U256::from(collateral));
+        txn = self.handle_txn_token(subnet, txn, collateral, 0).await?;
+
+        extend_call_with_pending_block(txn).await
+    }
+
+    /// Builds the gateway call that funds `to` in the child `subnet`.
+    async fn fund_call(
+        &self,
+        subnet: &SubnetID,
+        from: &Address,
+        to: &Address,
+        amount: TokenAmount,
+    ) -> Result<ContractCall<SignerWithFeeEstimatorMiddleware, ()>> {
+        let value = amount
+            .atto()
+            .to_u128()
+            .ok_or_else(|| anyhow!("invalid value to fund"))?;
+
+        tracing::info!(
+            "fund with evm gateway contract: {:} with value: {value:}, original: {amount:?}",
+            self.ipc_contract_info.gateway_addr
+        );
+
+        let evm_subnet_id = gateway_manager_facet::SubnetID::try_from(subnet)?;
+        tracing::debug!("evm subnet id to fund: {evm_subnet_id:?}");
+
+        let signer = Arc::new(self.get_signer_with_fee_estimator(from)?);
+        let gateway_contract = gateway_manager_facet::GatewayManagerFacet::new(
+            self.ipc_contract_info.gateway_addr,
+            signer,
+        );
+
+        let mut txn = gateway_contract.fund(
+            evm_subnet_id,
+            gateway_manager_facet::FvmAddress::try_from(*to)?,
+        );
+        txn.tx.set_value(value);
+        extend_call_with_pending_block(txn).await
+    }
+
+    /// Returns the ERC20 token registered as the supply source of `subnet`, or an error if the
+    /// subnet is funded with the native coin.
+    async fn erc20_supply_source(&self, subnet: &SubnetID) -> Result<ethers::types::Address> {
+        let supply_source = self.get_subnet_supply_source(subnet).await?;
+        if supply_source.kind != AssetKind::ERC20 {
+            return Err(anyhow!("Invalid operation: Expected the subnet's supply source to be ERC20, but found a different kind."));
+        }
+
+        payload_to_evm_address(
+            supply_source
+                .token_address
+                .ok_or_else(|| anyhow!("zero adress not erc20"))?
+                .payload(),
+        )
+    }
+
+    /// Estimates the gas and current EIP-1559 fees of a call without sending it.
+    async fn estimate_call<D: ethers::abi::Detokenize>(
+        &self,
+        call: ContractCall<SignerWithFeeEstimatorMiddleware, D>,
+    ) -> Result<FeeEstimate> {
+        let gas_limit = call.estimate_gas().await?;
+        let fees = call
+            .client
+            .inner()
+            .estimate_gas_fees()
+            .await
+            .map_err(|e| anyhow!("cannot estimate gas fees: {e}"))?;
+        let value = call.tx.value().cloned().unwrap_or_default();
+
+        Ok(FeeEstimate {
+            gas_limit: gas_limit.as_u64(),
+            max_fee_per_gas: eth_to_fil_amount(&fees.max_fee_per_gas)?,
+            max_priority_fee_per_gas: eth_to_fil_amount(&fees.max_priority_fee_per_gas)?,
+            value: eth_to_fil_amount(&value)?,
+        })
+    }
+
     /// Get the ethers singer instance.
     /// We use filecoin addresses throughout our whole code-base
     /// and translate them to evm addresses when relevant.
@@ -1108,8 +1651,15 @@ impl EthSubnetManager {
 
         use super::gas_estimator_middleware::Eip1559GasEstimatorMiddleware;
 
+        let sender = wallet.address();
         let signer = SignerMiddleware::new(self.ipc_contract_info.provider.clone(), wallet);
-        Ok(Eip1559GasEstimatorMiddleware::new(signer))
+        let signer =
+            Eip1559GasEstimatorMiddleware::new(signer).with_fee_policy(self.fee_policy.clone());
+        Ok(NonceTrackingMiddleware::new(
+            signer,
+            self.ipc_contract_info.chain_id,
+            sender,
+        ))
     }
 
     pub fn from_subnet_with_wallet_store(
@@ -1117,14 +1667,13 @@ impl EthSubnetManager {
         keystore: Option<Arc<RwLock<PersistentKeyStore<EthKeyAddress>>>>,
     ) -> Result<Self> {
         let url = subnet.rpc_http().clone();
-        let auth_token = subnet.auth_token();
 
         let SubnetConfig::Fevm(config) = &subnet.config;
 
         let mut client = Client::builder();
 
-        if let Some(auth_token) = auth_token {
-            let auth = Authorization::Bearer(auth_token);
+        if let Some(auth) = subnet.auth() {
+            let auth = crate::auth::authorization(&auth)?;
             let mut auth_value = HeaderValue::from_str(&auth.to_string())?;
             auth_value.set_sensitive(true);
 
@@ -1138,9 +1687,32 @@ impl EthSubnetManager {
             client = client.timeout(timeout);
         }
 
+        if let Some(proxy) = subnet.proxy() {
+            client = client.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
+        if let Some(tls) = subnet.tls() {
+            client = with_tls(client, tls)?;
+        }
+
+        if let Some(pool) = subnet.pool() {
+            if let Some(max_idle) = pool.max_idle {
+                client = client.pool_max_idle_per_host(max_idle);
+            }
+            if let Some(secs) = pool.idle_timeout_secs {
+                client = client.pool_idle_timeout(Duration::from_secs(secs));
+            }
+            if let Some(secs) = pool.tcp_keepalive_secs {
+                client = client.tcp_keepalive(Duration::from_secs(secs));
+            }
+        }
+
         let client = client.build()?;
 
-        let provider = Http::new_with_client(url, client);
+        let mut provider = RecordingHttp::new(Http::new_with_client(url.clone(), client), url);
+        if let Some(max) = subnet.pool().and_then(|p| p.max_concurrent_requests) {
+            provider = provider.with_max_concurrent_requests(max);
+        }
 
         let mut provider = Provider::new(provider);
         // set polling interval for provider to fit fast child subnets block times.
@@ -1156,8 +1728,38 @@ impl EthSubnetManager {
             subnet.id.chain_id(),
             provider,
             keystore,
-        ))
+        )
+        .with_fee_policy(subnet.fee_policy()))
+    }
+}
+
+/// Configures the client certificate and the extra root certificate of an endpoint.
+fn with_tls(mut client: reqwest::ClientBuilder, tls: &TlsConfig) -> Result<reqwest::ClientBuilder> {
+    let read = |path: &std::path::PathBuf| {
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))
+    };
+
+    match (&tls.client_cert, &tls.client_key) {
+        (Some(cert), Some(key)) => {
+            let identity = reqwest::Identity::from_pkcs8_pem(&read(cert)?, &read(key)?)
+                .context("invalid tls client certificate or key")?;
+            client = client.identity(identity);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(anyhow!(
+                "tls client_cert and client_key must be set together"
+            ))
+        }
+    }
+
+    if let Some(ca) = &tls.ca {
+        let certificate =
+            reqwest::Certificate::from_pem(&read(ca)?).context("invalid tls ca certificate")?;
+        client = client.add_root_certificate(certificate);
     }
+
+    Ok(client)
 }
 
 #[async_trait]
@@ -1195,7 +1797,10 @@ impl BottomUpCheckpointRelayer for EthSubnetManager {
         let call = extend_call_with_pending_block(call).await?;
 
         let pending_tx = call.send().await?;
-        let receipt = pending_tx.retries(TRANSACTION_RECEIPT_RETRIES).await?;
+        let receipt = pending_tx
+            .retries(TRANSACTION_RECEIPT_RETRIES)
+            .confirmations(self.confirmations())
+            .await?;
         block_number_from_receipt(receipt)
     }
 
@@ -1281,6 +1886,19 @@ impl BottomUpCheckpointRelayer for EthSubnetManager {
 
         Ok(events)
     }
+
+    async fn current_membership(&self) -> Result<Vec<Validator>> {
+        self.gateway_membership(None).await
+    }
+
+    async fn power_table_at(&self, height: ChainEpoch) -> Result<Vec<Validator>> {
+        self.gateway_membership(Some(height)).await
+    }
+
+    async fn balance(&self, address: &Address) -> Result<TokenAmount> {
+        SubnetManager::wallet_balance(self, address).await
+    }
+
     async fn current_epoch(&self) -> Result<ChainEpoch> {
         let epoch = self
             .ipc_contract_info
@@ -1290,6 +1908,19 @@ impl BottomUpCheckpointRelayer for EthSubnetManager {
             .as_u64();
         Ok(epoch as ChainEpoch)
     }
+
+    async fn base_fee(&self) -> Result<TokenAmount> {
+        let block = self
+            .ipc_contract_info
+            .provider
+            .get_block(ethers::types::BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| anyhow!("latest block not found"))?;
+        let base_fee = block
+            .base_fee_per_gas
+            .ok_or_else(|| anyhow!("latest block has no base fee"))?;
+        eth_to_fil_amount(&base_fee)
+    }
 }
 
 lazy_static!(
@@ -1495,6 +2126,42 @@ where
     Ok(call.block(ethers::types::BlockNumber::Pending))
 }
 
+/// Reads `call` at `height` rather than at the latest block, if given.
+fn at_height<B, D, M>(
+    call: ethers_contract::FunctionCall<B, D, M>,
+    height: Option<ChainEpoch>,
+) -> ethers_contract::FunctionCall<B, D, M>
+where
+    B: std::borrow::Borrow<D>,
+    M: ethers::abi::Detokenize,
+{
+    match height {
+        Some(height) => call.block(height as u64),
+        None => call,
+    }
+}
+
+/// Get the address of the subnet actor deployed in the transaction receipt
+fn deployed_subnet_address(receipt: ethers::types::TransactionReceipt) -> Result<Address> {
+    for log in receipt.logs {
+        tracing::debug!("log: {log:?}");
+
+        match ethers_contract::parse_log::<register_subnet_facet::SubnetDeployedFilter>(log) {
+            Ok(subnet_deploy) => {
+                let register_subnet_facet::SubnetDeployedFilter { subnet_addr } = subnet_deploy;
+
+                tracing::debug!("subnet deployed at {subnet_addr:?}");
+                return ethers_address_to_fil_address(&subnet_addr);
+            }
+            Err(_) => {
+                tracing::debug!("no event for subnet actor published yet, continue");
+                continue;
+            }
+        }
+    }
+    Err(anyhow!("no logs receipt"))
+}
+
 /// Get the block number from the transaction receipt
 fn block_number_from_receipt(
     receipt: Option<ethers::types::TransactionReceipt>,
@@ -3,6 +3,8 @@
 
 mod gas_estimator_middleware;
 mod manager;
+mod nonce_middleware;
+mod rpc_recorder;
 
 use async_trait::async_trait;
 use fvm_shared::clock::ChainEpoch;
@@ -10,6 +12,7 @@ use ipc_api::subnet_id::SubnetID;
 
 use super::subnet::SubnetManager;
 pub use manager::EthSubnetManager;
+pub use rpc_recorder::RpcRecorder;
 
 use ipc_actors_abis::subnet_actor_checkpointing_facet;
 
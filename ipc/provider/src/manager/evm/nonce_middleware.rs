@@ -0,0 +1,288 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+
+//! Nonce allocation shared by every EVM sender in the process.
+//!
+//! Subnet managers are created per call, so a relayer submitting checkpoints and claiming rewards
+//! from the same key would otherwise race on `eth_getTransactionCount` and reuse nonces. All
+//! signers built by the manager go through [`NonceTrackingMiddleware`], which reserves nonces from
+//! a single table keyed by chain id and sender address.
+//!
+//! The middleware must wrap the gas estimator rather than sit beneath it: the estimator fills the
+//! transaction through `SignerMiddleware::fill_transaction`, which sets the nonce from
+//! `eth_getTransactionCount` if none was assigned yet.
+
+use async_trait::async_trait;
+use ethers::{
+    core::types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, H160, U256},
+    providers::{Middleware, MiddlewareError, PendingTransaction},
+};
+use ipc_observability::lazy_static;
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+/// How long after the last accepted transaction the node must keep reporting fewer transactions
+/// than we handed out before the missing nonces are considered dropped and handed out again. Nodes
+/// may accept a transaction before counting it in `eth_getTransactionCount`.
+const GAP_REPAIR_DELAY: Duration = Duration::from_secs(120);
+
+lazy_static! {
+    static ref NONCES: Mutex<HashMap<(u64, H160), NonceState>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Error, Debug)]
+pub enum NonceTrackingError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> MiddlewareError for NonceTrackingError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        NonceTrackingError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            NonceTrackingError::MiddlewareError(e) => Some(e),
+        }
+    }
+}
+
+/// The nonces handed out for a single sender on a single chain.
+#[derive(Debug, Default)]
+struct NonceState {
+    /// The next nonce to hand out, `None` until the first reservation.
+    next: Option<u64>,
+    /// Nonces that were reserved but whose transaction has not been accepted by the node yet.
+    in_flight: BTreeSet<u64>,
+    /// When the node last accepted one of the reserved nonces.
+    last_accepted: Option<Instant>,
+}
+
+impl NonceState {
+    /// Reserves the next nonce given the pending transaction count reported by the chain.
+    fn reserve(&mut self, chain_pending: u64, now: Instant) -> u64 {
+        let settled = self
+            .last_accepted
+            .map_or(true, |at| now.duration_since(at) >= GAP_REPAIR_DELAY);
+        let next = match self.next {
+            // Someone else (another process, a wallet) used this key: skip past their nonces.
+            Some(next) if next < chain_pending => chain_pending,
+            // Nothing is in flight and the node still knows fewer transactions than we handed out
+            // well after accepting the last one: some of them were dropped, so fill the gap
+            // instead of queueing behind it forever.
+            Some(next) if next > chain_pending && self.in_flight.is_empty() && settled => {
+                chain_pending
+            }
+            Some(next) => next,
+            None => chain_pending,
+        };
+
+        self.next = Some(next + 1);
+        self.in_flight.insert(next);
+        next
+    }
+
+    /// Marks a reserved nonce as resolved. A nonce whose transaction was rejected is handed out
+    /// again if nothing was reserved after it.
+    fn release(&mut self, nonce: u64, accepted: bool, now: Instant) {
+        self.in_flight.remove(&nonce);
+        if accepted {
+            self.last_accepted = Some(now);
+        } else if self.next == Some(nonce + 1) {
+            self.next = Some(nonce);
+        }
+    }
+}
+
+/// Assigns nonces from the process-wide table to the transactions of a single sender.
+#[derive(Debug)]
+pub struct NonceTrackingMiddleware<M: Middleware> {
+    inner: M,
+    chain_id: u64,
+    sender: H160,
+}
+
+impl<M: Middleware> NonceTrackingMiddleware<M> {
+    pub fn new(inner: M, chain_id: u64, sender: H160) -> Self {
+        Self {
+            inner,
+            chain_id,
+            sender,
+        }
+    }
+
+    async fn reserve_nonce(&self) -> Result<u64, NonceTrackingError<M>> {
+        // Query before taking the lock, so a slow endpoint does not hold up every other sender.
+        // Concurrent senders that read the same count are told apart by the reserved nonces.
+        let chain_pending = self
+            .inner
+            .get_transaction_count(self.sender, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(NonceTrackingError::MiddlewareError)?;
+
+        Ok(NONCES
+            .lock()
+            .await
+            .entry((self.chain_id, self.sender))
+            .or_default()
+            .reserve(chain_pending.as_u64(), Instant::now()))
+    }
+
+    async fn release_nonce(&self, nonce: u64, accepted: bool) {
+        if let Some(state) = NONCES.lock().await.get_mut(&(self.chain_id, self.sender)) {
+            state.release(nonce, accepted, Instant::now());
+        }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for NonceTrackingMiddleware<M> {
+    type Error = NonceTrackingError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Sends the transaction with a nonce reserved from the shared table, unless the caller
+    /// already set one explicitly.
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+
+        if tx.nonce().is_some() {
+            return self
+                .inner()
+                .send_transaction(tx, block)
+                .await
+                .map_err(NonceTrackingError::MiddlewareError);
+        }
+
+        let nonce = self.reserve_nonce().await?;
+        tx.set_nonce(U256::from(nonce));
+
+        let result = self.inner().send_transaction(tx, block).await;
+        self.release_nonce(nonce, result.is_ok()).await;
+
+        result.map_err(NonceTrackingError::MiddlewareError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NonceState, NonceTrackingMiddleware, GAP_REPAIR_DELAY, NONCES};
+    use crate::manager::evm::gas_estimator_middleware::Eip1559GasEstimatorMiddleware;
+    use ethers::middleware::SignerMiddleware;
+    use ethers::providers::{Middleware, Provider};
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::{Address, Eip1559TransactionRequest, H256};
+    use std::time::Instant;
+
+    const CHAIN_ID: u64 = 314159;
+
+    #[test]
+    fn test_reserve_sequential_nonces() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        assert_eq!(state.reserve(5, now), 5);
+        // the node has not seen the first transaction yet
+        assert_eq!(state.reserve(5, now), 6);
+        assert_eq!(state.reserve(6, now), 7);
+    }
+
+    #[test]
+    fn test_rejected_nonce_is_reused() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        let nonce = state.reserve(3, now);
+        state.release(nonce, false, now);
+        assert_eq!(state.reserve(3, now), 3);
+    }
+
+    #[test]
+    fn test_skips_nonces_used_elsewhere() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        let nonce = state.reserve(0, now);
+        state.release(nonce, true, now);
+        assert_eq!(state.reserve(4, now), 4);
+    }
+
+    #[test]
+    fn test_repairs_gap_from_dropped_transactions() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        for chain_pending in 10..13 {
+            let nonce = state.reserve(chain_pending, now);
+            state.release(nonce, true, now);
+        }
+        // the last two transactions were dropped from the mempool
+        assert_eq!(state.reserve(11, now + GAP_REPAIR_DELAY), 11);
+    }
+
+    #[test]
+    fn test_no_gap_repair_before_node_counts_accepted() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        let nonce = state.reserve(10, now);
+        state.release(nonce, true, now);
+        // the node accepted the transaction but does not count it as pending yet
+        assert_eq!(state.reserve(10, now), 11);
+    }
+
+    #[test]
+    fn test_no_gap_repair_while_in_flight() {
+        let now = Instant::now();
+        let mut state = NonceState::default();
+        let first = state.reserve(10, now);
+        assert_eq!(state.reserve(10, now + GAP_REPAIR_DELAY), first + 1);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_assigned_through_signer_stack() {
+        let (provider, mock) = Provider::mocked();
+        let wallet = LocalWallet::from_bytes(&[7u8; 32])
+            .unwrap()
+            .with_chain_id(CHAIN_ID);
+        let sender = wallet.address();
+        let signer = SignerMiddleware::new(provider, wallet);
+        let client = NonceTrackingMiddleware::new(
+            Eip1559GasEstimatorMiddleware::new(signer),
+            CHAIN_ID,
+            sender,
+        );
+
+        for _ in 0..2 {
+            // Each send queries the pending count once and submits the raw transaction once. The
+            // same 32 byte value answers both calls: a count of 5 and a transaction hash.
+            let count_or_hash = H256::from_low_u64_be(5);
+            mock.push(count_or_hash).unwrap();
+            mock.push(count_or_hash).unwrap();
+
+            let tx = Eip1559TransactionRequest::new()
+                .from(sender)
+                .to(Address::zero())
+                .chain_id(CHAIN_ID)
+                .gas(21_000)
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(1);
+            client.send_transaction(tx, None).await.unwrap();
+        }
+
+        // Both nonces came from the shared table rather than from `eth_getTransactionCount` in
+        // the signer, and the second one was not reissued although the node still reports 5.
+        let nonces = NONCES.lock().await;
+        let state = nonces.get(&(CHAIN_ID, sender)).unwrap();
+        assert_eq!(state.next, Some(7));
+        assert!(state.in_flight.is_empty());
+    }
+}
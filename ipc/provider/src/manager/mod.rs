@@ -3,8 +3,10 @@
 pub use crate::lotus::message::ipc::SubnetInfo;
 pub use evm::{EthManager, EthSubnetManager};
 pub use subnet::{
-    BottomUpCheckpointRelayer, GetBlockHashResult, SubnetGenesisInfo, SubnetManager,
-    TopDownFinalityQuery, TopDownQueryPayload,
+    BootstrapStatus, BottomUpCheckpointRelayer, Capabilities, Capability, CreatedSubnet,
+    CrossMsgTx, FeeEstimate, GetBlockHashResult, Operation, StakeHistoryEntry, SubnetCreation,
+    SubnetGenesisInfo, SubnetManager, TokenInfo, TopDownFinalityQuery, TopDownQueryPayload, TxRef,
+    UnsupportedCapability,
 };
 
 pub mod evm;
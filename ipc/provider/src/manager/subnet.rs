@@ -3,6 +3,7 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use fvm_shared::bigint::BigInt;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::{address::Address, econ::TokenAmount};
 use ipc_actors_abis::subnet_actor_activity_facet::ValidatorClaim;
@@ -15,7 +16,10 @@ use ipc_api::staking::{StakingChangeRequest, ValidatorInfo};
 use ipc_api::subnet::{Asset, ConstructParams, PermissionMode};
 use ipc_api::subnet_id::SubnetID;
 use ipc_api::validator::Validator;
-use std::collections::{BTreeMap, HashMap};
+use num_traits::Signed;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 use crate::lotus::message::ipc::SubnetInfo;
 
@@ -26,9 +30,20 @@ pub trait SubnetManager:
 {
     /// Deploys a new subnet actor on the `parent` subnet and with the
     /// configuration passed in `ConstructParams`.
-    /// The result of the function is the ID address for the subnet actor from which the final
-    /// subnet ID can be inferred.
-    async fn create_subnet(&self, from: Address, params: ConstructParams) -> Result<Address>;
+    /// The result of the function holds the ID address for the subnet actor from which the final
+    /// subnet ID can be inferred, and the transaction that created it.
+    async fn create_subnet(&self, from: Address, params: ConstructParams) -> Result<CreatedSubnet>;
+
+    /// Sends the creation of a subnet without waiting for it to be included. Returns the hash
+    /// of the transaction, to follow with [`SubnetManager::create_subnet_status`].
+    async fn submit_create_subnet(
+        &self,
+        from: Address,
+        params: ConstructParams,
+    ) -> Result<[u8; 32]>;
+
+    /// Where the subnet creation sent in transaction `tx_hash` stands.
+    async fn create_subnet_status(&self, tx_hash: &[u8; 32]) -> Result<SubnetCreation>;
 
     /// Performs the call to join a subnet from a wallet address and staking an amount
     /// of collateral. This function, as well as all of the ones on this trait, can infer
@@ -71,6 +86,10 @@ pub trait SubnetManager:
         gateway_addr: Address,
     ) -> Result<HashMap<SubnetID, SubnetInfo>>;
 
+    /// Lists the cross-net messages waiting in the postbox of the gateway, by their key, to be
+    /// propagated.
+    async fn list_postbox(&self) -> Result<Vec<([u8; 32], IpcEnvelope)>>;
+
     /// Claims any collateral that may be available to claim by validators that
     /// have left the subnet.
     async fn claim_collateral(&self, subnet: SubnetID, from: Address) -> Result<()>;
@@ -157,6 +176,17 @@ pub trait SubnetManager:
     /// Gets the subnet supply source
     async fn get_subnet_supply_source(&self, subnet: &SubnetID) -> Result<Asset>;
 
+    /// Gets the address, symbol and decimals of the ERC20 supply source of the subnet.
+    async fn get_supply_token_info(&self, subnet: &SubnetID) -> Result<TokenInfo>;
+
+    /// Gets the balance of `address` in the ERC20 supply source of the subnet, in the token's
+    /// smallest unit.
+    async fn supply_token_balance(
+        &self,
+        subnet: &SubnetID,
+        address: &Address,
+    ) -> Result<TokenAmount>;
+
     /// Gets the subnet collateral source
     async fn get_subnet_collateral_source(&self, subnet: &SubnetID) -> Result<Asset>;
 
@@ -184,6 +214,13 @@ pub trait SubnetManager:
     /// Lists all the validators
     async fn list_validators(&self, subnet: &SubnetID) -> Result<Vec<(Address, ValidatorInfo)>>;
 
+    /// Lists the validators as they were at `height` of the current subnet, the parent.
+    async fn list_validators_at(
+        &self,
+        subnet: &SubnetID,
+        height: ChainEpoch,
+    ) -> Result<Vec<(Address, ValidatorInfo)>>;
+
     async fn set_federated_power(
         &self,
         from: &Address,
@@ -192,6 +229,292 @@ pub trait SubnetManager:
         public_keys: &[Vec<u8>],
         federated_power: &[u128],
     ) -> Result<ChainEpoch>;
+
+    /// Reports the optional operations supported by this backend, so callers can
+    /// refuse an unsupported operation upfront instead of failing halfway through.
+    fn capabilities(&self) -> Capabilities;
+
+    /// Previews the gas and fees that `from` would pay to execute `operation`, without
+    /// sending anything to the network.
+    async fn estimate(&self, from: Address, operation: Operation) -> Result<FeeEstimate>;
+
+    /// Looks up a transaction and the cross-net messages it committed in the gateway.
+    /// Returns `None` if the transaction is unknown or not yet included in a block.
+    async fn cross_msg_tx(&self, tx_hash: &[u8; 32]) -> Result<Option<CrossMsgTx>>;
+
+    /// The number of top-down messages executed so far by the gateway of the network this
+    /// manager is connected to.
+    async fn applied_top_down_nonce(&self) -> Result<u64>;
+
+    /// The number of top-down messages committed so far for the child `subnet` in the gateway
+    /// of the network this manager is connected to.
+    async fn top_down_nonce(&self, subnet: &SubnetID) -> Result<u64>;
+
+    /// Reports how far a subnet that is not yet bootstrapped is from its activation thresholds.
+    async fn bootstrap_status(&self, subnet: &SubnetID) -> Result<BootstrapStatus>;
+
+    /// How the power of the validators of the subnet is set.
+    async fn permission_mode(&self, subnet: &SubnetID) -> Result<PermissionMode>;
+
+    /// Lists the staking changes of `validator` recorded by the subnet actor between the `from`
+    /// and `to` epochs of the parent, oldest first. Collateral put up before the subnet was
+    /// bootstrapped is not recorded as a change.
+    async fn stake_history(
+        &self,
+        subnet: &SubnetID,
+        validator: &Address,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<StakeHistoryEntry>>;
+
+    /// Lists the top-down messages committed for the child `subnet` between the `from` and `to`
+    /// epochs, with the transactions that sent them.
+    async fn top_down_msg_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<(IpcEnvelope, TxRef)>>;
+
+    /// Lists the transactions that queued bottom-up messages between the `from` and `to` epochs
+    /// of the network this manager is connected to, by the tracing id of the message (see
+    /// [`IpcEnvelope::tracing_id`]).
+    async fn bottom_up_msg_txs(
+        &self,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<HashMap<[u8; 32], TxRef>>;
+
+    /// Lists the heights of the bottom-up checkpoints of the child `subnet` committed between
+    /// the `from` and `to` epochs, with the transactions that committed them.
+    async fn checkpoint_commit_txs(
+        &self,
+        subnet: &SubnetID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<Vec<(ChainEpoch, TxRef)>>;
+}
+
+/// The transaction behind an event, see [`SubnetManager::top_down_msg_txs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxRef {
+    /// The height of the block that included the transaction.
+    pub height: ChainEpoch,
+    pub tx_hash: [u8; 32],
+    /// The timestamp of the block, in seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// A staking change of a validator, see [`SubnetManager::stake_history`].
+#[derive(Debug, Clone)]
+pub struct StakeHistoryEntry {
+    /// The epoch of the parent at which the change was requested.
+    pub height: ChainEpoch,
+    /// The transaction that requested the change.
+    pub tx_hash: [u8; 32],
+    pub change: StakingChangeRequest,
+}
+
+/// The progress of a subnet towards bootstrapping, see [`SubnetManager::bootstrap_status`].
+#[derive(Debug, Clone)]
+pub struct BootstrapStatus {
+    pub bootstrapped: bool,
+    /// The number of validators that joined the subnet.
+    pub validators: u64,
+    pub min_validators: u64,
+    /// The collateral put up by the validators that joined.
+    pub collateral: TokenAmount,
+    pub min_collateral: TokenAmount,
+    /// The epoch of the parent at which the subnet was bootstrapped, once it is.
+    pub genesis_epoch: Option<ChainEpoch>,
+}
+
+/// The progress of a subnet creation, see [`SubnetManager::create_subnet_status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubnetCreation {
+    /// Not included yet, or without enough confirmations.
+    Pending,
+    /// Confirmed, with the address of the new subnet actor.
+    Created(Address),
+    /// Reverted or dropped.
+    Failed(String),
+}
+
+/// A subnet actor deployed by [`SubnetManager::create_subnet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatedSubnet {
+    pub address: Address,
+    /// The transaction that created the subnet.
+    pub tx_hash: [u8; 32],
+    /// The epoch of the parent that included the transaction.
+    pub height: ChainEpoch,
+}
+
+/// The cross-net messages committed by a transaction, see [`SubnetManager::cross_msg_tx`].
+#[derive(Debug, Clone)]
+pub struct CrossMsgTx {
+    /// The height of the block that included the transaction.
+    pub height: ChainEpoch,
+    /// Top-down messages committed for a child subnet.
+    pub top_down: Vec<IpcEnvelope>,
+    /// The number of bottom-up messages queued for the next checkpoint.
+    pub bottom_up: usize,
+}
+
+/// A state changing operation whose cost can be previewed with [`SubnetManager::estimate`].
+#[derive(Debug, Clone)]
+pub enum Operation {
+    CreateSubnet(ConstructParams),
+    Join {
+        subnet: SubnetID,
+        collateral: TokenAmount,
+        public_key: Vec<u8>,
+    },
+    Stake {
+        subnet: SubnetID,
+        collateral: TokenAmount,
+    },
+    Fund {
+        subnet: SubnetID,
+        to: Address,
+        amount: TokenAmount,
+    },
+}
+
+/// The expected cost of an operation, as returned by [`SubnetManager::estimate`].
+#[derive(Debug, Clone)]
+pub struct FeeEstimate {
+    pub gas_limit: u64,
+    pub max_fee_per_gas: TokenAmount,
+    pub max_priority_fee_per_gas: TokenAmount,
+    /// The value transferred along with the transaction, e.g. the collateral.
+    pub value: TokenAmount,
+}
+
+impl FeeEstimate {
+    /// The most the sender can be charged for gas.
+    pub fn max_gas_cost(&self) -> TokenAmount {
+        TokenAmount::from_atto(self.max_fee_per_gas.atto() * self.gas_limit)
+    }
+
+    /// The balance the sender needs to hold for the operation to go through.
+    pub fn max_total_cost(&self) -> TokenAmount {
+        self.max_gas_cost() + self.value.clone()
+    }
+}
+
+/// The metadata of an ERC20 token, to render and parse amounts in the token's own precision.
+/// Amounts of the token are carried in [`TokenAmount`]s holding its smallest unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl TokenInfo {
+    /// Parses a decimal amount of whole tokens, e.g. `1.5`, into the smallest unit of the token.
+    pub fn parse_amount(&self, amount: &str) -> Result<TokenAmount> {
+        let decimals = self.decimals as usize;
+        let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        if fraction.len() > decimals {
+            return Err(anyhow::anyhow!(
+                "{amount} has more than the {decimals} decimals of {}",
+                self.symbol
+            ));
+        }
+        let digits = format!("{whole}{fraction:0<decimals$}");
+        let atto = BigInt::from_str(&digits)
+            .map_err(|e| anyhow::anyhow!("not a token amount {amount}: {e}"))?;
+        if atto.is_negative() {
+            return Err(anyhow::anyhow!("negative token amount {amount}"));
+        }
+        Ok(TokenAmount::from_atto(atto))
+    }
+
+    /// Renders an amount in the smallest unit of the token as whole tokens with its symbol.
+    pub fn format_amount(&self, amount: &TokenAmount) -> String {
+        let decimals = self.decimals as usize;
+        let digits = format!(
+            "{:0>width$}",
+            amount.atto().to_string(),
+            width = decimals + 1
+        );
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        match fraction.is_empty() {
+            true => format!("{whole} {}", self.symbol),
+            false => format!("{whole}.{fraction} {}", self.symbol),
+        }
+    }
+}
+
+/// Operations that not every parent network backend is able to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Capability {
+    FundWithToken,
+    ApproveToken,
+    PreFund,
+    PreRelease,
+    SetFederatedPower,
+    ValidatorRewards,
+}
+
+/// The set of [`Capability`] supported by a subnet manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    supported: HashSet<Capability>,
+}
+
+impl Capabilities {
+    /// A backend that supports every optional operation.
+    pub fn all() -> Self {
+        Self {
+            supported: Capability::iter().collect(),
+        }
+    }
+
+    /// A backend that supports none of the optional operations.
+    pub fn none() -> Self {
+        Self {
+            supported: HashSet::new(),
+        }
+    }
+
+    pub fn with(mut self, capability: Capability) -> Self {
+        self.supported.insert(capability);
+        self
+    }
+
+    pub fn without(mut self, capability: Capability) -> Self {
+        self.supported.remove(&capability);
+        self
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.supported.contains(&capability)
+    }
+
+    /// Returns an error if the capability is not supported by the backend of `subnet`.
+    pub fn ensure(&self, capability: Capability, subnet: &SubnetID) -> Result<()> {
+        if !self.supports(capability) {
+            return Err(UnsupportedCapability {
+                capability,
+                subnet: subnet.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Returned when an operation needs a [`Capability`] the backend of a subnet does not have.
+#[derive(Debug, thiserror::Error)]
+#[error("{capability} is not supported on the parent network of {subnet}")]
+pub struct UnsupportedCapability {
+    pub capability: Capability,
+    pub subnet: SubnetID,
 }
 
 #[derive(Debug)]
@@ -271,8 +594,18 @@ pub trait BottomUpCheckpointRelayer: Send + Sync {
     ) -> Result<Option<BottomUpCheckpointBundle>>;
     /// Queries the signature quorum reached events at target height.
     async fn quorum_reached_events(&self, height: ChainEpoch) -> Result<Vec<QuorumReachedEvent>>;
+    /// The validators of the current membership in the gateway, with the weights used to count
+    /// checkpoint signatures.
+    async fn current_membership(&self) -> Result<Vec<Validator>>;
+    /// The validators of the membership in the gateway at `height`, the power table the
+    /// checkpoint of that height was signed against.
+    async fn power_table_at(&self, height: ChainEpoch) -> Result<Vec<Validator>>;
+    /// The balance of an address, such as the submitter's, in the current subnet.
+    async fn balance(&self, address: &Address) -> Result<TokenAmount>;
     /// Get the current epoch in the current subnet
     async fn current_epoch(&self) -> Result<ChainEpoch>;
+    /// The base fee per gas of the latest block in the current subnet.
+    async fn base_fee(&self) -> Result<TokenAmount>;
 }
 
 /// The validator reward related functions, such as check reward and claim reward for mining blocks
@@ -304,3 +637,41 @@ pub trait ValidatorRewarder: Send + Sync {
         claims: Vec<(u64, ValidatorClaim)>,
     ) -> Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
+
+    use super::TokenInfo;
+
+    #[test]
+    fn test_token_amounts() {
+        let usdc = TokenInfo {
+            address: Address::new_id(100),
+            symbol: "USDC".to_string(),
+            decimals: 6,
+        };
+        assert_eq!(
+            usdc.parse_amount("1.5").unwrap(),
+            TokenAmount::from_atto(1_500_000)
+        );
+        assert_eq!(
+            usdc.parse_amount("2").unwrap(),
+            TokenAmount::from_atto(2_000_000)
+        );
+        assert!(usdc.parse_amount("0.0000001").is_err());
+        assert!(usdc.parse_amount("-1").is_err());
+        assert!(usdc.parse_amount("abc").is_err());
+
+        assert_eq!(
+            usdc.format_amount(&TokenAmount::from_atto(1_500_000)),
+            "1.5 USDC"
+        );
+        assert_eq!(
+            usdc.format_amount(&TokenAmount::from_atto(7)),
+            "0.000007 USDC"
+        );
+        assert_eq!(usdc.format_amount(&TokenAmount::from_atto(0)), "0 USDC");
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Notifications of relayer and syncer events, posted as JSON to webhooks or piped to a local
+//! command, so operators can alert on them without scraping logs.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use fvm_shared::clock::ChainEpoch;
+use serde::{Deserialize, Serialize};
+
+/// How long a webhook has to accept a notification.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where notifications are delivered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// URLs every notification is posted to.
+    #[serde(default)]
+    pub webhooks: Vec<String>,
+    /// A shell command run for every notification, with the JSON on its standard input.
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+impl NotifyConfig {
+    pub fn is_empty(&self) -> bool {
+        self.webhooks.is_empty() && self.command.is_none()
+    }
+}
+
+/// An event worth alerting on. Serialized with the name of the event in the `event` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Notification {
+    CheckpointSubmitted {
+        subnet: String,
+        height: ChainEpoch,
+        parent_epoch: ChainEpoch,
+    },
+    CheckpointFailed {
+        subnet: String,
+        height: ChainEpoch,
+        error: String,
+    },
+    /// The last checkpoint committed in the parent moved up to `height`.
+    CheckpointConfirmed {
+        subnet: String,
+        height: ChainEpoch,
+    },
+    /// A final parent block carries top-down messages, such as deposits, for the subnet.
+    DepositFinalized {
+        parent_height: ChainEpoch,
+        messages: usize,
+    },
+    /// The parent chain has not advanced for `stalled_secs`.
+    SyncStalled {
+        parent_height: ChainEpoch,
+        stalled_secs: u64,
+    },
+    ReorgDetected {
+        parent_height: ChainEpoch,
+    },
+    /// The balance of the relayer's submitter in the parent fell below the configured minimum.
+    LowBalance {
+        subnet: String,
+        address: String,
+        balance: String,
+        minimum: String,
+    },
+}
+
+/// The body that is delivered.
+#[derive(Serialize)]
+struct Envelope<'a> {
+    timestamp: u64,
+    #[serde(flatten)]
+    notification: &'a Notification,
+}
+
+/// Delivers notifications according to a [`NotifyConfig`].
+#[derive(Clone)]
+pub struct Notifier {
+    config: NotifyConfig,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    pub fn new(config: NotifyConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Delivers `notification` in the background. Failures are logged and never hold up the
+    /// caller.
+    pub fn notify(&self, notification: Notification) {
+        if self.config.is_empty() {
+            return;
+        }
+        let body = match to_json(&notification) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("cannot encode notification {notification:?}: {e}");
+                return;
+            }
+        };
+
+        for url in &self.config.webhooks {
+            let request = self
+                .client
+                .post(url)
+                .header("content-type", "application/json")
+                .timeout(WEBHOOK_TIMEOUT)
+                .body(body.clone());
+            let url = url.clone();
+            tokio::spawn(async move {
+                match request.send().await.and_then(|r| r.error_for_status()) {
+                    Ok(_) => tracing::debug!("notification posted to {url}"),
+                    Err(e) => tracing::warn!("cannot post notification to {url}: {e}"),
+                }
+            });
+        }
+
+        if let Some(command) = self.config.command.clone() {
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = run_command(&command, &body) {
+                    tracing::warn!("notification command failed: {e:#}");
+                }
+            });
+        }
+    }
+}
+
+fn to_json(notification: &Notification) -> Result<String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Ok(serde_json::to_string(&Envelope {
+        timestamp,
+        notification,
+    })?)
+}
+
+fn run_command(command: &str, body: &str) -> Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {command}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // commands are free to ignore their input
+        match stdin.write_all(body.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow!("{command} exited with {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_command, to_json, Notification};
+
+    #[test]
+    fn test_notification_json() {
+        let json = to_json(&Notification::CheckpointSubmitted {
+            subnet: "/r314159/t01".to_string(),
+            height: 400,
+            parent_epoch: 1234,
+        })
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["event"], "checkpoint_submitted");
+        assert_eq!(value["height"], 400);
+        assert!(value["timestamp"].is_u64());
+
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.json");
+        run_command(&format!("cat > {}", out.display()), &json).unwrap();
+        assert_eq!(std::fs::read_to_string(out).unwrap(), json);
+        assert!(run_command("exit 3", &json).is_err());
+    }
+}
@@ -5,14 +5,26 @@ use ipc_observability::{
     impl_traceable, impl_traceables, lazy_static, register_metrics, serde::HexEncodableBlockHash,
     Recordable, TraceLevel, Traceable,
 };
-use prometheus::{register_int_gauge, IntGauge, Registry};
+use prometheus::{
+    register_int_counter, register_int_gauge, register_int_gauge_vec, IntCounter, IntGauge,
+    IntGaugeVec, Registry,
+};
 
 register_metrics! {
     BOTTOMUP_CHECKPOINT_FINALIZED_HEIGHT: IntGauge
         = register_int_gauge!("bottomup_checkpoint_finalized_height", "Height of the checkpoint finalized");
+    PROVIDER_CONFIG_RELOADS: IntCounter
+        = register_int_counter!("provider_config_reloads", "Number of times the provider config was reloaded");
+    VALIDATOR_MISSED_CHECKPOINTS: IntGaugeVec = register_int_gauge_vec!(
+        "validator_missed_checkpoints",
+        "Number of the latest checkpoints in a row the validator did not sign",
+        &["validator"]
+    );
 }
 
 impl_traceables!(TraceLevel::Info, "Bottomup", CheckpointSubmitted);
+impl_traceables!(TraceLevel::Info, "Provider", ConfigReloaded);
+impl_traceables!(TraceLevel::Info, "Bottomup", ValidatorLivenessChecked);
 
 #[derive(Debug)]
 pub struct CheckpointSubmitted {
@@ -26,6 +38,31 @@ impl Recordable for CheckpointSubmitted {
     }
 }
 
+#[derive(Debug)]
+pub struct ConfigReloaded {
+    pub subnets: usize,
+}
+
+impl Recordable for ConfigReloaded {
+    fn record_metrics(&self) {
+        PROVIDER_CONFIG_RELOADS.inc();
+    }
+}
+
+#[derive(Debug)]
+pub struct ValidatorLivenessChecked {
+    pub validator: String,
+    pub missed_in_a_row: i64,
+}
+
+impl Recordable for ValidatorLivenessChecked {
+    fn record_metrics(&self) {
+        VALIDATOR_MISSED_CHECKPOINTS
+            .with_label_values(&[&self.validator])
+            .set(self.missed_in_a_row);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
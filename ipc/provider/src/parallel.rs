@@ -0,0 +1,55 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Running many queries at once, such as one per configured subnet or per wallet address, without
+//! sending them all to the endpoints at the same time.
+
+use std::future::Future;
+
+use futures_util::stream::{self, StreamExt};
+
+/// The number of queries run at once by commands that fan out, unless told otherwise. Endpoints
+/// with a `max_concurrent_requests` limit in their config hold back requests above it anyway.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Runs `futures` with at most `limit` of them in progress at once, and returns their outputs in
+/// the order the futures were given.
+pub async fn join_bounded<I, F>(futures: I, limit: usize) -> Vec<F::Output>
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+{
+    stream::iter(futures).buffered(limit.max(1)).collect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::join_bounded;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_join_bounded() {
+        let in_flight = AtomicUsize::new(0);
+        let most_in_flight = AtomicUsize::new(0);
+
+        let outputs = join_bounded(
+            (0..10u64).map(|i| {
+                let in_flight = &in_flight;
+                let most_in_flight = &most_in_flight;
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    most_in_flight.fetch_max(now, Ordering::SeqCst);
+                    // the later futures finish first, the outputs keep their order regardless
+                    tokio::time::sleep(Duration::from_millis(10 - i)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    i
+                }
+            }),
+            3,
+        )
+        .await;
+
+        assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        assert_eq!(most_in_flight.load(Ordering::SeqCst), 3);
+    }
+}
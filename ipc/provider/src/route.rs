@@ -0,0 +1,74 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Funding a subnet more than one level below the source, one hop at a time.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use fvm_shared::clock::ChainEpoch;
+use ipc_api::subnet_id::SubnetID;
+
+/// How often the balance in an intermediate subnet is polled for a deposit.
+pub const ARRIVAL_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// How long a deposit has to arrive in an intermediate subnet before the route is abandoned.
+pub const ARRIVAL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// The progress of a multi-hop fund, reported as each hop completes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FundProgress {
+    /// The fund into `subnet` was included in its parent at `epoch`.
+    Funded { subnet: SubnetID, epoch: ChainEpoch },
+    /// Waiting for the deposit to be executed in `subnet` before funding the next hop.
+    Waiting { subnet: SubnetID },
+    /// The deposit was executed in `subnet`.
+    Arrived { subnet: SubnetID },
+}
+
+/// The subnets to fund, in order, to move funds from `source` down to `destination`.
+pub fn plan_fund_route(source: &SubnetID, destination: &SubnetID) -> Result<Vec<SubnetID>> {
+    let is_descendant = destination.children_as_ref().len() > source.children_as_ref().len()
+        && destination
+            .common_parent(source)
+            .map_or(false, |(_, common)| &common == source);
+    if !is_descendant {
+        return Err(anyhow!(
+            "{destination} is not a descendant of {source}, cannot fund it from there"
+        ));
+    }
+
+    let mut hops = vec![];
+    let mut current = source.clone();
+    while &current != destination {
+        current = destination
+            .down(&current)
+            .ok_or_else(|| anyhow!("cannot move down from {current} to {destination}"))?;
+        hops.push(current.clone());
+    }
+    Ok(hops)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ipc_api::subnet_id::SubnetID;
+
+    use super::plan_fund_route;
+
+    #[test]
+    fn test_plan_fund_route() {
+        let id = |s: &str| SubnetID::from_str(s).unwrap();
+
+        assert_eq!(
+            plan_fund_route(&id("/r314159"), &id("/r314159/t01/t02")).unwrap(),
+            vec![id("/r314159/t01"), id("/r314159/t01/t02")]
+        );
+        assert_eq!(
+            plan_fund_route(&id("/r314159/t01"), &id("/r314159/t01/t02")).unwrap(),
+            vec![id("/r314159/t01/t02")]
+        );
+        assert!(plan_fund_route(&id("/r314159/t01"), &id("/r314159/t03/t02")).is_err());
+        assert!(plan_fund_route(&id("/r314159/t01"), &id("/r314159")).is_err());
+        assert!(plan_fund_route(&id("/r314159"), &id("/r314159")).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Small key-value storage for the state of long-running processes, such as the checkpoint
+//! relayer, so they can pick up where they left off after a crash or a restart.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use ethers::utils::keccak256;
+use ipc_wallet::write_atomic;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The storage used by long-running processes to persist their state.
+pub trait StateStore: Send + Sync {
+    /// Returns the value stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing the previous value.
+    fn put(&self, key: &str, value: &[u8]) -> Result<()>;
+
+    /// Removes `key`, if it exists.
+    fn delete(&self, key: &str) -> Result<()>;
+
+    /// Returns the keys starting with `prefix`, in lexicographic order.
+    fn keys(&self, prefix: &str) -> Result<Vec<String>>;
+}
+
+/// Returns the JSON value stored under `key`, if any.
+pub fn get_json<T: DeserializeOwned>(store: &dyn StateStore, key: &str) -> Result<Option<T>> {
+    store
+        .get(key)?
+        .map(|bytes| serde_json::from_slice(&bytes))
+        .transpose()
+        .with_context(|| format!("cannot decode stored value of {key}"))
+}
+
+/// Stores `value` as JSON under `key`.
+pub fn put_json<T: Serialize>(store: &dyn StateStore, key: &str, value: &T) -> Result<()> {
+    store.put(key, &serde_json::to_vec(value)?)
+}
+
+/// A store keeping each key in its own file in a directory. Files are named after the hash of
+/// their key, as keys containing subnet ids can be longer than a file name may be, and hold the
+/// key before the value. Values are written to a temporary file, synced and renamed into place,
+/// so a crash never leaves a partially written value behind.
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Opens the store in `dir`, creating the directory if it does not exist.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs_err::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Opens the store in `dir` without touching the disk. The directory is created by the
+    /// first write, so a store that is only read works in a read-only location.
+    pub fn open_lazy(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(hex::encode(keccak256(key)))
+    }
+
+    /// Reads the key and the value stored in the file at `path`.
+    fn read(path: &Path) -> Result<Option<(String, Vec<u8>)>> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(anyhow!("cannot read {}: {e}", path.display())),
+        };
+        decode_entry(&bytes)
+            .map(Some)
+            .ok_or_else(|| anyhow!("{} is not a state store entry", path.display()))
+    }
+}
+
+/// The key length, the key and the value of an entry, as written to its file.
+fn encode_entry(key: &str, value: &[u8]) -> Vec<u8> {
+    let mut bytes = (key.len() as u32).to_be_bytes().to_vec();
+    bytes.extend_from_slice(key.as_bytes());
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+fn decode_entry(bytes: &[u8]) -> Option<(String, Vec<u8>)> {
+    let len = u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+    let key = bytes.get(4..4 + len)?;
+    let value = bytes.get(4 + len..)?;
+    Some((String::from_utf8(key.to_vec()).ok()?, value.to_vec()))
+}
+
+impl StateStore for FileStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match Self::read(&self.path(key)) {
+            Ok(entry) => Ok(entry.map(|(_, value)| value)),
+            Err(e) => Err(anyhow!("cannot read {key} from the state store: {e}")),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        fs_err::create_dir_all(&self.dir)?;
+        write_atomic(&self.path(key), &encode_entry(key, value))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(anyhow!("cannot delete {key} from the state store: {e}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = vec![];
+        if !self.dir.exists() {
+            return Ok(keys);
+        }
+        for entry in fs_err::read_dir(&self.dir)? {
+            let entry = entry?;
+            // skips temporary files and anything else that was not written by the store
+            let is_entry = entry
+                .file_name()
+                .to_str()
+                .map_or(false, |name| name.len() == 64 && hex::decode(name).is_ok());
+            if !is_entry {
+                continue;
+            }
+            // the entry may have been deleted since the directory was listed
+            if let Some((key, _)) = Self::read(&entry.path())? {
+                if key.starts_with(prefix) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// A store that keeps everything in memory, for tests and one-off runs.
+#[derive(Default)]
+pub struct MemoryStore {
+    values: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl StateStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.values.lock().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        self.values.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn keys(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .values
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_json, put_json, FileStore, StateStore};
+
+    #[test]
+    fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::open(dir.path()).unwrap();
+
+        put_json(&store, "relayer//r314159/t01/400", &42u64).unwrap();
+        put_json(&store, "relayer//r314159/t01/300", &41u64).unwrap();
+        put_json(&store, "other", &0u64).unwrap();
+
+        // values survive reopening the store
+        let store = FileStore::open(dir.path()).unwrap();
+        assert_eq!(
+            get_json::<u64>(&store, "relayer//r314159/t01/400").unwrap(),
+            Some(42)
+        );
+        assert_eq!(
+            store.keys("relayer/").unwrap(),
+            vec!["relayer//r314159/t01/300", "relayer//r314159/t01/400"]
+        );
+
+        store.delete("relayer//r314159/t01/400").unwrap();
+        store.delete("missing").unwrap();
+        assert_eq!(
+            get_json::<u64>(&store, "relayer//r314159/t01/400").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_file_store_long_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::open(dir.path()).unwrap();
+
+        // a subnet four levels deep, with f410 addresses at each level
+        let level = "/t410fkkzj2ek6l7uwctmwk3z7p4xl5yfnppfoi6ej5zy";
+        let key = format!("relayer//r314159{}/in-flight/400", level.repeat(4));
+        put_json(&store, &key, &42u64).unwrap();
+
+        assert_eq!(get_json::<u64>(&store, &key).unwrap(), Some(42));
+        assert_eq!(store.keys("relayer/").unwrap(), vec![key]);
+    }
+
+    #[test]
+    fn test_file_store_created_on_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::open_lazy(dir.path().join("state"));
+
+        assert_eq!(get_json::<u64>(&store, "key").unwrap(), None);
+        assert!(store.keys("").unwrap().is_empty());
+        assert!(!dir.path().join("state").exists());
+
+        put_json(&store, "key", &1u64).unwrap();
+        assert_eq!(get_json::<u64>(&store, "key").unwrap(), Some(1));
+    }
+}
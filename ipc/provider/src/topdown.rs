@@ -0,0 +1,99 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Guards against applying a top-down message twice when an RPC endpoint returns the same event
+//! more than once, out of order, or for overlapping ranges of heights.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use ipc_api::cross::IpcEnvelope;
+
+/// Drops the repeated copies of top-down messages and orders them by nonce. The gateway gives
+/// every top-down message of a subnet its own nonce, so two different messages with the same
+/// nonce mean the responses cannot be trusted and are reported as an error.
+pub fn dedup_top_down_msgs(msgs: Vec<IpcEnvelope>) -> Result<Vec<IpcEnvelope>> {
+    let mut by_nonce = BTreeMap::new();
+    for msg in msgs {
+        match by_nonce.get(&msg.local_nonce) {
+            Some(existing) if existing == &msg => continue,
+            Some(_) => {
+                return Err(anyhow!(
+                    "conflicting top down messages with nonce {}",
+                    msg.local_nonce
+                ))
+            }
+            None => {
+                by_nonce.insert(msg.local_nonce, msg);
+            }
+        }
+    }
+    Ok(by_nonce.into_values().collect())
+}
+
+/// Remembers the next top-down nonce expected for a subnet, so messages already seen in an
+/// earlier query are not yielded again.
+#[derive(Debug, Default, Clone)]
+pub struct TopDownNonceGuard {
+    next: Option<u64>,
+}
+
+impl TopDownNonceGuard {
+    /// Keeps the messages not admitted before, in nonce order.
+    pub fn admit(&mut self, msgs: Vec<IpcEnvelope>) -> Result<Vec<IpcEnvelope>> {
+        let msgs = dedup_top_down_msgs(msgs)?;
+        let fresh = msgs
+            .into_iter()
+            .filter(|m| self.next.map_or(true, |next| m.local_nonce >= next))
+            .collect::<Vec<_>>();
+        if let Some(last) = fresh.last() {
+            self.next = Some(last.local_nonce + 1);
+        }
+        Ok(fresh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::topdown::{dedup_top_down_msgs, TopDownNonceGuard};
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
+    use ipc_api::cross::IpcEnvelope;
+    use ipc_api::subnet_id::SubnetID;
+
+    fn msg(nonce: u64, value: u64) -> IpcEnvelope {
+        let subnet = SubnetID::new(123, vec![Address::new_id(1001)]);
+        let mut msg = IpcEnvelope::new_fund_msg(
+            &subnet,
+            &Address::new_id(100),
+            &Address::new_id(101),
+            TokenAmount::from_atto(value),
+        )
+        .unwrap();
+        msg.local_nonce = nonce;
+        msg
+    }
+
+    #[test]
+    fn test_dedup_top_down_msgs() {
+        let msgs = vec![msg(2, 20), msg(0, 0), msg(1, 10), msg(0, 0), msg(2, 20)];
+        let nonces = dedup_top_down_msgs(msgs)
+            .unwrap()
+            .iter()
+            .map(|m| m.local_nonce)
+            .collect::<Vec<_>>();
+        assert_eq!(nonces, vec![0, 1, 2]);
+
+        assert!(dedup_top_down_msgs(vec![msg(0, 0), msg(0, 1)]).is_err());
+    }
+
+    #[test]
+    fn test_nonce_guard() {
+        let mut guard = TopDownNonceGuard::default();
+        assert_eq!(guard.admit(vec![msg(1, 1), msg(0, 0)]).unwrap().len(), 2);
+        // an overlapping query only yields what is new
+        let fresh = guard.admit(vec![msg(1, 1), msg(2, 2)]).unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].local_nonce, 2);
+        assert!(guard.admit(vec![msg(0, 0)]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! The changes to the validator set of a subnet between two heights of its parent.
+
+use std::collections::BTreeMap;
+
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use ipc_api::staking::ValidatorInfo;
+
+/// A validator and its confirmed collateral at one of the heights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorStake {
+    pub validator: Address,
+    pub collateral: TokenAmount,
+    /// Whether the validator was active, rather than waiting.
+    pub active: bool,
+}
+
+/// A validator in the set at both heights whose collateral or activity changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakeChange {
+    pub validator: Address,
+    pub before: ValidatorStake,
+    pub after: ValidatorStake,
+}
+
+impl StakeChange {
+    /// The change in confirmed collateral, negative if the validator unstaked.
+    pub fn delta(&self) -> TokenAmount {
+        &self.after.collateral - &self.before.collateral
+    }
+}
+
+/// The validators that joined, left, or changed their stake between heights `from` and `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSetDiff {
+    pub from: ChainEpoch,
+    pub to: ChainEpoch,
+    pub joined: Vec<ValidatorStake>,
+    pub left: Vec<ValidatorStake>,
+    pub changed: Vec<StakeChange>,
+}
+
+impl ValidatorSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.joined.is_empty() && self.left.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares the validator set `before`, at height `from`, with the set `after`, at height `to`.
+/// Each list is ordered by validator address.
+pub fn diff_validator_set(
+    from: ChainEpoch,
+    before: &[(Address, ValidatorInfo)],
+    to: ChainEpoch,
+    after: &[(Address, ValidatorInfo)],
+) -> ValidatorSetDiff {
+    let before = stakes(before);
+    let mut after = stakes(after);
+
+    let mut diff = ValidatorSetDiff {
+        from,
+        to,
+        joined: vec![],
+        left: vec![],
+        changed: vec![],
+    };
+    for (validator, before) in before {
+        match after.remove(&validator) {
+            None => diff.left.push(before),
+            Some(after) if after != before => diff.changed.push(StakeChange {
+                validator,
+                before,
+                after,
+            }),
+            Some(_) => {}
+        }
+    }
+    diff.joined = after.into_values().collect();
+    diff
+}
+
+fn stakes(validators: &[(Address, ValidatorInfo)]) -> BTreeMap<Address, ValidatorStake> {
+    validators
+        .iter()
+        .map(|(validator, info)| {
+            let stake = ValidatorStake {
+                validator: *validator,
+                collateral: info.staking.confirmed_collateral().clone(),
+                active: info.is_active,
+            };
+            (*validator, stake)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff_validator_set;
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
+    use ipc_actors_abis::subnet_actor_getter_facet;
+    use ipc_api::staking::{ValidatorInfo, ValidatorStakingInfo};
+
+    fn validator(id: u64, collateral: u64, is_active: bool) -> (Address, ValidatorInfo) {
+        let staking = ValidatorStakingInfo::try_from(subnet_actor_getter_facet::ValidatorInfo {
+            federated_power: 0.into(),
+            confirmed_collateral: collateral.into(),
+            total_collateral: collateral.into(),
+            metadata: Default::default(),
+        })
+        .unwrap();
+        let info = ValidatorInfo {
+            staking,
+            is_active,
+            is_waiting: !is_active,
+        };
+        (Address::new_id(id), info)
+    }
+
+    #[test]
+    fn test_diff_validator_set() {
+        let before = vec![
+            validator(1, 10, true),
+            validator(2, 10, true),
+            validator(3, 5, false),
+        ];
+        let after = vec![
+            validator(1, 10, true),
+            validator(3, 15, true),
+            validator(4, 20, true),
+        ];
+
+        let diff = diff_validator_set(100, &before, 200, &after);
+
+        assert_eq!(diff.joined.len(), 1);
+        assert_eq!(diff.joined[0].validator, Address::new_id(4));
+        assert_eq!(diff.left.len(), 1);
+        assert_eq!(diff.left[0].validator, Address::new_id(2));
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].validator, Address::new_id(3));
+        assert_eq!(diff.changed[0].delta(), TokenAmount::from_atto(10));
+        assert!(!diff.changed[0].before.active && diff.changed[0].after.active);
+
+        assert!(diff_validator_set(100, &before, 200, &before).is_empty());
+    }
+}
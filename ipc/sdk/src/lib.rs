@@ -0,0 +1,54 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+//! Stable entry point for services integrating with IPC.
+//!
+//! Bots, explorers and other downstream services should depend on this crate instead of
+//! `ipc-provider`, `ipc-api` and `ipc-wallet` directly. Only the items re-exported here are part
+//! of the supported surface: internal modules of those crates can be moved or renamed between
+//! releases without a version bump of this crate, while anything removed or changed here is a
+//! breaking change.
+
+// The provider used to query and operate on subnets, and its configuration.
+pub use ipc_provider::{
+    builder::SubnetBuilder,
+    config::{Config, Subnet as SubnetConfig},
+    default_config_path, default_repo_path, IpcProvider,
+};
+
+/// Errors surfaced by the provider that callers may want to match on.
+pub mod error {
+    pub use ipc_api::error::Error;
+    pub use ipc_provider::DeadlineElapsed;
+}
+
+/// Identifiers and addresses of subnets and cross-net actors.
+pub mod id {
+    pub use ipc_api::address::IPCAddress;
+    pub use ipc_api::subnet_id::SubnetID;
+}
+
+/// Typed parameters and results of provider operations.
+pub mod types {
+    pub use ipc_api::staking::{StakingChangeRequest, ValidatorInfo};
+    pub use ipc_api::subnet::{Asset, AssetKind, ConsensusType, ConstructParams, PermissionMode};
+    pub use ipc_api::validator::Validator;
+    pub use ipc_provider::manager::{
+        Capabilities, Capability, FeeEstimate, Operation, SubnetGenesisInfo, SubnetInfo,
+    };
+    pub use ipc_provider::{CrossMsgDelivery, CrossMsgStatus};
+}
+
+/// Cross-net messages and the checkpoints that carry them.
+pub mod events {
+    pub use ipc_api::checkpoint::{
+        BottomUpCheckpoint, BottomUpCheckpointBundle, BottomUpMsgBatch, QuorumReachedEvent,
+    };
+    pub use ipc_api::cross::{IpcEnvelope, IpcMsgKind};
+}
+
+/// Key management.
+pub mod wallet {
+    pub use ipc_wallet::{
+        EthKeyAddress, EvmKeyStore, KeyStoreConfig, PersistentKeyStore, WalletType,
+    };
+}
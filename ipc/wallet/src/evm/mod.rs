@@ -53,6 +53,16 @@ impl KeyInfo {
     pub fn private_key(&self) -> &[u8] {
         &self.private_key
     }
+
+    /// A short identifier of the key, to compare keys without showing them: the first 8 bytes
+    /// of the blake2b hash of the uncompressed public key, hex encoded.
+    pub fn fingerprint(&self) -> Result<String> {
+        let sk = libsecp256k1::SecretKey::parse_slice(&self.private_key)
+            .map_err(|e| anyhow::anyhow!("invalid secp256k1 private key: {e}"))?;
+        let pk = libsecp256k1::PublicKey::from_secret_key(&sk).serialize();
+        let hash = blake2b_simd::Params::new().hash_length(32).hash(&pk);
+        Ok(hex::encode(&hash.as_bytes()[..8]))
+    }
 }
 
 impl Drop for KeyInfo {
@@ -5,21 +5,29 @@
 
 use crate::evm::memory::MemoryKeyStore;
 use crate::evm::{KeyInfo, KeyStore};
+use crate::lock::{write_atomic, KeystoreLock};
 use anyhow::anyhow;
 use anyhow::Result;
-use fs::File;
 use fs_err as fs;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::io::{BufReader, BufWriter, ErrorKind};
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use zeroize::Zeroize;
 
+/// The extension of the marker written next to a keystore once it was written with a checksum,
+/// so that a checksum that disappears from the keystore is noticed.
+const SEALED_EXTENSION: &str = "sealed";
+
 #[derive(Default)]
 pub struct PersistentKeyStore<T> {
     memory: MemoryKeyStore<T>,
     file_path: PathBuf,
+    /// Keys whose address in the file does not match their private key.
+    mismatched: HashSet<T>,
+    /// Set when the file does not match the checksum written with it.
+    checksum_error: Option<String>,
 }
 
 /// The persistent key information written to disk
@@ -51,12 +59,36 @@ impl Drop for PersistentKeyInfo {
     }
 }
 
+/// The keys written together with their checksum, so that both are replaced by the same rename.
+#[derive(Serialize, Deserialize)]
+struct SealedKeys {
+    checksum: String,
+    keys: Vec<PersistentKeyInfo>,
+}
+
+/// The contents of a keystore file. Keystores written before checksums were introduced are a
+/// plain list of keys.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PersistedKeys {
+    Sealed(SealedKeys),
+    Unsealed(Vec<PersistentKeyInfo>),
+}
+
 impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> KeyStore
     for PersistentKeyStore<T>
 {
     type Key = T;
 
     fn get(&self, addr: &Self::Key) -> Result<Option<KeyInfo>> {
+        self.ensure_intact()?;
+        if self.mismatched.contains(addr) {
+            return Err(anyhow!(
+                "refusing to use key {} from {:?}: its address does not match its private key",
+                addr.to_string(),
+                self.file_path
+            ));
+        }
         self.memory.get(addr)
     }
 
@@ -65,25 +97,19 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> KeyStore
     }
 
     fn put(&mut self, info: KeyInfo) -> Result<Self::Key> {
-        let addr = self.memory.put(info)?;
-        self.flush_no_encryption()?;
-        Ok(addr)
+        self.update(|memory| memory.put(info))
     }
 
     fn remove(&mut self, addr: &Self::Key) -> Result<()> {
-        self.memory.remove(addr)?;
-        self.flush_no_encryption()
+        self.update(|memory| memory.remove(addr))
     }
 
     fn set_default(&mut self, addr: &Self::Key) -> Result<()> {
-        self.memory.set_default(addr)?;
-        self.flush_no_encryption()
+        self.update(|memory| memory.set_default(addr))
     }
 
     fn get_default(&mut self) -> Result<Option<Self::Key>> {
-        let default = self.memory.get_default()?;
-        self.flush_no_encryption()?;
-        Ok(default)
+        self.update(|memory| memory.get_default())
     }
 }
 
@@ -95,34 +121,59 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> PersistentKey
             }
         }
 
-        let p = match File::open(&path) {
-            Ok(p) => p,
-            Err(e) => {
-                return if e.kind() == ErrorKind::NotFound {
-                    log::info!("key store does not exist, initialized to empty key store");
-                    Ok(Self {
-                        memory: MemoryKeyStore {
-                            data: Default::default(),
-                            default: None,
-                        },
-                        file_path: path,
-                    })
-                } else {
-                    Err(anyhow!("cannot create key store: {e:}"))
-                };
-            }
+        let mut store = Self {
+            memory: MemoryKeyStore {
+                data: Default::default(),
+                default: None,
+            },
+            file_path: path,
+            mismatched: Default::default(),
+            checksum_error: None,
         };
-        let reader = BufReader::new(p);
 
-        let persisted_key_info: Vec<PersistentKeyInfo> =
-            serde_json::from_reader(reader).map_err(|e| {
-                anyhow!(
-                    "failed to deserialize keyfile, initializing new keystore at: {:?} due to: {e:}",
-                    path
-                )
-            })?;
+        // not read while another process merges its changes into the file
+        let lock = KeystoreLock::acquire(&store.file_path)?;
+        let found = store.read()?;
+        drop(lock);
+        if !found {
+            log::info!("key store does not exist, initialized to empty key store");
+        }
+        if let Some(e) = &store.checksum_error {
+            log::warn!("{e}");
+        }
+
+        Ok(store)
+    }
+
+    /// Replaces the keys in memory with the ones in the file, if it exists. Must be called with
+    /// the keystore locked.
+    fn read(&mut self) -> Result<bool> {
+        let path = &self.file_path;
+        let contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.memory.data.clear();
+                self.memory.default = None;
+                self.mismatched.clear();
+                self.checksum_error = None;
+                return Ok(false);
+            }
+            Err(e) => return Err(anyhow!("cannot create key store: {e:}")),
+        };
+        let persisted: PersistedKeys = serde_json::from_slice(&contents).map_err(|e| {
+            anyhow!(
+                "failed to deserialize keyfile, initializing new keystore at: {:?} due to: {e:}",
+                path
+            )
+        })?;
+        let checksum_error = check_checksum(path, &persisted)?;
+        let persisted_key_info = match &persisted {
+            PersistedKeys::Sealed(sealed) => &sealed.keys,
+            PersistedKeys::Unsealed(keys) => keys,
+        };
 
         let mut key_infos = HashMap::new();
+        let mut mismatched = HashSet::new();
         for info in persisted_key_info.iter() {
             let key_info = KeyInfo {
                 private_key: hex::decode(&info.private_key)?,
@@ -132,6 +183,14 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> PersistentKey
             if info.address != addr.to_string() {
                 addr = T::try_from(key_info.clone())
                     .map_err(|_| anyhow!("cannot convert private key to address"))?;
+                if !info.address.eq_ignore_ascii_case(&addr.to_string()) {
+                    log::warn!(
+                        "key {} in {path:?} is stored under address {}",
+                        addr.to_string(),
+                        info.address
+                    );
+                    mismatched.insert(addr.clone());
+                }
             }
 
             key_infos.insert(addr, key_info);
@@ -145,17 +204,70 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> PersistentKey
             None => None,
         };
 
-        Ok(Self {
-            memory: MemoryKeyStore {
-                data: key_infos,
-                default,
-            },
-            file_path: path,
-        })
+        self.memory = MemoryKeyStore {
+            data: key_infos,
+            default,
+        };
+        self.mismatched = mismatched;
+        self.checksum_error = checksum_error;
+        Ok(true)
+    }
+
+    /// Applies `f` to the keys as they are in the file and writes them back, holding the lock
+    /// throughout, so the keys another process wrote since this keystore was loaded are kept.
+    fn update<R>(&mut self, f: impl FnOnce(&mut MemoryKeyStore<T>) -> Result<R>) -> Result<R> {
+        let _lock = KeystoreLock::acquire(&self.file_path)?;
+        self.read()?;
+        let result = f(&mut self.memory)?;
+        self.flush_no_encryption()?;
+        Ok(result)
+    }
+
+    /// The problems found when the keystore was loaded: a file that does not match its
+    /// checksum, or keys stored under another address. Keys cannot be used until they are
+    /// resolved, or accepted with [`PersistentKeyStore::reseal`].
+    pub fn verify(&self) -> Vec<String> {
+        let mut problems = vec![];
+        if let Some(e) = &self.checksum_error {
+            problems.push(e.clone());
+        }
+        for addr in &self.mismatched {
+            problems.push(format!(
+                "key {} is stored under another address",
+                addr.to_string()
+            ));
+        }
+        problems
+    }
+
+    /// Accepts the keys as they are, storing each under the address of its private key, and
+    /// writes a new checksum.
+    pub fn reseal(&mut self) -> Result<()> {
+        let _lock = KeystoreLock::acquire(&self.file_path)?;
+        self.read()?;
+        self.checksum_error = None;
+        self.mismatched.clear();
+        self.flush_no_encryption()
+    }
+
+    fn ensure_intact(&self) -> Result<()> {
+        match &self.checksum_error {
+            Some(e) => Err(anyhow!("refusing to use the keystore: {e}")),
+            None => Ok(()),
+        }
     }
 
-    /// Write all keys to file without any encryption.
+    /// Write all keys to file without any encryption. Must be called with the keystore locked.
     fn flush_no_encryption(&self) -> Result<()> {
+        // rewriting the file would seal whatever was tampered with
+        self.ensure_intact()?;
+        if !self.mismatched.is_empty() {
+            return Err(anyhow!(
+                "refusing to write {:?}, it has keys stored under another address",
+                self.file_path
+            ));
+        }
+
         let dir = self
             .file_path
             .parent()
@@ -163,12 +275,8 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> PersistentKey
 
         fs::create_dir_all(dir)?;
 
-        let file = File::create(&self.file_path)?;
-
         // TODO: do we need to set path permission?
 
-        let writer = BufWriter::new(file);
-
         let to_persist = self
             .memory
             .data
@@ -183,13 +291,55 @@ impl<T: Clone + Eq + Hash + TryFrom<KeyInfo> + Default + ToString> PersistentKey
             })
             .collect::<Vec<_>>();
 
-        serde_json::to_writer_pretty(writer, &to_persist)
+        let sealed = SealedKeys {
+            checksum: checksum(&to_persist)?,
+            keys: to_persist,
+        };
+        let contents = serde_json::to_vec_pretty(&sealed)
             .map_err(|e| anyhow!("failed to serialize and write key info: {e}"))?;
+        write_atomic(&self.file_path, &contents)?;
+
+        let sealed_path = sealed_path(&self.file_path);
+        if !sealed_path.exists() {
+            write_atomic(&sealed_path, &[])?;
+        }
 
         Ok(())
     }
 }
 
+fn sealed_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(SEALED_EXTENSION);
+    PathBuf::from(path)
+}
+
+fn checksum(keys: &[PersistentKeyInfo]) -> Result<String> {
+    Ok(blake2b_simd::Params::new()
+        .hash_length(32)
+        .hash(&serde_json::to_vec(keys)?)
+        .to_hex()
+        .to_string())
+}
+
+/// Compares the keys with the checksum written with them. Keystores written before checksums
+/// were introduced get one on their next write, but once a keystore was written with a checksum
+/// it must keep carrying one.
+fn check_checksum(path: &Path, persisted: &PersistedKeys) -> Result<Option<String>> {
+    match persisted {
+        PersistedKeys::Sealed(sealed) if sealed.checksum != checksum(&sealed.keys)? => Ok(Some(
+            format!(
+                "{path:?} does not match its checksum, it was modified outside ipc-cli or is corrupted"
+            ),
+        )),
+        PersistedKeys::Unsealed(_) if sealed_path(path).exists() => Ok(Some(format!(
+            "{path:?} lost the checksum it was written with, it was modified outside ipc-cli"
+        ))),
+        _ => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::evm::KeyInfo;
@@ -250,6 +400,92 @@ mod tests {
         assert_eq!(key_from_store.unwrap(), key_info);
     }
 
+    #[test]
+    fn test_concurrent_writers_keep_keys() {
+        let keystore_folder = tempfile::tempdir().unwrap().into_path();
+        let keystore_location = keystore_folder.join("eth_keystore");
+
+        // e.g. a relayer and a manual cli invocation, both loaded before either wrote
+        let mut first = PersistentKeyStore::<Key>::new(keystore_location.clone()).unwrap();
+        let mut second = PersistentKeyStore::<Key>::new(keystore_location.clone()).unwrap();
+
+        let first_key = KeyInfo {
+            private_key: vec![0, 1, 2],
+        };
+        let second_key = KeyInfo {
+            private_key: vec![0, 1, 3],
+        };
+        let first_addr = first.put(first_key.clone()).unwrap();
+        let second_addr = second.put(second_key.clone()).unwrap();
+
+        let ks = PersistentKeyStore::<Key>::new(keystore_location).unwrap();
+        assert_eq!(ks.get(&first_addr).unwrap(), Some(first_key));
+        assert_eq!(ks.get(&second_addr).unwrap(), Some(second_key));
+    }
+
+    #[test]
+    fn test_tampered_keystore() {
+        let keystore_folder = tempfile::tempdir().unwrap().into_path();
+        let keystore_location = keystore_folder.join("eth_keystore");
+
+        let mut ks = PersistentKeyStore::new(keystore_location.clone()).unwrap();
+        let key_info = KeyInfo {
+            private_key: vec![0, 1, 2],
+        };
+        let addr = Key::try_from(key_info.clone()).unwrap();
+        ks.put(key_info.clone()).unwrap();
+
+        let ks = PersistentKeyStore::<Key>::new(keystore_location.clone()).unwrap();
+        assert!(ks.verify().is_empty());
+
+        // swap the private key for another one, under the same address
+        let contents = std::fs::read_to_string(&keystore_location).unwrap();
+        std::fs::write(
+            &keystore_location,
+            contents.replace("\"private_key\": \"000102\"", "\"private_key\": \"000103\""),
+        )
+        .unwrap();
+
+        let mut ks = PersistentKeyStore::<Key>::new(keystore_location.clone()).unwrap();
+        assert_eq!(ks.verify().len(), 2);
+        assert!(ks.get(&addr).is_err());
+        let mut other = PersistentKeyStore::<Key>::new(keystore_location.clone()).unwrap();
+        assert!(other.put(key_info.clone()).is_err());
+
+        ks.reseal().unwrap();
+        let ks = PersistentKeyStore::<Key>::new(keystore_location).unwrap();
+        assert!(ks.verify().is_empty());
+        assert_eq!(ks.get(&addr).unwrap(), None);
+        assert!(ks
+            .get(&Key {
+                data: "000103".to_string()
+            })
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_stripped_checksum() {
+        let keystore_folder = tempfile::tempdir().unwrap().into_path();
+        let keystore_location = keystore_folder.join("eth_keystore");
+
+        let mut ks = PersistentKeyStore::new(keystore_location.clone()).unwrap();
+        let key_info = KeyInfo {
+            private_key: vec![0, 1, 2],
+        };
+        let addr = Key::try_from(key_info.clone()).unwrap();
+        ks.put(key_info).unwrap();
+
+        // rewrite the keystore as a plain list of keys, as written before checksums
+        let contents: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&keystore_location).unwrap()).unwrap();
+        std::fs::write(&keystore_location, contents["keys"].to_string()).unwrap();
+
+        let ks = PersistentKeyStore::<Key>::new(keystore_location).unwrap();
+        assert_eq!(ks.verify().len(), 1);
+        assert!(ks.get(&addr).is_err());
+    }
+
     #[test]
     fn test_default() {
         let keystore_folder = tempfile::tempdir().unwrap().into_path();
@@ -7,7 +7,7 @@ use fs::{create_dir, File};
 use fs_err as fs;
 use std::{
     fmt::Display,
-    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    io::{BufReader, ErrorKind, Read},
     path::{Path, PathBuf},
 };
 
@@ -27,6 +27,7 @@ use xsalsa20poly1305::{
 };
 
 use super::errors::Error;
+use crate::lock::{write_atomic, KeystoreLock};
 
 pub const KEYSTORE_NAME: &str = "keystore.json";
 pub const ENCRYPTED_KEYSTORE_NAME: &str = "keystore";
@@ -331,6 +332,21 @@ impl KeyStore {
     }
 
     pub fn flush(&self) -> anyhow::Result<()> {
+        match &self.persistence {
+            Some(persistent_keystore) => {
+                let _lock = KeystoreLock::acquire(&persistent_keystore.file_path)?;
+                self.write()
+            }
+            None => {
+                // NoOp for MemKeyStore
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the keys to the file of a persistent keystore. Must be called with the keystore
+    /// locked.
+    fn write(&self) -> anyhow::Result<()> {
         match &self.persistence {
             Some(persistent_keystore) => {
                 let dir = persistent_keystore
@@ -338,15 +354,8 @@ impl KeyStore {
                     .parent()
                     .ok_or_else(|| Error::Other("Invalid Path".to_string()))?;
                 fs::create_dir_all(dir)?;
-                let file = File::create(&persistent_keystore.file_path)?;
 
-                // Restrict permissions on files containing private keys
-                #[cfg(unix)]
-                crate::utils::set_user_perm(&file)?;
-
-                let mut writer = BufWriter::new(file);
-
-                match &self.encryption {
+                let contents = match &self.encryption {
                     Some(encrypted_keystore) => {
                         // Flush For EncryptedKeyStore
                         let data = serde_ipld_dagcbor::to_vec(&self.key_info).map_err(|e| {
@@ -357,9 +366,7 @@ impl KeyStore {
                             EncryptedKeyStore::encrypt(&encrypted_keystore.encryption_key, &data)?;
                         let mut salt_vec = encrypted_keystore.salt.to_vec();
                         salt_vec.extend(encrypted_data);
-                        writer.write_all(&salt_vec)?;
-
-                        Ok(())
+                        salt_vec
                     }
                     None => {
                         let mut key_info: HashMap<String, PersistentKeyInfo> = HashMap::new();
@@ -374,19 +381,64 @@ impl KeyStore {
                         }
 
                         // Flush for PersistentKeyStore
-                        serde_json::to_writer_pretty(writer, &key_info).map_err(|e| {
+                        serde_json::to_vec_pretty(&key_info).map_err(|e| {
                             Error::Other(format!("failed to serialize and write key info: {e}"))
-                        })?;
-
-                        Ok(())
+                        })?
                     }
+                };
+
+                // Restricts permissions on the file, which contains private keys, and keeps other
+                // processes sharing the keystore from seeing it half written
+                write_atomic(&persistent_keystore.file_path, &contents)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Locks the file of a persistent keystore and reloads the keys from it, so that a change
+    /// written back under the same lock keeps the keys other processes wrote since it was opened.
+    fn lock_and_reload(&mut self) -> anyhow::Result<Option<KeystoreLock>> {
+        let Some(persistent_keystore) = &self.persistence else {
+            return Ok(None);
+        };
+        let lock = KeystoreLock::acquire(&persistent_keystore.file_path)?;
+
+        let contents = match fs::read(&persistent_keystore.file_path) {
+            Ok(contents) if !contents.is_empty() => contents,
+            Ok(_) => return Ok(Some(lock)),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Some(lock)),
+            Err(e) => return Err(e.into()),
+        };
+        self.key_info = match &self.encryption {
+            Some(encrypted_keystore) => {
+                if contents.len() < RECOMMENDED_SALT_LEN + NONCE_SIZE {
+                    anyhow::bail!("encrypted keystore is truncated");
+                }
+                let (salt, data) = contents.split_at(RECOMMENDED_SALT_LEN);
+                if salt != encrypted_keystore.salt {
+                    anyhow::bail!("the keystore was recreated by another process, open it again");
                 }
+                let decrypted_data =
+                    EncryptedKeyStore::decrypt(&encrypted_keystore.encryption_key, data)?;
+                serde_ipld_dagcbor::from_slice(&decrypted_data)?
             }
             None => {
-                // NoOp for MemKeyStore
-                Ok(())
+                let persisted_key_info: HashMap<String, PersistentKeyInfo> =
+                    serde_json::from_slice(&contents)?;
+                persisted_key_info
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let private_key = BASE64_STANDARD.decode(&value.private_key)?;
+                        let key_info = KeyInfo {
+                            private_key,
+                            key_type: value.key_type,
+                        };
+                        Ok((key, key_info))
+                    })
+                    .collect::<anyhow::Result<_>>()?
             }
-        }
+        };
+        Ok(Some(lock))
     }
 
     /// Return all of the keys that are stored in the `KeyStore`
@@ -401,13 +453,16 @@ impl KeyStore {
 
     /// Save a key/`KeyInfo` pair to the `KeyStore`
     pub fn put(&mut self, key: String, key_info: KeyInfo) -> Result<(), Error> {
+        let _lock = self
+            .lock_and_reload()
+            .map_err(|err| Error::Other(err.to_string()))?;
         if self.key_info.contains_key(&key) {
             return Err(Error::KeyExists);
         }
         self.key_info.insert(key, key_info);
 
         if self.persistence.is_some() {
-            self.flush().map_err(|err| Error::Other(err.to_string()))?;
+            self.write().map_err(|err| Error::Other(err.to_string()))?;
         }
 
         Ok(())
@@ -415,10 +470,11 @@ impl KeyStore {
 
     /// Remove the key and corresponding `KeyInfo` from the `KeyStore`
     pub fn remove(&mut self, key: String) -> anyhow::Result<KeyInfo> {
+        let _lock = self.lock_and_reload()?;
         let key_out = self.key_info.remove(&key).ok_or(Error::KeyInfo)?;
 
         if self.persistence.is_some() {
-            self.flush()?;
+            self.write()?;
         }
 
         Ok(key_out)
@@ -608,6 +664,26 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_concurrent_writers_keep_keys() -> Result<()> {
+        let keystore_location = tempfile::tempdir()?.into_path();
+        let mut first = KeyStore::new(KeyStoreConfig::Persistent(keystore_location.clone()))?;
+        let mut second = KeyStore::new(KeyStoreConfig::Persistent(keystore_location.clone()))?;
+
+        let first_key = wallet::generate_key(SignatureType::Secp256k1)?;
+        let second_key = wallet::generate_key(SignatureType::Secp256k1)?;
+        first.put(format!("wallet-{}", first_key.address), first_key.key_info)?;
+        second.put(
+            format!("wallet-{}", second_key.address),
+            second_key.key_info,
+        )?;
+
+        let ks_read = KeyStore::new(KeyStoreConfig::Persistent(keystore_location))?;
+        ensure!(ks_read.list().len() == 2);
+
+        Ok(())
+    }
+
     impl quickcheck::Arbitrary for KeyInfo {
         fn arbitrary(g: &mut quickcheck::Gen) -> Self {
             let sigtype = g
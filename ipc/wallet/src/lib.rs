@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 
 mod evm;
 mod fvm;
+mod lock;
 
 #[cfg(feature = "with-ethers")]
 pub use crate::evm::{random_eth_key_info, EthKeyAddress};
@@ -16,6 +17,7 @@ pub use crate::evm::{
     DEFAULT_KEYSTORE_NAME,
 };
 pub use crate::fvm::*;
+pub use crate::lock::{set_lock_wait, write_atomic, DEFAULT_LOCK_WAIT};
 
 /// WalletType determines the kind of keys and wallets
 /// supported in the keystore
@@ -0,0 +1,166 @@
+// Copyright 2022-2024 Protocol Labs
+// SPDX-License-Identifier: MIT
+
+//! Keystore files shared between processes.
+//!
+//! A relayer daemon and manual ipc-cli invocations can open the same keystore at once. Writers
+//! take an exclusive lock on a `.lock` file next to the keystore, and replace the keystore with
+//! a write to a temporary file followed by a rename. Readers never see a half-written file. The
+//! lock is also taken within the process, so threads sharing a keystore are serialized too.
+//!
+//! Writers hold the lock from reading the keystore to writing it back, so keys added by another
+//! process in the meantime are merged rather than overwritten.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use fs_err as fs;
+use std::io::Write;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// How long to wait for another process to release a keystore by default.
+pub const DEFAULT_LOCK_WAIT: Duration = Duration::from_secs(10);
+/// How often a held lock is retried.
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Serializes the keystore access of the threads of this process.
+static IN_PROCESS: Mutex<()> = Mutex::new(());
+/// The longest time to wait for a lock, `None` to wait as long as it takes.
+static LOCK_WAIT: Mutex<Option<Duration>> = Mutex::new(Some(DEFAULT_LOCK_WAIT));
+
+/// Sets how long to wait for a keystore locked by another process, `None` to wait until it is
+/// released.
+pub fn set_lock_wait(wait: Option<Duration>) {
+    *LOCK_WAIT.lock().unwrap_or_else(|e| e.into_inner()) = wait;
+}
+
+fn lock_wait() -> Option<Duration> {
+    *LOCK_WAIT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// An exclusive lock on a keystore file, released when dropped.
+pub struct KeystoreLock {
+    _file: fs::File,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl KeystoreLock {
+    /// Locks the keystore at `path`, waiting for other processes as set with [`set_lock_wait`].
+    pub fn acquire(path: &Path) -> Result<Self> {
+        // Waiting blocks the thread. On the worker of a multi-threaded runtime, such as the one
+        // of ipc-cli, the other tasks of the worker are handed to other threads in the meantime.
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| Self::acquire_blocking(path))
+            }
+            _ => Self::acquire_blocking(path),
+        }
+    }
+
+    fn acquire_blocking(path: &Path) -> Result<Self> {
+        let guard = IN_PROCESS.lock().unwrap_or_else(|e| e.into_inner());
+
+        let lock_path = with_suffix(path, ".lock");
+        if let Some(dir) = lock_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)?;
+
+        let wait = lock_wait();
+        let start = Instant::now();
+        while !try_lock(&file)? {
+            if wait.map_or(false, |wait| start.elapsed() >= wait) {
+                return Err(anyhow!(
+                    "keystore {path:?} is locked by another process, retry or pass --wait-for-lock"
+                ));
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+
+        Ok(Self {
+            _file: file,
+            _guard: guard,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn try_lock(file: &fs::File) -> Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    // the lock is released when the file is closed
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let e = std::io::Error::last_os_error();
+    match e.raw_os_error() {
+        Some(libc::EWOULDBLOCK) => Ok(false),
+        _ => Err(e).context("failed to lock keystore"),
+    }
+}
+
+#[cfg(not(unix))]
+fn try_lock(_file: &fs::File) -> Result<bool> {
+    Ok(true)
+}
+
+/// Replaces the file at `path` with `contents`, so it holds either the old or the new contents
+/// even if the process dies halfway. The file is only readable by the user.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let tmp = with_suffix(path, &format!(".tmp.{}", std::process::id()));
+    let mut file = fs::File::create(&tmp)?;
+    #[cfg(unix)]
+    crate::utils::set_user_perm(&file)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path.as_os_str());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_atomic, KeystoreLock};
+
+    #[test]
+    fn test_write_atomic_under_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+
+        {
+            let _lock = KeystoreLock::acquire(&path).unwrap();
+            write_atomic(&path, b"[]").unwrap();
+            write_atomic(&path, b"[1]").unwrap();
+        }
+        // released on drop
+        let _lock = KeystoreLock::acquire(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"[1]");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_acquire_within_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+
+        let lock = KeystoreLock::acquire(&path).unwrap();
+        let waiting = tokio::spawn(async move { KeystoreLock::acquire(&path).map(|_| ()) });
+        // the waiting task does not keep this one from running and releasing the lock
+        tokio::task::yield_now().await;
+        drop(lock);
+        waiting.await.unwrap().unwrap();
+    }
+}
@@ -132,7 +132,7 @@ make build
 echo "$DASHES Using 3 address in wallet..."
 for i in {0..2}
 do
-  addr=$(cat ${IPC_CONFIG_FOLDER}/evm_keystore.json | jq .[$i].address | tr -d '"')
+  addr=$(cat ${IPC_CONFIG_FOLDER}/evm_keystore.json | jq "(if type == \"array\" then . else .keys end)[$i].address" | tr -d '"')
   wallet_addresses+=($addr)
   echo "Wallet $i address: $addr"
 done